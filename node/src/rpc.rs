@@ -27,6 +27,7 @@ use std::sync::Arc;
 use eth::EthDeps;
 use futures::channel::mpsc;
 use jsonrpsee::RpcModule;
+use pallet_emission0_rpc::{Emission0ApiServer, Emission0Rpc};
 use pallet_permission0_rpc::{Permission0Rpc, Permission0StreamApiServer};
 use pallet_torus0_rpc::{Torus0ApiServer, Torus0Rpc};
 use polkadot_sdk::{
@@ -87,6 +88,7 @@ where
 
     io.merge(System::new(client.clone(), pool).into_rpc())?;
     io.merge(TransactionPayment::new(client.clone()).into_rpc())?;
+    io.merge(Emission0Rpc::new(client.clone()).into_rpc())?;
     io.merge(Permission0Rpc::new(client.clone()).into_rpc())?;
     io.merge(Torus0Rpc::new(client.clone()).into_rpc())?;
 
@@ -0,0 +1,21 @@
+#![allow(clippy::multiple_bound_locations)]
+
+use codec::{Decode, Encode};
+use polkadot_sdk::sp_std::vec::Vec;
+
+use crate::{EpochReport, EpochSimulation};
+
+polkadot_sdk::sp_api::decl_runtime_apis! {
+    /// RPC related to Emission0.
+    pub trait Emission0RuntimeApi<AccountId: Encode + Decode, Balance: Decode, BlockNumber: Decode> {
+        /// Returns the reports of the last `count` distributed epochs, from
+        /// the most recent to the oldest. Only the epochs still kept on-chain
+        /// are returned.
+        fn epoch_reports(count: u32) -> Vec<(u64, EpochReport<AccountId, Balance, BlockNumber>)>;
+
+        /// Simulates the distribution of the current pending emission as if
+        /// the epoch ran now, without mutating storage. Each entry in
+        /// `weight_overrides` replaces the weights set by that agent.
+        fn simulate_epoch(weight_overrides: Vec<(AccountId, Vec<(AccountId, u16)>)>) -> EpochSimulation<AccountId, Balance>;
+    }
+}
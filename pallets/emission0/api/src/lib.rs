@@ -1,6 +1,10 @@
 #![no_std]
 
-use polkadot_sdk::frame_support::dispatch::DispatchResult;
+use codec::{Decode, Encode, MaxEncodedLen};
+use polkadot_sdk::{frame_support::dispatch::DispatchResult, sp_std::vec::Vec};
+use scale_info::TypeInfo;
+
+pub mod api;
 
 #[derive(Default)]
 pub struct ConsensusMemberStats {
@@ -8,6 +12,58 @@ pub struct ConsensusMemberStats {
     pub dividends: u16,
 }
 
+/// Summary of a single distributed epoch.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct EpochReport<AccountId, Balance, BlockNumber> {
+    /// Block in which the epoch was distributed.
+    pub block: BlockNumber,
+    /// Total amount issued for this epoch, including the treasury cut.
+    pub emitted: Balance,
+    /// Amount sent to the DAO treasury.
+    pub treasury_fee: Balance,
+    /// Amount not distributed, returned to the pending emission.
+    pub remaining: Balance,
+    /// Amounts received by each agent in this epoch.
+    pub agents: Vec<AgentEmission<AccountId, Balance>>,
+}
+
+/// Amounts received by an agent in a single epoch.
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, PartialEq, Eq, Debug)]
+pub struct AgentEmission<AccountId, Balance> {
+    pub agent_id: AccountId,
+    pub incentives: Balance,
+    /// Dividends distributed to the agent and its stakers, after
+    /// weight control fees.
+    pub dividends: Balance,
+}
+
+/// Result of simulating the distribution of an epoch.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct EpochSimulation<AccountId, Balance> {
+    /// Pending emission that would be distributed, including the treasury cut.
+    pub emission: Balance,
+    /// Amount that would be sent to the DAO treasury.
+    pub treasury_fee: Balance,
+    /// Amounts each consensus member would receive.
+    pub agents: Vec<AgentSimulation<AccountId, Balance>>,
+}
+
+/// Simulated amounts for a single consensus member.
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct AgentSimulation<AccountId, Balance> {
+    pub agent_id: AccountId,
+    pub incentives: Balance,
+    /// Dividends after weight control fees.
+    pub dividends: Balance,
+    /// Dividends paid to each staker of the agent, after staking fees.
+    pub stakers: Vec<(AccountId, Balance)>,
+    /// Amount staked to the agent itself: its incentives and the dividends
+    /// not paid to stakers.
+    pub agent_payout: Balance,
+}
+
 pub trait Emission0Api<AccountId> {
     /// Fetches stats emitted by the consensus for an agent.
     /// Returns `None` if the agent has not taken part in the last consensus
@@ -15,4 +71,12 @@ pub trait Emission0Api<AccountId> {
     fn consensus_stats(member: &AccountId) -> Option<ConsensusMemberStats>;
 
     fn delegate_weight_control(delegator: &AccountId, delegatee: &AccountId) -> DispatchResult;
+
+    /// Number of records of other agents [`Self::rotate_key`] rewrites when
+    /// rotating the key.
+    fn key_references(key: &AccountId) -> u32;
+
+    /// Moves the consensus membership, weight control delegations and bonds
+    /// of an agent key to the key replacing it.
+    fn rotate_key(old_key: &AccountId, new_key: &AccountId);
 }
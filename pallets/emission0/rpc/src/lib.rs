@@ -0,0 +1,56 @@
+use std::sync::Arc;
+
+use jsonrpsee::{core::RpcResult, types::ErrorObject};
+use pallet_emission0_api::{api::Emission0RuntimeApi, EpochSimulation};
+use polkadot_sdk::{
+    sp_api::ProvideRuntimeApi,
+    sp_blockchain::HeaderBackend,
+    sp_runtime::{
+        traits::{IdentifyAccount, Verify},
+        MultiSignature,
+    },
+};
+use torus_runtime::opaque::Block;
+
+type Signature = MultiSignature;
+type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+type Balance = u128;
+type BlockNumber = u64;
+
+#[jsonrpsee::proc_macros::rpc(client, server)]
+pub trait Emission0Api {
+    #[method(name = "emission0_simulateEpoch")]
+    async fn simulate_epoch(
+        &self,
+        weight_overrides: Option<Vec<(AccountId, Vec<(AccountId, u16)>)>>,
+    ) -> RpcResult<EpochSimulation<AccountId, Balance>>;
+}
+
+pub struct Emission0Rpc<Client> {
+    client: Arc<Client>,
+}
+
+impl<Client> Emission0Rpc<Client> {
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait::async_trait]
+impl<Client> Emission0ApiServer for Emission0Rpc<Client>
+where
+    Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+    Client::Api: Emission0RuntimeApi<Block, AccountId, Balance, BlockNumber>,
+{
+    async fn simulate_epoch(
+        &self,
+        weight_overrides: Option<Vec<(AccountId, Vec<(AccountId, u16)>)>>,
+    ) -> RpcResult<EpochSimulation<AccountId, Balance>> {
+        let runtime = &*self.client.runtime_api();
+        let at = self.client.info().best_hash;
+
+        runtime
+            .simulate_epoch(at, weight_overrides.unwrap_or_default())
+            .map_err(|err| ErrorObject::owned(1, "Runtime execution failed", Some(err.to_string())))
+    }
+}
@@ -10,40 +10,78 @@ use polkadot_sdk::{
 
 use crate::*;
 
+/// Registers two agents, the second being an allocator staked enough to set
+/// weights on the first.
+fn weight_setter<T: Config>() -> (T::AccountId, T::AccountId) {
+    let module_key: T::AccountId = account("agent", 0, 2);
+    let module_key2: T::AccountId = account("agent2", 0, 3);
+
+    <T::Torus>::force_register_agent(&module_key, b"agent".to_vec(), vec![], vec![])
+        .expect("failed to register agent");
+    <T::Torus>::force_register_agent(&module_key2, b"agent2".to_vec(), vec![], vec![])
+        .expect("failed to register agent");
+
+    <T::Governance>::force_set_whitelisted(&module_key);
+    <T::Governance>::force_set_whitelisted(&module_key2);
+
+    <T::Governance>::set_allocator(&module_key2);
+    let _ = <T::Currency>::deposit_creating(&module_key2, <T::Torus>::min_validator_stake() * 2);
+
+    <T::Torus>::force_set_stake(
+        &module_key2,
+        &module_key2,
+        <T::Torus>::min_validator_stake(),
+    )
+    .unwrap();
+
+    (module_key, module_key2)
+}
+
 #[benchmarks]
 mod benchmarks {
     use super::*;
 
     #[benchmark]
     fn set_weights() {
-        let module_key: T::AccountId = account("agent", 0, 2);
-        let module_key2: T::AccountId = account("agent2", 0, 3);
+        let (module_key, module_key2) = weight_setter::<T>();
+        let weights = vec![(module_key, 10)];
 
-        <T::Governance>::set_allocator(&module_key2);
+        #[extrinsic_call]
+        set_weights(RawOrigin::Signed(module_key2), weights)
+    }
 
-        <T::Torus>::force_register_agent(&module_key, b"agent".to_vec(), vec![], vec![])
-            .expect("failed to register agent");
-        <T::Torus>::force_register_agent(&module_key2, b"agent2".to_vec(), vec![], vec![])
-            .expect("failed to register agent");
+    #[benchmark]
+    fn commit_weights() {
+        let (module_key, module_key2) = weight_setter::<T>();
+        WeightsCommitReveal::<T>::put(true);
 
-        <T::Governance>::force_set_whitelisted(&module_key);
-        <T::Governance>::force_set_whitelisted(&module_key2);
+        let hash =
+            weight_control::weights_commit_hash::<T>(&module_key2, &[(module_key, 10)], b"salt");
 
-        <T::Governance>::set_allocator(&module_key2);
-        let _ =
-            <T::Currency>::deposit_creating(&module_key2, <T::Torus>::min_validator_stake() * 2);
+        #[extrinsic_call]
+        commit_weights(RawOrigin::Signed(module_key2), hash)
+    }
 
-        <T::Torus>::force_set_stake(
-            &module_key2,
-            &module_key2,
-            <T::Torus>::min_validator_stake(),
-        )
-        .unwrap();
+    #[benchmark]
+    fn reveal_weights() {
+        let (module_key, module_key2) = weight_setter::<T>();
+        WeightsCommitReveal::<T>::put(true);
 
         let weights = vec![(module_key, 10)];
+        let salt = b"salt".to_vec();
+
+        // Commits in the first epoch and reveals in the following one.
+        WeightCommits::<T>::insert(
+            &module_key2,
+            WeightCommit::<T> {
+                hash: weight_control::weights_commit_hash::<T>(&module_key2, &weights, &salt),
+                block: 0u32.into(),
+            },
+        );
+        frame_system::Pallet::<T>::set_block_number(<T::Torus>::reward_interval().into());
 
         #[extrinsic_call]
-        set_weights(RawOrigin::Signed(module_key2), weights)
+        reveal_weights(RawOrigin::Signed(module_key2), weights, salt)
     }
 
     #[benchmark]
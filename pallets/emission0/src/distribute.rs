@@ -22,7 +22,10 @@ use polkadot_sdk::{
     sp_tracing::{error, info},
 };
 
-use crate::{BalanceOf, Config, ConsensusMember, IncentivesRatio, NegativeImbalanceOf, Weights};
+use crate::{
+    BalanceOf, Config, ConsensusMember, EpochAgents, EpochReport, IncentivesRatio,
+    NegativeImbalanceOf, ValidatorBonds, WeightControlShares, Weights,
+};
 
 mod math;
 
@@ -46,6 +49,8 @@ pub(super) fn distribute_emission<T: Config>(block_number: BlockNumberFor<T>) {
         return;
     }
 
+    crate::weight_control::clear_expired_commits::<T>(block_number);
+
     // The distribution is wrapped in a transaction.
     // If the consensus, for some reason, did not emit the entire pending emission,
     // it will go back to the accumulator and remove from total issuance.
@@ -54,16 +59,41 @@ pub(super) fn distribute_emission<T: Config>(block_number: BlockNumberFor<T>) {
         let emission = T::Currency::issue(emission);
         info!(target: "consensus", "will emit {} tokens", emission.peek());
 
-        let remaining = linear_rewards::<T>(emission);
+        let mut report = EpochReport::<T> {
+            block: block_number,
+            emitted: emission.peek(),
+            treasury_fee: 0,
+            remaining: 0,
+            agents: Default::default(),
+        };
+
+        let remaining = linear_rewards::<T>(emission, &mut report);
 
         crate::PendingEmission::<T>::set(remaining.peek());
 
         info!(target: "consensus", "{} tokens returned to pending emissions.", remaining.peek());
 
+        report.remaining = remaining.peek();
+        store_epoch_report::<T>(report);
+
         Ok(())
     });
 }
 
+/// Stores the report of the epoch that was just distributed, pruning the
+/// oldest one once more than [`Config::MaxEpochReports`] are kept.
+fn store_epoch_report<T: Config>(report: EpochReport<T>) {
+    let epoch = crate::EpochCount::<T>::get();
+    crate::EpochCount::<T>::set(epoch.saturating_add(1));
+
+    if let Some(expired) = epoch.checked_sub(T::MaxEpochReports::get() as u64) {
+        crate::EpochReports::<T>::remove(expired);
+    }
+
+    crate::EpochReports::<T>::insert(epoch, &report);
+    crate::Pallet::<T>::deposit_event(crate::Event::EpochDistributed(epoch, report));
+}
+
 #[doc(hidden)]
 pub fn get_total_emission_per_block<T: Config>() -> BalanceOf<T> {
     let total_issuance = <T as Config>::Currency::total_issuance();
@@ -91,7 +121,9 @@ pub struct ConsensusMemberInput<T: Config> {
     pub stakes: Vec<(T::AccountId, u128)>,
     pub total_stake: u128,
     pub normalized_stake: FixedU128,
-    pub delegating_to: Option<T::AccountId>,
+    /// Agents the weight control is delegated to, with the share given to
+    /// each of them.
+    pub delegating_to: Vec<(T::AccountId, Percent)>,
     pub whitelisted: bool,
 }
 
@@ -111,6 +143,15 @@ impl<T: Config> ConsensusMemberInput<T> {
 
     /// Creates a set of params for every agent registered to the network.
     pub fn all_members() -> BTreeMap<T::AccountId, ConsensusMemberInput<T>> {
+        Self::all_members_with_overrides(Default::default())
+    }
+
+    /// Same as [`Self::all_members`], but the weights set by the given agents
+    /// are replaced by the provided ones. Used to simulate epochs with
+    /// hypothetical weights.
+    pub fn all_members_with_overrides(
+        weight_overrides: BTreeMap<T::AccountId, Weights<T>>,
+    ) -> BTreeMap<T::AccountId, ConsensusMemberInput<T>> {
         let min_validator_stake = <T::Torus>::min_validator_stake();
 
         let mut whitelisted_agents: BTreeSet<_> = <T::Torus>::agent_ids()
@@ -118,27 +159,42 @@ impl<T: Config> ConsensusMemberInput<T> {
             .collect();
         let mut consensus_members: BTreeMap<_, _> = crate::ConsensusMembers::<T>::iter().collect();
 
-        let mut inputs: Vec<_> = crate::WeightControlDelegation::<T>::iter()
-            .map(|(delegator, recipient)| {
-                let is_whitelisted = whitelisted_agents.remove(&delegator);
-                consensus_members.remove(&delegator);
-
-                let mut input = if let Some(delegatee_input) = consensus_members.get(&recipient) {
-                    Self::from_agent(
-                        delegator.clone(),
-                        delegatee_input.weights.clone(),
-                        min_validator_stake,
-                    )
+        for (agent_id, weights) in weight_overrides {
+            consensus_members
+                .entry(agent_id)
+                .or_default()
+                .update_weights(weights);
+        }
+
+        let delegations: BTreeMap<_, _> = crate::WeightControlDelegation::<T>::iter().collect();
+
+        let mut inputs: Vec<_> = delegations
+            .iter()
+            .map(|(delegator, shares)| {
+                let is_whitelisted = whitelisted_agents.remove(delegator);
+
+                let weights = resolve_weight_setters::<T>(delegator, &delegations)
+                    .and_then(|setters| blend_weights::<T>(&setters, &consensus_members));
+
+                let mut input = if let Some(weights) = weights {
+                    Self::from_agent(delegator.clone(), weights, min_validator_stake)
                 } else {
                     Self::from_new_agent(delegator.clone(), is_whitelisted)
                 };
 
-                input.delegating_to = Some(recipient);
+                input.delegating_to = shares
+                    .iter()
+                    .map(|(delegatee, share)| (delegatee.clone(), *share))
+                    .collect();
 
-                (delegator, input)
+                (delegator.clone(), input)
             })
             .collect();
 
+        for delegator in delegations.keys() {
+            consensus_members.remove(delegator);
+        }
+
         inputs.extend(whitelisted_agents.into_iter().map(|agent_id| {
             let input = if let Some(member) = consensus_members.remove(&agent_id) {
                 Self::from_agent(agent_id.clone(), member.weights, min_validator_stake)
@@ -263,103 +319,214 @@ impl<T: Config> ConsensusMemberInput<T> {
             .map(|(k, v)| (k.clone(), Perquintill::from_rational(*v, self.total_stake)))
             .collect()
     }
-}
 
-#[must_use]
-fn linear_rewards<T: Config>(mut emission: NegativeImbalanceOf<T>) -> NegativeImbalanceOf<T> {
-    let treasury_fee = <T::Governance>::treasury_emission_fee();
-    if !treasury_fee.is_zero() {
-        let treasury_fee = treasury_fee.mul_floor(emission.peek());
-        let treasury_fee = emission.extract(treasury_fee);
-        T::Currency::resolve_creating(&<T::Governance>::dao_treasury_address(), treasury_fee);
-    }
-
-    let inputs = ConsensusMemberInput::<T>::all_members();
-
-    let id_to_idx: BTreeMap<_, _> = inputs
-        .keys()
-        .cloned()
-        .enumerate()
-        .map(|(idx, id)| (id, idx))
-        .collect();
+    /// Splits the dividends of the agent between its stakers, proportionally
+    /// to their stake and discounting the agent's staking fee. Whatever is not
+    /// paid to stakers is kept by the agent.
+    pub fn staker_dividends(&self, dividend: BalanceOf<T>) -> Vec<(T::AccountId, BalanceOf<T>)> {
+        if dividend == 0 {
+            return Vec::new();
+        }
 
-    let mut weights: Vec<Vec<(usize, FixedU128)>> = vec![vec![]; inputs.len()];
-    let mut stakes = vec![FixedU128::from_inner(0); inputs.len()];
+        let staking_fee = <T::Torus>::staking_fee(&self.agent_id);
+        self.normalized_stakers()
+            .into_iter()
+            .map(|(staker, ratio)| {
+                let staker_dividend = ratio.mul_floor(dividend);
+                let stake_fee = staking_fee.mul_floor(staker_dividend);
 
-    for ((input, weights), stake) in inputs.values().zip(&mut weights).zip(&mut stakes) {
-        *stake = input.normalized_stake;
-        *weights = input
-            .weights
-            .iter()
-            .filter_map(|(id, weight)| {
-                let idx = id_to_idx.get(id)?;
-                Some((*idx, *weight))
+                (staker, staker_dividend.saturating_sub(stake_fee))
             })
-            .collect();
+            .collect()
     }
+}
 
-    let ranks = math::matmul_sparse(&weights, &stakes, inputs.len());
-    let incentives = math::normalize(ranks);
+/// Follows the weight control delegations starting at the delegator and
+/// returns the agents whose weights are used, with the share of the weight
+/// control reaching each of them. Returns `None` if the delegations loop back
+/// to the delegator or exceed [`Config::MaxWeightDelegationDepth`].
+fn resolve_weight_setters<T: Config>(
+    delegator: &T::AccountId,
+    delegations: &BTreeMap<T::AccountId, WeightControlShares<T>>,
+) -> Option<BTreeMap<T::AccountId, FixedU128>> {
+    let max_depth = T::MaxWeightDelegationDepth::get();
+
+    let mut setters = BTreeMap::<T::AccountId, FixedU128>::new();
+    let mut pending = vec![(delegator, FixedU128::from_u32(1), 0u32)];
+    while let Some((agent, share, depth)) = pending.pop() {
+        let Some(shares) = delegations.get(agent) else {
+            let setter_share = setters.entry(agent.clone()).or_default();
+            *setter_share = setter_share.saturating_add(share);
+            continue;
+        };
 
-    let bonds_delta = math::row_hadamard_sparse(&weights, &stakes);
-    let bonds_delta = math::col_normalize_sparse(bonds_delta, inputs.len());
+        if depth >= max_depth {
+            return None;
+        }
 
-    let dividends = math::matmul_transpose_sparse(&bonds_delta, &incentives);
-    let dividends = math::normalize(dividends);
+        for (delegatee, delegatee_share) in shares.iter() {
+            if delegatee == delegator {
+                return None;
+            }
 
-    let Emissions {
-        mut dividends,
-        incentives,
-    } = compute_emissions::<T>(&mut emission, &stakes, incentives, dividends);
+            let delegatee_share =
+                FixedU128::from_rational(delegatee_share.deconstruct().into(), 100);
+            pending.push((
+                delegatee,
+                share.saturating_mul(delegatee_share),
+                depth.saturating_add(1),
+            ));
+        }
+    }
 
-    for (idx, input) in inputs.values().enumerate() {
-        let Some(delegating_to) = &input.delegating_to else {
+    Some(setters)
+}
+
+/// Blends the normalized weights of the setters by their share of the weight
+/// control. Returns `None` if none of the setters is a consensus member.
+fn blend_weights<T: Config>(
+    setters: &BTreeMap<T::AccountId, FixedU128>,
+    consensus_members: &BTreeMap<T::AccountId, ConsensusMember<T>>,
+) -> Option<Weights<T>> {
+    // A single setter keeps its weights untouched.
+    if let (Some(setter), 1) = (setters.keys().next(), setters.len()) {
+        return consensus_members
+            .get(setter)
+            .map(|member| member.weights.clone());
+    }
+
+    let mut found = false;
+    let mut blended = BTreeMap::<T::AccountId, FixedU128>::new();
+    for (setter, share) in setters {
+        let Some(member) = consensus_members.get(setter) else {
             continue;
         };
+        found = true;
 
-        let Some(dividend) = dividends
-            .get_mut(idx)
-            .filter(|dividend| dividend.peek() > 0)
-        else {
+        let total = member.weights.iter().fold(0u128, |acc, (_, weight)| {
+            acc.saturating_add((*weight).into())
+        });
+        if total == 0 {
             continue;
-        };
+        }
 
-        let control_fee = <T::Torus>::weight_control_fee(delegating_to);
-        let control_fee = control_fee.mul_floor(dividend.peek());
-        let mut stake = dividend.extract(control_fee);
+        for (id, weight) in member.weights.iter() {
+            let weight = FixedU128::from_rational((*weight).into(), total);
+            let blended_weight = blended.entry(id.clone()).or_default();
+            *blended_weight = blended_weight.saturating_add(share.saturating_mul(weight));
+        }
+    }
 
-        if let Some(delegated_dividend) = id_to_idx
-            .get(delegating_to)
-            .and_then(|idx| dividends.get_mut(*idx))
-        {
-            delegated_dividend.subsume(stake);
-        } else {
-            // This is an impossible case, but if something changes in the future,
-            // the code is here.
-            <T::Permission0>::accumulate_streams(
-                delegating_to,
-                &pallet_permission0_api::generate_root_stream_id(delegating_to),
-                &mut stake,
-            );
+    if !found {
+        return None;
+    }
 
-            let stake_num = stake.peek();
-            T::Currency::resolve_creating(delegating_to, stake);
-            let _ = <T::Torus>::stake_to(delegating_to, delegating_to, stake_num);
-        }
+    let (ids, weights): (Vec<_>, Vec<_>) = blended.into_iter().unzip();
+    let weights = math::vec_max_upscale_to_u16(&weights);
+
+    Some(Weights::<T>::truncate_from(
+        ids.into_iter().zip(weights).collect(),
+    ))
+}
+
+/// Weight control fee charged by the agent for the weight control delegated
+/// to it.
+fn weight_control_fee<T: Config>(delegatee: &T::AccountId) -> Percent {
+    <T::Governance>::allocator_fee(delegatee)
+        .unwrap_or_else(|| <T::Torus>::weight_control_fee(delegatee))
+}
+
+/// Weight control fees owed to each delegatee over its share of `amount`.
+fn split_control_fees<T: Config>(
+    delegating_to: &[(T::AccountId, Percent)],
+    amount: BalanceOf<T>,
+) -> Vec<(&T::AccountId, BalanceOf<T>)> {
+    delegating_to
+        .iter()
+        .map(|(delegatee, share)| {
+            let fee = weight_control_fee::<T>(delegatee).mul_floor(share.mul_floor(amount));
+            (delegatee, fee)
+        })
+        .collect()
+}
+
+#[must_use]
+fn linear_rewards<T: Config>(
+    mut emission: NegativeImbalanceOf<T>,
+    report: &mut EpochReport<T>,
+) -> NegativeImbalanceOf<T> {
+    let treasury_fee = <T::Governance>::treasury_emission_fee();
+    if !treasury_fee.is_zero() {
+        let treasury_fee = treasury_fee.mul_floor(emission.peek());
+        let treasury_fee = emission.extract(treasury_fee);
+        report.treasury_fee = treasury_fee.peek();
+        T::Currency::resolve_creating(&<T::Governance>::dao_treasury_address(), treasury_fee);
     }
 
+    let inputs = ConsensusMemberInput::<T>::all_members();
+
+    let Distribution {
+        incentives,
+        dividends,
+        orphan_fees,
+        bonds,
+    } = compute_distribution::<T>(&inputs, emission.peek());
+
+    store_bonds::<T>(&inputs, &bonds);
+
+    report.agents = EpochAgents::<T>::truncate_from(
+        inputs
+            .values()
+            .zip(&incentives)
+            .zip(&dividends)
+            .filter(|((_, incentive), dividend)| **incentive > 0 || **dividend > 0)
+            .map(
+                |((input, incentive), dividend)| pallet_emission0_api::AgentEmission {
+                    agent_id: input.agent_id.clone(),
+                    incentives: *incentive,
+                    dividends: *dividend,
+                },
+            )
+            .collect(),
+    );
+
     let upscaled_incentives: Vec<_> = incentives
         .iter()
-        .map(|i| FixedU128::from_inner(i.peek()))
+        .map(|i| FixedU128::from_inner(*i))
         .collect();
     let upscaled_incentives = math::vec_max_upscale_to_u16(&upscaled_incentives);
 
     let upscaled_dividends: Vec<_> = dividends
         .iter()
-        .map(|i| FixedU128::from_inner(i.peek()))
+        .map(|i| FixedU128::from_inner(*i))
         .collect();
     let upscaled_dividends = math::vec_max_upscale_to_u16(&upscaled_dividends);
 
+    let incentives: Vec<_> = incentives
+        .into_iter()
+        .map(|amount| emission.extract(amount))
+        .collect();
+    let dividends: Vec<_> = dividends
+        .into_iter()
+        .map(|amount| emission.extract(amount))
+        .collect();
+
+    for (delegating_to, control_fee) in orphan_fees {
+        // This is an impossible case, but if something changes in the future,
+        // the code is here.
+        let mut stake = emission.extract(control_fee);
+
+        <T::Permission0>::accumulate_streams(
+            &delegating_to,
+            &pallet_permission0_api::generate_root_stream_id(&delegating_to),
+            &mut stake,
+        );
+
+        let stake_num = stake.peek();
+        T::Currency::resolve_creating(&delegating_to, stake);
+        let _ = <T::Torus>::stake_to(&delegating_to, &delegating_to, stake_num);
+    }
+
     for ((((input, incentive), mut dividend), upscaled_incentives), upscaled_dividends) in inputs
         .values()
         .zip(incentives)
@@ -382,19 +549,9 @@ fn linear_rewards<T: Config>(mut emission: NegativeImbalanceOf<T>) -> NegativeIm
             }
         };
 
-        if dividend.peek() != 0 {
-            let fixed_dividend = dividend.peek();
-
-            let stakers = input.normalized_stakers();
-            let delegation_fee = <T::Torus>::staking_fee(&input.agent_id);
-            for (staker, ratio) in stakers {
-                let staker_dividend = ratio.mul_floor(fixed_dividend);
-                let stake_fee = delegation_fee.mul_floor(staker_dividend);
-
-                let stake = dividend.extract(staker_dividend.saturating_sub(stake_fee));
-
-                add_stake(staker, stake);
-            }
+        for (staker, staker_dividend) in input.staker_dividends(dividend.peek()) {
+            let stake = dividend.extract(staker_dividend);
+            add_stake(staker, stake);
         }
 
         let remaining_emission = incentive.merge(dividend);
@@ -412,21 +569,356 @@ fn linear_rewards<T: Config>(mut emission: NegativeImbalanceOf<T>) -> NegativeIm
                 },
             );
         } else {
-            crate::ConsensusMembers::<T>::remove(&input.agent_id);
-            crate::WeightControlDelegation::<T>::remove(&input.agent_id);
+            if let Some(member) = crate::ConsensusMembers::<T>::take(&input.agent_id) {
+                crate::weight_control::unindex_weights::<T>(&input.agent_id, &member.weights);
+            }
+            crate::weight_control::set_delegation::<T>(&input.agent_id, None);
         }
     }
 
     emission
 }
 
+/// Simulates the distribution of the current pending emission as if the
+/// epoch ran now, without mutating storage. The weights set by the agents in
+/// `weight_overrides` are replaced by the given ones.
+///
+/// Staker payouts are reported before any stream accumulation.
+pub fn simulate_epoch<T: Config>(
+    weight_overrides: Vec<(T::AccountId, Vec<(T::AccountId, u16)>)>,
+) -> pallet_emission0_api::EpochSimulation<T::AccountId, BalanceOf<T>> {
+    let emission = crate::PendingEmission::<T>::get();
+    let treasury_fee = <T::Governance>::treasury_emission_fee().mul_floor(emission);
+
+    let weight_overrides = weight_overrides
+        .into_iter()
+        .map(|(agent_id, weights)| (agent_id, Weights::<T>::truncate_from(weights)))
+        .collect();
+    let inputs = ConsensusMemberInput::<T>::all_members_with_overrides(weight_overrides);
+
+    let Distribution {
+        incentives,
+        dividends,
+        ..
+    } = compute_distribution::<T>(&inputs, emission.saturating_sub(treasury_fee));
+
+    let agents = inputs
+        .values()
+        .zip(incentives)
+        .zip(dividends)
+        .map(|((input, incentives), dividends)| {
+            let stakers = input.staker_dividends(dividends);
+            let paid_to_stakers = stakers.iter().fold(0, |acc: BalanceOf<T>, (_, amount)| {
+                acc.saturating_add(*amount)
+            });
+
+            pallet_emission0_api::AgentSimulation {
+                agent_id: input.agent_id.clone(),
+                incentives,
+                dividends,
+                agent_payout: incentives.saturating_add(dividends.saturating_sub(paid_to_stakers)),
+                stakers,
+            }
+        })
+        .collect();
+
+    pallet_emission0_api::EpochSimulation {
+        emission,
+        treasury_fee,
+        agents,
+    }
+}
+
+/// Amounts received by each consensus member in an epoch, in the same order
+/// as the member inputs.
+struct Distribution<T: Config> {
+    incentives: Vec<BalanceOf<T>>,
+    /// Dividends after weight control fees were moved to the delegatees.
+    dividends: Vec<BalanceOf<T>>,
+    /// Weight control fees owed to delegatees that are not consensus members.
+    orphan_fees: Vec<(T::AccountId, BalanceOf<T>)>,
+    /// Updated moving average of the bonds, indexed by member position.
+    bonds: Vec<Vec<(usize, FixedU128)>>,
+}
+
+/// Runs the consensus over the member inputs and computes how much of
+/// `to_be_emitted` each member receives. Does not touch storage.
+fn compute_distribution<T: Config>(
+    inputs: &BTreeMap<T::AccountId, ConsensusMemberInput<T>>,
+    to_be_emitted: BalanceOf<T>,
+) -> Distribution<T> {
+    let ConsensusMatrices {
+        id_to_idx,
+        weights,
+        stakes,
+    } = ConsensusMatrices::new(inputs);
+
+    let consensus = crate::ConsensusConfig::<T>::get();
+    let (weights, trust) = if consensus.enabled {
+        let (weights, trust) = clip_to_consensus(weights, &stakes, consensus.kappa);
+        (weights, Some(trust))
+    } else {
+        (weights, None)
+    };
+
+    let ranks = math::matmul_sparse(&weights, &stakes, inputs.len());
+    let incentives = math::normalize(ranks);
+
+    let bonds_delta = bonds_delta(&weights, &stakes);
+
+    let alpha = crate::BondsMovingAverage::<T>::get();
+    let bonds = if alpha == Percent::one() {
+        // Without smoothing, the bonds are exactly the ones formed this epoch.
+        bonds_delta
+    } else {
+        let previous_bonds = previous_bonds::<T>(inputs, &id_to_idx);
+        let alpha = FixedU128::from_rational(alpha.deconstruct().into(), 100);
+        let bonds = math::mat_ema_sparse(&bonds_delta, &previous_bonds, alpha);
+        math::col_normalize_sparse(bonds, inputs.len())
+    };
+
+    let dividends = math::matmul_transpose_sparse(&bonds, &incentives);
+    let dividends = match trust {
+        Some(trust) => penalize_divergence(dividends, &trust, consensus.divergence_penalty),
+        None => dividends,
+    };
+    let dividends = math::normalize(dividends);
+
+    let Emissions {
+        mut dividends,
+        incentives,
+    } = compute_emissions::<T>(to_be_emitted, &stakes, incentives, dividends);
+
+    // Fees are computed over the dividends before any fee is moved, so the
+    // fees received by an agent are not charged again by its own delegatees.
+    let max_depth = T::MaxWeightDelegationDepth::get();
+    let mut control_fees = Vec::new();
+    for (idx, input) in inputs.values().enumerate() {
+        if input.delegating_to.is_empty() {
+            continue;
+        }
+
+        let Some(dividend) = dividends.get_mut(idx).filter(|dividend| **dividend > 0) else {
+            continue;
+        };
+
+        let fees = split_control_fees::<T>(&input.delegating_to, *dividend);
+        let paid = fees
+            .iter()
+            .fold(0, |acc: BalanceOf<T>, (_, fee)| acc.saturating_add(*fee));
+        *dividend = dividend.saturating_sub(paid);
+
+        // Each agent receiving fees pays its own delegatees' fees over them.
+        let mut pending: Vec<_> = fees
+            .into_iter()
+            .map(|(recipient, fee)| (recipient, fee, 1u32))
+            .collect();
+        while let Some((recipient, control_fee, depth)) = pending.pop() {
+            let passed_on = match inputs.get(recipient) {
+                Some(recipient_input) if depth < max_depth => {
+                    split_control_fees::<T>(&recipient_input.delegating_to, control_fee)
+                }
+                _ => Vec::new(),
+            };
+            let passed = passed_on
+                .iter()
+                .fold(0, |acc: BalanceOf<T>, (_, fee)| acc.saturating_add(*fee));
+
+            control_fees.push((recipient, control_fee.saturating_sub(passed)));
+            pending.extend(
+                passed_on
+                    .into_iter()
+                    .map(|(next, fee)| (next, fee, depth.saturating_add(1))),
+            );
+        }
+    }
+
+    let mut orphan_fees = Vec::new();
+    for (recipient, control_fee) in control_fees {
+        if control_fee == 0 {
+            continue;
+        }
+
+        if let Some(delegated_dividend) = id_to_idx
+            .get(recipient)
+            .and_then(|idx| dividends.get_mut(*idx))
+        {
+            *delegated_dividend = delegated_dividend.saturating_add(control_fee);
+        } else {
+            orphan_fees.push((recipient.clone(), control_fee));
+        }
+    }
+
+    Distribution {
+        incentives,
+        dividends,
+        orphan_fees,
+        bonds,
+    }
+}
+
+/// Member weights and normalized stakes indexed by the member position in the
+/// inputs.
+struct ConsensusMatrices<T: Config> {
+    id_to_idx: BTreeMap<T::AccountId, usize>,
+    weights: Vec<Vec<(usize, FixedU128)>>,
+    stakes: Vec<FixedU128>,
+}
+
+impl<T: Config> ConsensusMatrices<T> {
+    fn new(inputs: &BTreeMap<T::AccountId, ConsensusMemberInput<T>>) -> Self {
+        let id_to_idx: BTreeMap<_, _> = inputs
+            .keys()
+            .cloned()
+            .enumerate()
+            .map(|(idx, id)| (id, idx))
+            .collect();
+
+        let mut weights: Vec<Vec<(usize, FixedU128)>> = vec![vec![]; inputs.len()];
+        let mut stakes = vec![FixedU128::from_inner(0); inputs.len()];
+
+        for ((input, weights), stake) in inputs.values().zip(&mut weights).zip(&mut stakes) {
+            *stake = input.normalized_stake;
+            *weights = input
+                .weights
+                .iter()
+                .filter_map(|(id, weight)| {
+                    let idx = id_to_idx.get(id)?;
+                    Some((*idx, *weight))
+                })
+                .collect();
+        }
+
+        Self {
+            id_to_idx,
+            weights,
+            stakes,
+        }
+    }
+}
+
+/// Clips the weights to the stake-weighted κ-majority of each column. Also
+/// returns the trust of each member: the fraction of its weights that was
+/// kept.
+fn clip_to_consensus(
+    weights: Vec<Vec<(usize, FixedU128)>>,
+    stakes: &[FixedU128],
+    kappa: Percent,
+) -> (Vec<Vec<(usize, FixedU128)>>, Vec<FixedU128>) {
+    let kappa = FixedU128::from_rational(kappa.deconstruct().into(), 100);
+    let consensus = math::weighted_median_col_sparse(stakes, &weights, weights.len(), kappa);
+    let clipped = math::col_clip_sparse(&weights, &consensus);
+
+    let trust = math::row_sum_sparse(&weights)
+        .into_iter()
+        .zip(math::row_sum_sparse(&clipped))
+        .map(|(sum, clipped_sum)| {
+            if sum == FixedU128::from_inner(0) {
+                FixedU128::from_u32(1)
+            } else {
+                clipped_sum.const_checked_div(sum).unwrap_or_default()
+            }
+        })
+        .collect();
+
+    (clipped, trust)
+}
+
+/// Reduces the dividends of each member by `penalty` times the fraction of its
+/// weights clipped by the consensus.
+fn penalize_divergence(
+    dividends: Vec<FixedU128>,
+    trust: &[FixedU128],
+    penalty: Percent,
+) -> Vec<FixedU128> {
+    let penalty = FixedU128::from_rational(penalty.deconstruct().into(), 100);
+
+    dividends
+        .into_iter()
+        .zip(trust)
+        .map(|(dividend, trust)| {
+            let divergence = FixedU128::from_u32(1).saturating_sub(*trust);
+            let kept = FixedU128::from_u32(1).saturating_sub(penalty.saturating_mul(divergence));
+            dividend.saturating_mul(kept)
+        })
+        .collect()
+}
+
+/// Bonds formed by the weights set in the current epoch: the stake each
+/// validator puts behind an agent, normalized over all validators bonding it.
+fn bonds_delta(
+    weights: &[Vec<(usize, FixedU128)>],
+    stakes: &[FixedU128],
+) -> Vec<Vec<(usize, FixedU128)>> {
+    let bonds_delta = math::row_hadamard_sparse(weights, stakes);
+    math::col_normalize_sparse(bonds_delta, weights.len())
+}
+
+/// Loads the bonds stored for the members. Members without a validator permit
+/// lose their bonds.
+fn previous_bonds<T: Config>(
+    inputs: &BTreeMap<T::AccountId, ConsensusMemberInput<T>>,
+    id_to_idx: &BTreeMap<T::AccountId, usize>,
+) -> Vec<Vec<(usize, FixedU128)>> {
+    inputs
+        .values()
+        .map(|input| {
+            if !input.validator_permit {
+                return Vec::new();
+            }
+
+            crate::Bonds::<T>::get(&input.agent_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|(id, bond)| Some((*id_to_idx.get(&id)?, bond)))
+                .collect()
+        })
+        .collect()
+}
+
+/// Replaces the stored bonds with the given ones, indexed by member position.
+fn store_bonds<T: Config>(
+    inputs: &BTreeMap<T::AccountId, ConsensusMemberInput<T>>,
+    bonds: &[Vec<(usize, FixedU128)>],
+) {
+    let ids: Vec<_> = inputs.keys().collect();
+
+    let _ = crate::Bonds::<T>::clear(u32::MAX, None);
+
+    for (validator, bonds) in ids.iter().zip(bonds) {
+        if bonds.is_empty() {
+            continue;
+        }
+
+        let bonds = bonds
+            .iter()
+            .filter_map(|(idx, bond)| Some(((*ids.get(*idx)?).clone(), *bond)))
+            .collect();
+        crate::Bonds::<T>::insert(*validator, ValidatorBonds::<T>::truncate_from(bonds));
+    }
+}
+
+/// Initializes [`crate::Bonds`] from the weights currently set, as if they
+/// had been held forever. Returns the amount of validators bonded.
+pub(crate) fn seed_bonds<T: Config>() -> usize {
+    let inputs = ConsensusMemberInput::<T>::all_members();
+    let ConsensusMatrices {
+        weights, stakes, ..
+    } = ConsensusMatrices::new(&inputs);
+
+    let bonds = bonds_delta(&weights, &stakes);
+    store_bonds::<T>(&inputs, &bonds);
+
+    bonds.iter().filter(|bonds| !bonds.is_empty()).count()
+}
+
 struct Emissions<T: Config> {
-    dividends: Vec<NegativeImbalanceOf<T>>,
-    incentives: Vec<NegativeImbalanceOf<T>>,
+    dividends: Vec<BalanceOf<T>>,
+    incentives: Vec<BalanceOf<T>>,
 }
 
 fn compute_emissions<T: Config>(
-    emission: &mut NegativeImbalanceOf<T>,
+    to_be_emitted: BalanceOf<T>,
     stake: &[FixedU128],
     incentives: Vec<FixedU128>,
     dividends: Vec<FixedU128>,
@@ -450,18 +942,21 @@ fn compute_emissions<T: Config>(
         Cow::Owned(dividends_emission)
     };
 
-    let to_be_emitted = FixedU128::from_inner(emission.peek());
-
+    // Amounts can never add up to more than what is to be emitted.
+    let mut remaining = to_be_emitted;
     let mut calculate_emissions = |v: &[FixedU128], to_be_emitted: FixedU128| {
         v.iter()
             .map(|&se| se.const_checked_mul(to_be_emitted).unwrap_or_default())
-            .map(|amount| emission.extract(amount.into_inner()))
+            .map(|amount| {
+                let amount = amount.into_inner().min(remaining);
+                remaining = remaining.saturating_sub(amount);
+                amount
+            })
             .collect::<Vec<_>>()
     };
 
     let incentives_ratio = IncentivesRatio::<T>::get().deconstruct();
 
-    let to_be_emitted = to_be_emitted.into_inner();
     let incentives_to_be_emitted;
     let dividends_to_be_emitted;
 
@@ -4,6 +4,7 @@
 use num_traits::float::FloatCore;
 use polkadot_sdk::{
     sp_runtime::{FixedPointNumber, FixedU128, traits::Saturating},
+    sp_std::collections::btree_map::BTreeMap,
     sp_std::vec,
     sp_std::vec::Vec,
 };
@@ -131,6 +132,121 @@ pub fn matmul_transpose_sparse(
     result
 }
 
+/// Stake-weighted κ-majority of each column: the highest value assigned to
+/// the column by validators holding at least `majority` of the total stake.
+/// With a majority of 50% this is the stake-weighted median. Columns without
+/// enough support result in zero.
+pub fn weighted_median_col_sparse(
+    stake: &[FixedU128],
+    sparse_matrix: &[Vec<(usize, FixedU128)>],
+    columns: usize,
+    majority: FixedU128,
+) -> Vec<FixedU128> {
+    let total_stake = stake
+        .iter()
+        .fold(FixedU128::default(), |acc, &e| acc.saturating_add(e));
+    let minimum_support = majority.min(FixedU128::one()).saturating_mul(total_stake);
+
+    let mut column_values: Vec<Vec<(FixedU128, FixedU128)>> = vec![vec![]; columns];
+    for (sparse_row, stake_i) in sparse_matrix.iter().zip(stake) {
+        if *stake_i == FixedU128::from_inner(0) {
+            continue;
+        }
+
+        for (j, value) in sparse_row {
+            if let Some(values) = column_values.get_mut(*j) {
+                values.push((*value, *stake_i));
+            }
+        }
+    }
+
+    column_values
+        .into_iter()
+        .map(|mut values| {
+            // Only the order of the values matters for the result, so ties
+            // between rows do not change the outcome.
+            values.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+
+            let mut support = FixedU128::from_inner(0);
+            values
+                .into_iter()
+                .find(|(_, stake)| {
+                    support = support.saturating_add(*stake);
+                    support >= minimum_support
+                })
+                .map(|(value, _)| value)
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Clips every entry of each column to the given column maximum, dropping the
+/// entries clipped to zero.
+pub fn col_clip_sparse(
+    sparse_matrix: &[Vec<(usize, FixedU128)>],
+    col_max: &[FixedU128],
+) -> Vec<Vec<(usize, FixedU128)>> {
+    sparse_matrix
+        .iter()
+        .map(|sparse_row| {
+            sparse_row
+                .iter()
+                .filter_map(|(j, value)| {
+                    let value = (*value).min(col_max.get(*j).copied().unwrap_or_default());
+                    (value != FixedU128::from_inner(0)).then_some((*j, value))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub fn row_sum_sparse(sparse_matrix: &[Vec<(usize, FixedU128)>]) -> Vec<FixedU128> {
+    sparse_matrix
+        .iter()
+        .map(|sparse_row| {
+            sparse_row
+                .iter()
+                .fold(FixedU128::default(), |acc, (_, value)| {
+                    acc.saturating_add(*value)
+                })
+        })
+        .collect()
+}
+
+/// Exponential moving average of two sparse matrices:
+/// `alpha * new + (1 - alpha) * old`. Rows are sorted by column and zeroed
+/// entries are dropped.
+pub fn mat_ema_sparse(
+    new: &[Vec<(usize, FixedU128)>],
+    old: &[Vec<(usize, FixedU128)>],
+    alpha: FixedU128,
+) -> Vec<Vec<(usize, FixedU128)>> {
+    let alpha = alpha.min(FixedU128::one());
+    let one_minus_alpha = FixedU128::one().saturating_sub(alpha);
+
+    let rows = new.len().max(old.len());
+    let mut result: Vec<Vec<(usize, FixedU128)>> = Vec::with_capacity(rows);
+
+    for i in 0..rows {
+        let mut row: BTreeMap<usize, FixedU128> = BTreeMap::new();
+
+        for (sparse_row, factor) in [(new.get(i), alpha), (old.get(i), one_minus_alpha)] {
+            for (j, value) in sparse_row.into_iter().flatten() {
+                let entry = row.entry(*j).or_default();
+                *entry = entry.saturating_add(factor.saturating_mul(*value));
+            }
+        }
+
+        result.push(
+            row.into_iter()
+                .filter(|(_, value)| *value != FixedU128::from_inner(0))
+                .collect(),
+        );
+    }
+
+    result
+}
+
 /// Max-upscale vector and convert to u16 so max_value = u16::MAX. Assumes
 /// non-negative normalized input.
 pub fn vec_max_upscale_to_u16(vec: &[FixedU128]) -> Vec<u16> {
@@ -307,4 +423,209 @@ mod tests {
             }
         }
     }
+
+    /// Deterministic xorshift generator, so property tests are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn next_u64(&mut self) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0
+        }
+
+        fn fixed(&mut self) -> FixedU128 {
+            FixedU128::from_rational(self.next_u64() % 1000 + 1, 1000)
+        }
+    }
+
+    /// Random sparse matrix with sorted, non-zero entries.
+    fn random_sparse_mat(rng: &mut Rng, rows: usize, cols: usize) -> Vec<Vec<(usize, FixedU128)>> {
+        let mut mat = vec![];
+        for _ in 0..rows {
+            let mut row = vec![];
+            for col in 0..cols {
+                if rng.next_u64() % 3 == 0 {
+                    row.push((col, rng.fixed()));
+                }
+            }
+            mat.push(row);
+        }
+        mat
+    }
+
+    fn col_sums(mat: &[Vec<(usize, FixedU128)>], cols: usize) -> Vec<FixedU128> {
+        let mut sums = vec![FixedU128::from_inner(0); cols];
+        for row in mat {
+            for (j, value) in row {
+                sums[*j] = sums[*j] + *value;
+            }
+        }
+        sums
+    }
+
+    fn to_u16_indices(mat: &[Vec<(usize, FixedU128)>]) -> Vec<Vec<(u16, FixedU128)>> {
+        mat.iter()
+            .map(|row| row.iter().map(|(j, value)| (*j as u16, *value)).collect())
+            .collect()
+    }
+
+    fn inplace_sparse_row_normalize(mat: &mut [Vec<(usize, FixedU128)>]) {
+        for row in mat {
+            let sum = row
+                .iter()
+                .fold(FixedU128::from_inner(0), |acc, (_, value)| acc + *value);
+            for (_, value) in row.iter_mut() {
+                *value = *value / sum;
+            }
+        }
+    }
+
+    #[test]
+    fn test_math_mat_ema_sparse() {
+        let new = vec![vec![(0, FixedU128::from_u32(1))], vec![]];
+        let old = vec![
+            vec![
+                (0, FixedU128::from_rational(1, 2)),
+                (1, FixedU128::from_rational(1, 2)),
+            ],
+            vec![(1, FixedU128::from_u32(1))],
+        ];
+
+        let ema = mat_ema_sparse(&new, &old, FixedU128::from_rational(1, 10));
+
+        assert_sparse_mat_compare(
+            &to_u16_indices(&ema),
+            &[
+                vec![
+                    (0, FixedU128::from_rational(55, 100)),
+                    (1, FixedU128::from_rational(45, 100)),
+                ],
+                vec![(1, FixedU128::from_rational(9, 10))],
+            ],
+            FixedU128::from_inner(0),
+        );
+    }
+
+    #[test]
+    fn test_math_mat_ema_sparse_bounds() {
+        let mut rng = Rng(0x5eed);
+
+        for _ in 0..200 {
+            let rows = (rng.next_u64() % 8 + 1) as usize;
+            let new = random_sparse_mat(&mut rng, rows, rows);
+            let old = random_sparse_mat(&mut rng, rows, rows);
+
+            assert_eq!(mat_ema_sparse(&new, &old, FixedU128::from_u32(1)), new);
+            assert_eq!(mat_ema_sparse(&new, &old, FixedU128::from_inner(0)), old);
+        }
+    }
+
+    #[test]
+    fn test_math_ema_bonds_full_alpha_matches_bonds_delta() {
+        let mut rng = Rng(0xb0d5);
+
+        for _ in 0..200 {
+            let n = (rng.next_u64() % 8 + 1) as usize;
+
+            let mut weights = random_sparse_mat(&mut rng, n, n);
+            inplace_sparse_row_normalize(&mut weights);
+            let stakes = normalize((0..n).map(|_| rng.fixed()).collect());
+            let previous_bonds = col_normalize_sparse(random_sparse_mat(&mut rng, n, n), n);
+
+            let incentives = normalize(matmul_sparse(&weights, &stakes, n));
+            let bonds_delta = col_normalize_sparse(row_hadamard_sparse(&weights, &stakes), n);
+            let expected = normalize(matmul_transpose_sparse(&bonds_delta, &incentives));
+
+            let bonds = mat_ema_sparse(&bonds_delta, &previous_bonds, FixedU128::from_u32(1));
+            let dividends = normalize(matmul_transpose_sparse(&bonds, &incentives));
+
+            assert_eq!(dividends, expected);
+        }
+    }
+
+    #[test]
+    fn test_math_ema_bonds_stay_col_normalized() {
+        let epsilon = FixedU128::from_float(0.000000001);
+        let mut rng = Rng(0xc01);
+
+        for _ in 0..200 {
+            let n = (rng.next_u64() % 8 + 1) as usize;
+            let alpha = FixedU128::from_rational(rng.next_u64() % 100, 100);
+
+            let bonds_delta = col_normalize_sparse(random_sparse_mat(&mut rng, n, n), n);
+            let previous_bonds = col_normalize_sparse(random_sparse_mat(&mut rng, n, n), n);
+
+            let bonds = mat_ema_sparse(&bonds_delta, &previous_bonds, alpha);
+            let bonds = col_normalize_sparse(bonds, n);
+
+            for sum in col_sums(&bonds, n) {
+                if sum != FixedU128::from_inner(0) {
+                    assert_float_compare(sum, FixedU128::from_u32(1), epsilon);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_math_weighted_median_col_sparse() {
+        let one = FixedU128::from_u32(1);
+        let half = FixedU128::from_rational(1, 2);
+
+        let stake = vec![
+            FixedU128::from_rational(3, 10),
+            FixedU128::from_rational(3, 10),
+            FixedU128::from_rational(4, 10),
+        ];
+        let weights = vec![vec![(0, one)], vec![(0, half), (1, half)], vec![(1, one)]];
+
+        // Column 0 has 30% of the stake at 1 and 60% at 0.5 or more.
+        // Column 1 has 40% of the stake at 1 and 70% at 0.5 or more.
+        let consensus = weighted_median_col_sparse(&stake, &weights, 2, half);
+        assert_vec_compare(&consensus, &[half, half], FixedU128::from_inner(0));
+
+        let consensus =
+            weighted_median_col_sparse(&stake, &weights, 2, FixedU128::from_rational(35, 100));
+        assert_vec_compare(&consensus, &[half, one], FixedU128::from_inner(0));
+
+        let consensus = weighted_median_col_sparse(&stake, &weights, 2, FixedU128::from_inner(0));
+        assert_vec_compare(&consensus, &[one, one], FixedU128::from_inner(0));
+
+        let consensus = weighted_median_col_sparse(&stake, &weights, 2, one);
+        assert_vec_compare(&consensus, &[0.into(), 0.into()], FixedU128::from_inner(0));
+
+        let clipped = col_clip_sparse(&weights, &[half, 0.into()]);
+        assert_eq!(clipped, vec![vec![(0, half)], vec![(0, half)], vec![]]);
+        assert_eq!(row_sum_sparse(&clipped), vec![half, half, 0.into()]);
+    }
+
+    #[test]
+    fn test_math_weighted_median_col_sparse_ignores_row_order() {
+        let mut rng = Rng(0x3ed1a);
+
+        for _ in 0..200 {
+            let n = (rng.next_u64() % 8 + 1) as usize;
+            let majority = FixedU128::from_rational(rng.next_u64() % 101, 100);
+
+            let weights = random_sparse_mat(&mut rng, n, n);
+            let stake = normalize((0..n).map(|_| rng.fixed()).collect());
+            let consensus = weighted_median_col_sparse(&stake, &weights, n, majority);
+
+            let reversed_weights: Vec<_> = weights.iter().rev().cloned().collect();
+            let reversed_stake: Vec<_> = stake.iter().rev().copied().collect();
+            assert_eq!(
+                weighted_median_col_sparse(&reversed_stake, &reversed_weights, n, majority),
+                consensus
+            );
+
+            let clipped = col_clip_sparse(&weights, &consensus);
+            for (row, clipped_row) in weights.iter().zip(&clipped) {
+                for (j, value) in clipped_row {
+                    assert!(*value <= consensus[*j]);
+                    assert!(row.contains(&(*j, *value)) || *value == consensus[*j]);
+                }
+            }
+        }
+    }
 }
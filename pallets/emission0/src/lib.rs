@@ -9,9 +9,10 @@ use pallet_emission0_api::Emission0Api;
 use polkadot_sdk::{
     frame_support::{DefaultNoBound, dispatch::DispatchResult, pallet_prelude::*},
     frame_system,
-    frame_system::pallet_prelude::OriginFor,
+    frame_system::pallet_prelude::{BlockNumberFor, OriginFor},
     polkadot_sdk_frame::{self as frame, traits::Currency},
-    sp_runtime::Percent,
+    sp_runtime::{BoundedBTreeMap, FixedU128, Percent},
+    sp_std::vec::Vec,
 };
 
 #[doc(hidden)]
@@ -24,11 +25,10 @@ pub mod weights;
 
 #[frame::pallet]
 pub mod pallet {
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(4);
 
     use core::num::NonZeroU128;
 
-    use frame::prelude::BlockNumberFor;
     use frame_system::ensure_signed;
     use pallet_governance_api::GovernanceApi;
     use pallet_permission0_api::{Permission0Api, Permission0StreamApi};
@@ -45,12 +45,45 @@ pub mod pallet {
     pub type ConsensusMembers<T: Config> =
         StorageMap<_, Identity, AccountIdOf<T>, ConsensusMember<T>>;
 
-    /// Map of agents delegating weight control to other agents. Emissions
-    /// derived from weight delegation are taxed and the fees go the original
-    /// weight setter.
+    /// Consensus members setting weights on each agent, indexed by the agent.
+    /// Reverse index of the weights in [`ConsensusMembers`].
+    #[pallet::storage]
+    pub type WeightSetters<T: Config> =
+        StorageDoubleMap<_, Identity, T::AccountId, Identity, T::AccountId, ()>;
+
+    /// Map of agents delegating weight control to other agents, with the
+    /// share of the weight control given to each of them. Emissions derived
+    /// from weight delegation are taxed and the fees go the original weight
+    /// setters.
     #[pallet::storage]
     pub type WeightControlDelegation<T: Config> =
-        StorageMap<_, Identity, T::AccountId, T::AccountId>;
+        StorageMap<_, Identity, T::AccountId, WeightControlShares<T>>;
+
+    /// Agents delegating weight control to each delegatee, indexed by the
+    /// delegatee. Reverse index of [`WeightControlDelegation`].
+    #[pallet::storage]
+    pub type WeightControlDelegators<T: Config> =
+        StorageDoubleMap<_, Identity, T::AccountId, Identity, T::AccountId, ()>;
+
+    /// Number of agents delegating weight control to each delegatee.
+    #[pallet::storage]
+    pub type WeightControlDelegatorCount<T: Config> =
+        StorageMap<_, Identity, T::AccountId, u32, ValueQuery>;
+
+    /// Whether weights must be committed with [`Pallet::commit_weights`] and
+    /// later revealed with [`Pallet::reveal_weights`], instead of being set
+    /// in clear with [`Pallet::set_weights`].
+    #[pallet::storage]
+    pub type WeightsCommitReveal<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Whether weight control can be delegated to any whitelisted agent with
+    /// enough stake to set weights, instead of only to allocators.
+    #[pallet::storage]
+    pub type OpenWeightDelegation<T: Config> = StorageValue<_, bool, ValueQuery>;
+
+    /// Weight commits waiting to be revealed, indexed by the committing agent.
+    #[pallet::storage]
+    pub type WeightCommits<T: Config> = StorageMap<_, Identity, T::AccountId, WeightCommit<T>>;
 
     /// Percentage of issued tokens to be burned every epoch.
     #[pallet::storage]
@@ -63,11 +96,39 @@ pub mod pallet {
     pub type IncentivesRatio<T: Config> =
         StorageValue<_, Percent, ValueQuery, T::DefaultIncentivesRatio>;
 
+    /// Weight given to the bonds formed in the current epoch when updating
+    /// the moving average of [`Bonds`]. 100% disables the smoothing, and
+    /// dividends follow the current weights only.
+    #[pallet::storage]
+    pub type BondsMovingAverage<T: Config> =
+        StorageValue<_, Percent, ValueQuery, T::DefaultBondsMovingAverage>;
+
+    /// Moving average of the bonds each validator holds on the agents it set
+    /// weights for. Dividends are distributed according to these bonds, so
+    /// support held for longer yields more than weights flipped every epoch.
+    #[pallet::storage]
+    pub type Bonds<T: Config> = StorageMap<_, Identity, AccountIdOf<T>, ValidatorBonds<T>>;
+
+    /// Parameters of the optional consensus step clipping validator weights to
+    /// the stake-weighted majority. Disabled by default.
+    #[pallet::storage]
+    pub type ConsensusConfig<T: Config> = StorageValue<_, ConsensusConfiguration, ValueQuery>;
+
     /// Amount of tokens accumulated since the last epoch. This increases on
     /// every block. See [`distribute::get_total_emission_per_block`].
     #[pallet::storage]
     pub type PendingEmission<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+    /// Number of epochs distributed so far. Also the index of the next epoch
+    /// report.
+    #[pallet::storage]
+    pub type EpochCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Summaries of the last distributed epochs, indexed by the epoch number.
+    /// Only the last [`Config::MaxEpochReports`] epochs are kept.
+    #[pallet::storage]
+    pub type EpochReports<T: Config> = StorageMap<_, Identity, u64, EpochReport<T>>;
+
     #[pallet::config]
     pub trait Config: polkadot_sdk::frame_system::Config {
         type RuntimeEvent: From<Event<Self>>
@@ -92,6 +153,29 @@ pub mod pallet {
         #[pallet::constant]
         type DefaultIncentivesRatio: Get<Percent>;
 
+        #[pallet::constant]
+        type DefaultBondsMovingAverage: Get<Percent>;
+
+        /// Number of epoch reports kept on-chain.
+        #[pallet::constant]
+        type MaxEpochReports: Get<u32>;
+
+        /// Maximum number of consensus agents tracked per epoch. Should be at
+        /// least the number of agents that can take part in consensus, agents
+        /// over it are left out of the epoch report and of validator bonds.
+        #[pallet::constant]
+        type MaxConsensusAgents: Get<u32>;
+
+        /// Maximum number of hops between an agent and the agent whose weights
+        /// it uses through weight control delegation.
+        #[pallet::constant]
+        type MaxWeightDelegationDepth: Get<u32>;
+
+        /// Maximum number of agents an agent can split its weight control
+        /// between.
+        #[pallet::constant]
+        type MaxWeightDelegatees: Get<u32>;
+
         type Currency: Currency<Self::AccountId, Balance = u128> + Send + Sync;
 
         type Torus: Torus0Api<Self::AccountId, BalanceOf<Self>>;
@@ -148,6 +232,40 @@ pub mod pallet {
 
         /// At the current state, agents cannot control their own weight.
         WeightControlNotEnabled,
+
+        /// Tried committing or revealing weights while commit-reveal is
+        /// disabled.
+        CommitRevealNotEnabled,
+
+        /// Tried setting weights in clear while commit-reveal is enabled.
+        WeightsMustBeCommitted,
+
+        /// Tried revealing weights without committing them first.
+        NoWeightCommit,
+
+        /// Weights can only be revealed in the epoch after the commit.
+        RevealTooEarly,
+
+        /// The epoch in which the committed weights could be revealed is over.
+        RevealWindowExpired,
+
+        /// The revealed weights and salt do not match the committed hash.
+        InvalidWeightsReveal,
+
+        /// The allocator reached the number of delegating agents it accepts.
+        AllocatorAtCapacity,
+
+        /// The delegation would make weight control loop back to the delegator.
+        WeightDelegationCycle,
+
+        /// The delegation would make a delegation chain longer than
+        /// [`Config::MaxWeightDelegationDepth`].
+        WeightDelegationTooDeep,
+
+        /// The weight control shares are empty, repeat an agent, contain a
+        /// zero share, exceed [`Config::MaxWeightDelegatees`] or do not add up
+        /// to 100%.
+        InvalidWeightControlShares,
     }
 
     #[pallet::event]
@@ -157,6 +275,12 @@ pub mod pallet {
         WeightsSet(T::AccountId),
         /// An agent gave weight control to the second agent.
         DelegatedWeightControl(T::AccountId, T::AccountId),
+        /// An agent split its weight control between the given agents.
+        DelegatedWeightControlShares(T::AccountId, Vec<(T::AccountId, Percent)>),
+        /// An epoch was distributed. Contains the epoch number and its report.
+        EpochDistributed(u64, EpochReport<T>),
+        /// An agent committed to a set of weights to be revealed later.
+        WeightsCommitted(T::AccountId),
     }
 
     #[pallet::call]
@@ -185,9 +309,45 @@ pub mod pallet {
         pub fn regain_weight_control(origin: OriginFor<T>) -> DispatchResult {
             weight_control::regain_weight_control::<T>(origin)
         }
+
+        #[pallet::call_index(3)]
+        #[pallet::weight((T::WeightInfo::commit_weights(), DispatchClass::Normal, Pays::Yes))]
+        pub fn commit_weights(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+            weight_control::commit_weights::<T>(origin, hash)
+        }
+
+        #[pallet::call_index(4)]
+        #[pallet::weight((T::WeightInfo::reveal_weights(), DispatchClass::Normal, Pays::Yes))]
+        pub fn reveal_weights(
+            origin: OriginFor<T>,
+            weights: sp_std::vec::Vec<(AccountIdOf<T>, u16)>,
+            salt: sp_std::vec::Vec<u8>,
+        ) -> DispatchResult {
+            weight_control::reveal_weights::<T>(origin, weights, salt)
+        }
+
+        /// Splits the weight control between several agents. Each share is the
+        /// portion of the weights and of the weight control fees that goes
+        /// through the agent, and all shares must add up to 100%.
+        #[pallet::call_index(5)]
+        #[pallet::weight((T::WeightInfo::delegate_weight_control(), DispatchClass::Normal, Pays::Yes))]
+        pub fn delegate_weight_control_shares(
+            origin: OriginFor<T>,
+            shares: sp_std::vec::Vec<(AccountIdOf<T>, Percent)>,
+        ) -> DispatchResult {
+            let origin = ensure_signed(origin)?;
+            weight_control::delegate_weight_control_shares::<T>(origin, shares)
+        }
     }
 }
 
+/// Shares of the weight control given to each delegatee, adding up to 100%.
+pub type WeightControlShares<T> = BoundedBTreeMap<
+    <T as frame_system::Config>::AccountId,
+    Percent,
+    <T as Config>::MaxWeightDelegatees,
+>;
+
 pub type Weights<T> =
     BoundedVec<(<T as frame_system::Config>::AccountId, u16), ConstU32<{ u32::MAX }>>;
 
@@ -205,6 +365,111 @@ impl<T: Config> ConsensusMember<T> {
     }
 }
 
+/// A hidden set of weights. See [`WeightCommits`].
+#[derive(
+    CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Decode, Encode, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct WeightCommit<T: Config> {
+    /// Hash of the agent, weights and salt. See
+    /// [`weight_control::weights_commit_hash`].
+    pub hash: T::Hash,
+    /// Block in which the weights were committed.
+    pub block: BlockNumberFor<T>,
+}
+
+/// See [`ConsensusConfig`].
+#[derive(Clone, Debug, PartialEq, Eq, Decode, Encode, MaxEncodedLen, TypeInfo)]
+pub struct ConsensusConfiguration {
+    /// Whether weights are clipped to the consensus before computing
+    /// incentives and bonds.
+    pub enabled: bool,
+    /// Fraction of the stake (κ) that must assign at least a given weight to
+    /// an agent for that weight to be kept. 50% clips weights to the
+    /// stake-weighted median.
+    pub kappa: Percent,
+    /// How much of its dividends a validator loses for the fraction of its
+    /// weights that was clipped. At 100%, a validator whose weights were
+    /// entirely clipped receives no dividends.
+    pub divergence_penalty: Percent,
+}
+
+impl Default for ConsensusConfiguration {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            kappa: Percent::from_percent(50),
+            divergence_penalty: Percent::one(),
+        }
+    }
+}
+
+/// Bonds held by a validator, normalized over all validators bonding the
+/// same agent. See [`Bonds`].
+pub type ValidatorBonds<T> = BoundedVec<
+    (<T as frame_system::Config>::AccountId, FixedU128),
+    <T as Config>::MaxConsensusAgents,
+>;
+
+/// Amounts received by each agent in an epoch, bounded by
+/// [`Config::MaxConsensusAgents`].
+pub type EpochAgents<T> = BoundedVec<
+    pallet_emission0_api::AgentEmission<AccountIdOf<T>, BalanceOf<T>>,
+    <T as Config>::MaxConsensusAgents,
+>;
+
+/// Summary of a distributed epoch. See [`EpochReports`].
+#[derive(
+    CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Decode, Encode, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct EpochReport<T: Config> {
+    /// Block in which the epoch was distributed.
+    pub block: BlockNumberFor<T>,
+    /// Total amount issued for this epoch, including the treasury cut.
+    pub emitted: BalanceOf<T>,
+    /// Amount sent to the DAO treasury.
+    pub treasury_fee: BalanceOf<T>,
+    /// Amount not distributed, returned to [`PendingEmission`].
+    pub remaining: BalanceOf<T>,
+    /// Amounts received by each agent. Agents that received nothing are
+    /// omitted.
+    pub agents: EpochAgents<T>,
+}
+
+impl<T: Config> From<EpochReport<T>>
+    for pallet_emission0_api::EpochReport<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>
+{
+    fn from(report: EpochReport<T>) -> Self {
+        Self {
+            block: report.block,
+            emitted: report.emitted,
+            treasury_fee: report.treasury_fee,
+            remaining: report.remaining,
+            agents: report.agents.into_inner(),
+        }
+    }
+}
+
+impl<T: Config> Pallet<T> {
+    /// Returns the reports of the last `count` epochs still kept on-chain,
+    /// from the most recent to the oldest.
+    pub fn epoch_reports(
+        count: u32,
+    ) -> Vec<(
+        u64,
+        pallet_emission0_api::EpochReport<AccountIdOf<T>, BalanceOf<T>, BlockNumberFor<T>>,
+    )> {
+        let count = count.min(T::MaxEpochReports::get());
+
+        (0..EpochCount::<T>::get())
+            .rev()
+            .take(count as usize)
+            .filter_map(|epoch| Some((epoch, EpochReports::<T>::get(epoch)?.into())))
+            .collect()
+    }
+}
+
 impl<T: Config> Emission0Api<T::AccountId> for Pallet<T> {
     fn consensus_stats(
         member: &T::AccountId,
@@ -223,4 +488,12 @@ impl<T: Config> Emission0Api<T::AccountId> for Pallet<T> {
     ) -> DispatchResult {
         weight_control::delegate_weight_control::<T>(delegator.clone(), delegatee.clone())
     }
+
+    fn key_references(key: &T::AccountId) -> u32 {
+        weight_control::key_references::<T>(key)
+    }
+
+    fn rotate_key(old_key: &T::AccountId, new_key: &T::AccountId) {
+        weight_control::rotate_key::<T>(old_key, new_key)
+    }
 }
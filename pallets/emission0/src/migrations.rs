@@ -1 +1,72 @@
+use polkadot_sdk::frame_support::{
+    migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, weights::Weight,
+};
 
+use crate::{Config, Pallet};
+
+pub mod v3 {
+    use polkadot_sdk::{sp_std::marker::PhantomData, sp_tracing::info};
+
+    use super::*;
+
+    /// Seeds the validator bonds from the weights set before the upgrade.
+    pub type Migration<T, W> = VersionedMigration<2, 3, MigrateToV3<T>, Pallet<T>, W>;
+
+    pub struct MigrateToV3<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for MigrateToV3<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let validators = crate::distribute::seed_bonds::<T>();
+            info!("seeded bonds for {validators} validators");
+
+            Weight::zero()
+        }
+    }
+}
+
+pub mod v4 {
+    use polkadot_sdk::{sp_std::marker::PhantomData, sp_tracing::info};
+
+    use super::*;
+    use crate::{
+        ConsensusMembers, WeightControlDelegation, WeightControlDelegatorCount,
+        WeightControlDelegators,
+        weight_control::{index_weights, whole_weight_control},
+    };
+
+    /// Turns each weight control delegation into a single 100% share, and
+    /// indexes the delegators of each delegatee and the setters of the
+    /// weights on each agent.
+    pub type Migration<T, W> = VersionedMigration<3, 4, MigrateToV4<T>, Pallet<T>, W>;
+
+    pub struct MigrateToV4<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for MigrateToV4<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut delegations = 0u32;
+
+            WeightControlDelegation::<T>::translate::<T::AccountId, _>(|delegator, delegatee| {
+                delegations = delegations.saturating_add(1);
+
+                WeightControlDelegators::<T>::insert(&delegatee, &delegator, ());
+                WeightControlDelegatorCount::<T>::mutate(&delegatee, |count| {
+                    *count = count.saturating_add(1)
+                });
+
+                Some(whole_weight_control::<T>(delegatee))
+            });
+
+            info!("migrated {delegations} weight control delegations");
+
+            let mut members = 0u32;
+            for (setter, member) in ConsensusMembers::<T>::iter() {
+                members = members.saturating_add(1);
+                index_weights::<T>(&setter, &member.weights);
+            }
+
+            info!("indexed the weights of {members} consensus members");
+
+            Weight::zero()
+        }
+    }
+}
@@ -1,34 +1,160 @@
 use pallet_governance_api::GovernanceApi;
-use pallet_torus0_api::Torus0Api;
+use pallet_torus0_api::{ProxyOperation, Torus0Api};
 use polkadot_sdk::{
-    frame_support::{dispatch::DispatchResult, ensure},
-    frame_system::ensure_signed,
-    polkadot_sdk_frame::prelude::OriginFor,
+    frame_support::{dispatch::DispatchResult, ensure, traits::Get},
+    frame_system::{self, ensure_signed},
+    polkadot_sdk_frame::prelude::{BlockNumberFor, OriginFor},
     sp_core::ConstU32,
-    sp_runtime::BoundedVec,
-    sp_std,
+    sp_runtime::{
+        BoundedVec, DispatchError, Percent,
+        traits::{Hash, Saturating, UniqueSaturatedInto},
+    },
+    sp_std::{self, collections::btree_map::BTreeMap, vec, vec::Vec},
 };
 
-use crate::{ConsensusMember, ConsensusMembers};
+use crate::{
+    ConsensusMember, ConsensusMembers, WeightCommit, WeightControlShares, WeightSetters, Weights,
+};
 
 pub fn set_weights<T: crate::Config>(
     origin: OriginFor<T>,
-    mut weights: sp_std::vec::Vec<(T::AccountId, u16)>,
+    weights: sp_std::vec::Vec<(T::AccountId, u16)>,
 ) -> DispatchResult {
-    let acc_id = ensure_signed(origin)?;
-    <T::Governance>::ensure_allocator(&acc_id)?;
+    let acc_id = ensure_weight_setter::<T>(origin)?;
+    ensure_can_set_weights::<T>(&acc_id)?;
+
+    ensure!(
+        !crate::WeightsCommitReveal::<T>::get(),
+        crate::Error::<T>::WeightsMustBeCommitted
+    );
+
+    update_weights::<T>(acc_id, weights)
+}
+
+/// Commits to a set of weights without publishing them. The weights must be
+/// revealed with [`reveal_weights`] during the epoch following the commit.
+/// Committing again replaces the previous commit.
+pub fn commit_weights<T: crate::Config>(origin: OriginFor<T>, hash: T::Hash) -> DispatchResult {
+    let acc_id = ensure_weight_setter::<T>(origin)?;
+    ensure_can_set_weights::<T>(&acc_id)?;
 
     ensure!(
-        !crate::WeightControlDelegation::<T>::contains_key(&acc_id),
+        crate::WeightsCommitReveal::<T>::get(),
+        crate::Error::<T>::CommitRevealNotEnabled
+    );
+
+    crate::WeightCommits::<T>::insert(
+        &acc_id,
+        WeightCommit::<T> {
+            hash,
+            block: <frame_system::Pallet<T>>::block_number(),
+        },
+    );
+
+    crate::Pallet::<T>::deposit_event(crate::Event::<T>::WeightsCommitted(acc_id));
+
+    Ok(())
+}
+
+/// Reveals the weights committed in the previous epoch, setting them. Commits
+/// not revealed in time are discarded, and the agent keeps its previous
+/// weights.
+pub fn reveal_weights<T: crate::Config>(
+    origin: OriginFor<T>,
+    weights: sp_std::vec::Vec<(T::AccountId, u16)>,
+    salt: sp_std::vec::Vec<u8>,
+) -> DispatchResult {
+    let acc_id = ensure_weight_setter::<T>(origin)?;
+    ensure_can_set_weights::<T>(&acc_id)?;
+
+    ensure!(
+        crate::WeightsCommitReveal::<T>::get(),
+        crate::Error::<T>::CommitRevealNotEnabled
+    );
+
+    let commit =
+        crate::WeightCommits::<T>::get(&acc_id).ok_or(crate::Error::<T>::NoWeightCommit)?;
+
+    let commit_epoch = epoch_of::<T>(commit.block);
+    let current_epoch = epoch_of::<T>(<frame_system::Pallet<T>>::block_number());
+    ensure!(
+        current_epoch > commit_epoch,
+        crate::Error::<T>::RevealTooEarly
+    );
+    ensure!(
+        current_epoch == commit_epoch.saturating_add(1),
+        crate::Error::<T>::RevealWindowExpired
+    );
+
+    ensure!(
+        commit.hash == weights_commit_hash::<T>(&acc_id, &weights, &salt),
+        crate::Error::<T>::InvalidWeightsReveal
+    );
+
+    crate::WeightCommits::<T>::remove(&acc_id);
+
+    update_weights::<T>(acc_id, weights)
+}
+
+/// Hash to be committed with [`commit_weights`] for the given weights.
+pub fn weights_commit_hash<T: crate::Config>(
+    agent: &T::AccountId,
+    weights: &[(T::AccountId, u16)],
+    salt: &[u8],
+) -> T::Hash {
+    T::Hashing::hash_of(&(agent, weights, salt))
+}
+
+/// Removes the commits that can no longer be revealed.
+pub(crate) fn clear_expired_commits<T: crate::Config>(block_number: BlockNumberFor<T>) {
+    let current_epoch = epoch_of::<T>(block_number);
+
+    let expired: sp_std::vec::Vec<_> = crate::WeightCommits::<T>::iter()
+        .filter(|(_, commit)| epoch_of::<T>(commit.block).saturating_add(1) < current_epoch)
+        .map(|(agent, _)| agent)
+        .collect();
+
+    for agent in expired {
+        crate::WeightCommits::<T>::remove(agent);
+    }
+}
+
+/// Index of the epoch the block belongs to. Epochs start right after the
+/// emission is distributed.
+fn epoch_of<T: crate::Config>(block_number: BlockNumberFor<T>) -> u64 {
+    let block_number: u64 = block_number.unique_saturated_into();
+    block_number
+        .checked_div(<T::Torus>::reward_interval().into())
+        .unwrap_or_default()
+}
+
+/// Returns the agent setting weights, resolving proxy keys allowed to set
+/// weights on its behalf.
+fn ensure_weight_setter<T: crate::Config>(
+    origin: OriginFor<T>,
+) -> Result<T::AccountId, DispatchError> {
+    let who = ensure_signed(origin)?;
+    Ok(<T::Torus>::resolve_proxy(&who, ProxyOperation::SetWeights))
+}
+
+fn ensure_can_set_weights<T: crate::Config>(acc_id: &T::AccountId) -> DispatchResult {
+    // Once governance opened weight delegation, agents receiving it without
+    // being allocators must be able to set the weights delegated to them.
+    if !crate::OpenWeightDelegation::<T>::get() {
+        <T::Governance>::ensure_allocator(acc_id)?;
+    }
+
+    ensure!(
+        !crate::WeightControlDelegation::<T>::contains_key(acc_id),
         crate::Error::<T>::CannotSetWeightsWhileDelegating,
     );
 
     ensure!(
-        <T::Torus>::is_agent_registered(&acc_id) && <T::Governance>::is_whitelisted(&acc_id),
+        <T::Torus>::is_agent_registered(acc_id) && <T::Governance>::is_whitelisted(acc_id),
         crate::Error::<T>::AgentIsNotRegistered
     );
 
-    let total_stake: u128 = <T::Torus>::staked_by(&acc_id)
+    let total_stake: u128 = <T::Torus>::staked_by(acc_id)
         .iter()
         .map(|(_, stake)| *stake)
         .sum();
@@ -37,6 +163,13 @@ pub fn set_weights<T: crate::Config>(
         crate::Error::<T>::NotEnoughStakeToSetWeights
     );
 
+    Ok(())
+}
+
+fn update_weights<T: crate::Config>(
+    acc_id: T::AccountId,
+    mut weights: sp_std::vec::Vec<(T::AccountId, u16)>,
+) -> DispatchResult {
     for (target, _) in &weights {
         ensure!(
             &acc_id != target,
@@ -57,6 +190,8 @@ pub fn set_weights<T: crate::Config>(
 
     ConsensusMembers::<T>::mutate(&acc_id, |member: &mut Option<ConsensusMember<T>>| {
         let member = member.get_or_insert_with(Default::default);
+        unindex_weights::<T>(&acc_id, &member.weights);
+        index_weights::<T>(&acc_id, &weights);
         member.update_weights(weights);
     });
 
@@ -65,12 +200,52 @@ pub fn set_weights<T: crate::Config>(
     Ok(())
 }
 
+/// Adds the weights set by `setter` to the [`WeightSetters`] index.
+pub(crate) fn index_weights<T: crate::Config>(setter: &T::AccountId, weights: &Weights<T>) {
+    for (target, _) in weights.iter() {
+        WeightSetters::<T>::insert(target, setter, ());
+    }
+}
+
+/// Removes the weights set by `setter` from the [`WeightSetters`] index.
+pub(crate) fn unindex_weights<T: crate::Config>(setter: &T::AccountId, weights: &Weights<T>) {
+    for (target, _) in weights.iter() {
+        WeightSetters::<T>::remove(target, setter);
+    }
+}
+
 pub fn delegate_weight_control<T: crate::Config>(
     delegator: T::AccountId,
     delegatee: T::AccountId,
 ) -> DispatchResult {
+    delegate_weight_control_shares::<T>(delegator, vec![(delegatee, Percent::one())])
+}
+
+/// Splits the weight control of the delegator between the given delegatees.
+/// The shares must add up to 100%.
+pub fn delegate_weight_control_shares<T: crate::Config>(
+    delegator: T::AccountId,
+    shares: Vec<(T::AccountId, Percent)>,
+) -> DispatchResult {
+    let total_shares = shares.iter().fold(0u32, |acc, (_, share)| {
+        acc.saturating_add(share.deconstruct().into())
+    });
+    ensure!(
+        total_shares == 100 && shares.iter().all(|(_, share)| !share.is_zero()),
+        crate::Error::<T>::InvalidWeightControlShares
+    );
+
+    let shares_len = shares.len();
+    let shares: WeightControlShares<T> = BTreeMap::from_iter(shares)
+        .try_into()
+        .map_err(|_| crate::Error::<T>::InvalidWeightControlShares)?;
     ensure!(
-        delegator != delegatee,
+        shares.len() == shares_len,
+        crate::Error::<T>::InvalidWeightControlShares
+    );
+
+    ensure!(
+        !shares.contains_key(&delegator),
         crate::Error::<T>::CannotDelegateWeightControlToSelf,
     );
 
@@ -79,20 +254,158 @@ pub fn delegate_weight_control<T: crate::Config>(
         crate::Error::<T>::AgentIsNotRegistered
     );
 
+    for delegatee in shares.keys() {
+        ensure_can_receive_weight_control::<T>(&delegator, delegatee)?;
+    }
+
+    ensure_valid_delegation_chain::<T>(&delegator, shares.keys())?;
+
+    set_delegation::<T>(&delegator, Some(shares.clone()));
+
+    let event = match shares.iter().next() {
+        Some((delegatee, _)) if shares.len() == 1 => {
+            crate::Event::<T>::DelegatedWeightControl(delegator, delegatee.clone())
+        }
+        _ => {
+            crate::Event::<T>::DelegatedWeightControlShares(delegator, shares.into_iter().collect())
+        }
+    };
+    crate::Pallet::<T>::deposit_event(event);
+
+    Ok(())
+}
+
+/// Sets or clears the weight control delegation of `delegator`, keeping
+/// [`crate::WeightControlDelegators`] and
+/// [`crate::WeightControlDelegatorCount`] in sync.
+pub fn set_delegation<T: crate::Config>(
+    delegator: &T::AccountId,
+    shares: Option<WeightControlShares<T>>,
+) {
+    if let Some(previous) = crate::WeightControlDelegation::<T>::take(delegator) {
+        for delegatee in previous.keys() {
+            crate::WeightControlDelegators::<T>::remove(delegatee, delegator);
+            crate::WeightControlDelegatorCount::<T>::mutate_exists(delegatee, |count| {
+                *count = count
+                    .map(|count| count.saturating_sub(1))
+                    .filter(|count| *count > 0);
+            });
+        }
+    }
+
+    let Some(shares) = shares else {
+        return;
+    };
+
+    for delegatee in shares.keys() {
+        crate::WeightControlDelegators::<T>::insert(delegatee, delegator, ());
+        crate::WeightControlDelegatorCount::<T>::mutate(delegatee, |count| {
+            *count = count.saturating_add(1)
+        });
+    }
+
+    crate::WeightControlDelegation::<T>::insert(delegator, shares);
+}
+
+/// Shares delegating the whole weight control to a single agent.
+pub fn whole_weight_control<T: crate::Config>(delegatee: T::AccountId) -> WeightControlShares<T> {
+    let mut shares = WeightControlShares::<T>::new();
+    let _ = shares.try_insert(delegatee, Percent::one());
+    shares
+}
+
+fn ensure_can_receive_weight_control<T: crate::Config>(
+    delegator: &T::AccountId,
+    delegatee: &T::AccountId,
+) -> DispatchResult {
     ensure!(
-        <T::Torus>::is_agent_registered(&delegatee) && <T::Governance>::is_whitelisted(&delegatee),
+        <T::Torus>::is_agent_registered(delegatee) && <T::Governance>::is_whitelisted(delegatee),
         crate::Error::<T>::AgentIsNotRegistered
     );
 
-    // At the current network stage, it only makes sense to delegate weight control
-    // to allocators.
-    <T::Governance>::ensure_allocator(&delegatee)?;
+    // Unless governance opened weight delegation, it only makes sense to delegate
+    // weight control to allocators.
+    if !crate::OpenWeightDelegation::<T>::get() {
+        <T::Governance>::ensure_allocator(delegatee)?;
+    } else if <T::Governance>::ensure_allocator(delegatee).is_err() {
+        let total_stake: u128 = <T::Torus>::staked_by(delegatee)
+            .iter()
+            .map(|(_, stake)| *stake)
+            .sum();
+        ensure!(
+            total_stake >= <T::Torus>::min_validator_stake(),
+            crate::Error::<T>::NotEnoughStakeToSetWeights
+        );
+    }
+
+    if let Some(capacity) = <T::Governance>::allocator_capacity(delegatee) {
+        let mut delegators = crate::WeightControlDelegatorCount::<T>::get(delegatee);
+        if crate::WeightControlDelegators::<T>::contains_key(delegatee, delegator) {
+            delegators = delegators.saturating_sub(1);
+        }
+        ensure!(
+            delegators < capacity,
+            crate::Error::<T>::AllocatorAtCapacity
+        );
+    }
+
+    Ok(())
+}
+
+/// Ensures that delegating weight control from `delegator` to `delegatees`
+/// does not loop back to the delegator, and that no agent ends up more than
+/// [`crate::Config::MaxWeightDelegationDepth`] hops away from the agents whose
+/// weights it uses.
+fn ensure_valid_delegation_chain<'a, T: crate::Config>(
+    delegator: &T::AccountId,
+    delegatees: impl IntoIterator<Item = &'a T::AccountId>,
+) -> DispatchResult {
+    let max_depth = T::MaxWeightDelegationDepth::get();
+
+    // Hops from the delegator to the agents setting the weights.
+    let mut depth = 1u32;
+    let mut level: Vec<_> = delegatees.into_iter().cloned().collect();
+    loop {
+        level = level
+            .iter()
+            .filter_map(crate::WeightControlDelegation::<T>::get)
+            .flat_map(|shares| shares.into_keys())
+            .collect();
+        if level.is_empty() {
+            break;
+        }
 
-    crate::WeightControlDelegation::<T>::set(&delegator, Some(delegatee.clone()));
+        ensure!(
+            !level.contains(delegator),
+            crate::Error::<T>::WeightDelegationCycle
+        );
 
-    crate::Pallet::<T>::deposit_event(crate::Event::<T>::DelegatedWeightControl(
-        delegator, delegatee,
-    ));
+        depth = depth.saturating_add(1);
+        ensure!(
+            depth <= max_depth,
+            crate::Error::<T>::WeightDelegationTooDeep
+        );
+    }
+
+    // Agents delegating to the delegator, directly or not, move further away
+    // from the weights as well.
+    let mut level = vec![delegator.clone()];
+    loop {
+        level = level
+            .iter()
+            .flat_map(|agent| crate::WeightControlDelegators::<T>::iter_key_prefix(agent))
+            .filter(|from| from != delegator)
+            .collect();
+        if level.is_empty() {
+            break;
+        }
+
+        depth = depth.saturating_add(1);
+        ensure!(
+            depth <= max_depth,
+            crate::Error::<T>::WeightDelegationTooDeep
+        );
+    }
 
     Ok(())
 }
@@ -100,12 +413,102 @@ pub fn delegate_weight_control<T: crate::Config>(
 pub fn regain_weight_control<T: crate::Config>(origin: OriginFor<T>) -> DispatchResult {
     let acc_id = ensure_signed(origin)?;
 
-    if <T::Governance>::ensure_allocator(&acc_id).is_err() {
+    if !crate::OpenWeightDelegation::<T>::get()
+        && <T::Governance>::ensure_allocator(&acc_id).is_err()
+    {
         return Err(crate::Error::<T>::WeightControlNotEnabled.into());
     }
 
-    crate::WeightControlDelegation::<T>::mutate(acc_id, |val| match val.take() {
-        Some(_) => Ok(()),
-        None => Err(crate::Error::<T>::AgentIsNotDelegating.into()),
-    })
+    ensure!(
+        crate::WeightControlDelegation::<T>::contains_key(&acc_id),
+        crate::Error::<T>::AgentIsNotDelegating
+    );
+    set_delegation::<T>(&acc_id, None);
+
+    Ok(())
+}
+
+/// Moves the consensus membership, weight control delegation and bonds of an
+/// agent key to the key replacing it, and rewrites the weights, delegations
+/// and bonds other agents hold on the old key. Unrevealed weight commits are
+/// dropped, as their hash is bound to the old key.
+pub fn rotate_key<T: crate::Config>(old_key: &T::AccountId, new_key: &T::AccountId) {
+    let rotate = |key: &mut T::AccountId| {
+        if key == old_key {
+            *key = new_key.clone();
+        }
+    };
+
+    if let Some(member) = ConsensusMembers::<T>::take(old_key) {
+        unindex_weights::<T>(old_key, &member.weights);
+        index_weights::<T>(new_key, &member.weights);
+        ConsensusMembers::<T>::insert(new_key, member);
+    }
+
+    if let Some(shares) = crate::WeightControlDelegation::<T>::get(old_key) {
+        set_delegation::<T>(old_key, None);
+        set_delegation::<T>(new_key, Some(shares));
+    }
+
+    if let Some(bonds) = crate::Bonds::<T>::take(old_key) {
+        crate::Bonds::<T>::insert(new_key, bonds);
+    }
+
+    crate::WeightCommits::<T>::remove(old_key);
+
+    let setters: Vec<_> = WeightSetters::<T>::drain_prefix(old_key)
+        .map(|(setter, _)| setter)
+        .collect();
+    for setter in setters {
+        ConsensusMembers::<T>::mutate(&setter, |member: &mut Option<ConsensusMember<T>>| {
+            if let Some(member) = member {
+                member
+                    .weights
+                    .iter_mut()
+                    .for_each(|(agent, _)| rotate(agent));
+                // Weights are kept sorted by agent, see `update_weights`.
+                member.weights.sort_by(|(a, _), (b, _)| a.cmp(b));
+            }
+        });
+        WeightSetters::<T>::insert(new_key, &setter, ());
+    }
+
+    let delegators: Vec<_> =
+        crate::WeightControlDelegators::<T>::iter_key_prefix(old_key).collect();
+    for delegator in delegators {
+        let Some(mut shares) = crate::WeightControlDelegation::<T>::get(&delegator) else {
+            continue;
+        };
+
+        if let Some(share) = shares.remove(old_key) {
+            let share = shares
+                .get(new_key)
+                .copied()
+                .unwrap_or_default()
+                .saturating_add(share);
+            let _ = shares.try_insert(new_key.clone(), share);
+        }
+
+        set_delegation::<T>(&delegator, Some(shares));
+    }
+
+    // Only validators hold bonds, so this is bounded by the maximum amount
+    // of validators.
+    crate::Bonds::<T>::translate_values(|mut bonds: crate::ValidatorBonds<T>| {
+        bonds.iter_mut().for_each(|(agent, _)| rotate(agent));
+        Some(bonds)
+    });
+}
+
+/// Number of records of other agents [`rotate_key`] rewrites when rotating
+/// the key: the weights set on it, the weight control delegated to it and the
+/// bonds of every validator.
+pub fn key_references<T: crate::Config>(key: &T::AccountId) -> u32 {
+    let setters = WeightSetters::<T>::iter_key_prefix(key).count() as u32;
+    let delegators = crate::WeightControlDelegatorCount::<T>::get(key);
+    let validators = crate::Bonds::<T>::iter_keys().count() as u32;
+
+    setters
+        .saturating_add(delegators)
+        .saturating_add(validators)
 }
@@ -41,6 +41,8 @@ pub trait WeightInfo {
 	fn set_weights() -> Weight;
 	fn delegate_weight_control() -> Weight;
 	fn regain_weight_control() -> Weight;
+	fn commit_weights() -> Weight;
+	fn reveal_weights() -> Weight;
 }
 
 /// Weights for `pallet_emission0` using the Substrate node and recommended hardware.
@@ -99,6 +101,66 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2_u64))
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Emission0::OpenWeightDelegation` (r:1 w:0)
+	/// Proof: `Emission0::OpenWeightDelegation` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Allocators` (r:1 w:0)
+	/// Proof: `Governance::Allocators` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegation` (r:1 w:0)
+	/// Proof: `Emission0::WeightControlDelegation` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Whitelist` (r:1 w:0)
+	/// Proof: `Governance::Whitelist` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakedBy` (r:2 w:0)
+	/// Proof: `Torus0::StakedBy` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::MinValidatorStake` (r:1 w:0)
+	/// Proof: `Torus0::MinValidatorStake` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightsCommitReveal` (r:1 w:0)
+	/// Proof: `Emission0::WeightsCommitReveal` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightCommits` (r:0 w:1)
+	/// Proof: `Emission0::WeightCommits` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	fn commit_weights() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `6098`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 6098)
+			.saturating_add(T::DbWeight::get().reads(9_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Emission0::OpenWeightDelegation` (r:1 w:0)
+	/// Proof: `Emission0::OpenWeightDelegation` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Allocators` (r:1 w:0)
+	/// Proof: `Governance::Allocators` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegation` (r:1 w:0)
+	/// Proof: `Emission0::WeightControlDelegation` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:2 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Whitelist` (r:2 w:0)
+	/// Proof: `Governance::Whitelist` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakedBy` (r:2 w:0)
+	/// Proof: `Torus0::StakedBy` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::MinValidatorStake` (r:1 w:0)
+	/// Proof: `Torus0::MinValidatorStake` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightsCommitReveal` (r:1 w:0)
+	/// Proof: `Emission0::WeightsCommitReveal` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightCommits` (r:1 w:1)
+	/// Proof: `Emission0::WeightCommits` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::RewardInterval` (r:1 w:0)
+	/// Proof: `Torus0::RewardInterval` (`max_values`: Some(1), `max_size`: Some(2), added: 497, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::ConsensusMembers` (r:1 w:1)
+	/// Proof: `Emission0::ConsensusMembers` (`max_values`: None, `max_size`: Some(4294967295), added: 2474, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightSetters` (r:0 w:1)
+	/// Proof: `Emission0::WeightSetters` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	fn reveal_weights() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `761`
+		//  Estimated: `7654`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(35_000_000, 7654)
+			.saturating_add(T::DbWeight::get().reads(14_u64))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -156,4 +218,64 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2_u64))
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Emission0::OpenWeightDelegation` (r:1 w:0)
+	/// Proof: `Emission0::OpenWeightDelegation` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Allocators` (r:1 w:0)
+	/// Proof: `Governance::Allocators` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegation` (r:1 w:0)
+	/// Proof: `Emission0::WeightControlDelegation` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Whitelist` (r:1 w:0)
+	/// Proof: `Governance::Whitelist` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakedBy` (r:2 w:0)
+	/// Proof: `Torus0::StakedBy` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::MinValidatorStake` (r:1 w:0)
+	/// Proof: `Torus0::MinValidatorStake` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightsCommitReveal` (r:1 w:0)
+	/// Proof: `Emission0::WeightsCommitReveal` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightCommits` (r:0 w:1)
+	/// Proof: `Emission0::WeightCommits` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	fn commit_weights() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `612`
+		//  Estimated: `6098`
+		// Minimum execution time: 22_000_000 picoseconds.
+		Weight::from_parts(23_000_000, 6098)
+			.saturating_add(RocksDbWeight::get().reads(9_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Emission0::OpenWeightDelegation` (r:1 w:0)
+	/// Proof: `Emission0::OpenWeightDelegation` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Allocators` (r:1 w:0)
+	/// Proof: `Governance::Allocators` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegation` (r:1 w:0)
+	/// Proof: `Emission0::WeightControlDelegation` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:2 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Governance::Whitelist` (r:2 w:0)
+	/// Proof: `Governance::Whitelist` (`max_values`: None, `max_size`: Some(32), added: 2507, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakedBy` (r:2 w:0)
+	/// Proof: `Torus0::StakedBy` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::MinValidatorStake` (r:1 w:0)
+	/// Proof: `Torus0::MinValidatorStake` (`max_values`: Some(1), `max_size`: Some(16), added: 511, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightsCommitReveal` (r:1 w:0)
+	/// Proof: `Emission0::WeightsCommitReveal` (`max_values`: Some(1), `max_size`: Some(1), added: 496, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightCommits` (r:1 w:1)
+	/// Proof: `Emission0::WeightCommits` (`max_values`: None, `max_size`: Some(68), added: 2543, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::RewardInterval` (r:1 w:0)
+	/// Proof: `Torus0::RewardInterval` (`max_values`: Some(1), `max_size`: Some(2), added: 497, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::ConsensusMembers` (r:1 w:1)
+	/// Proof: `Emission0::ConsensusMembers` (`max_values`: None, `max_size`: Some(4294967295), added: 2474, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightSetters` (r:0 w:1)
+	/// Proof: `Emission0::WeightSetters` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	fn reveal_weights() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `761`
+		//  Estimated: `7654`
+		// Minimum execution time: 34_000_000 picoseconds.
+		Weight::from_parts(35_000_000, 7654)
+			.saturating_add(RocksDbWeight::get().reads(14_u64))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+	}
 }
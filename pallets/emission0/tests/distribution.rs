@@ -3,9 +3,11 @@
 use std::array::from_fn;
 
 use pallet_emission0::{
-    Config, ConsensusMember, ConsensusMembers, EmissionRecyclingPercentage, IncentivesRatio,
+    Bonds, BondsMovingAverage, Config, ConsensusConfig, ConsensusConfiguration, ConsensusMember,
+    ConsensusMembers, EmissionRecyclingPercentage, EpochCount, EpochReports, IncentivesRatio,
     PendingEmission, WeightControlDelegation,
-    distribute::{ConsensusMemberInput, get_total_emission_per_block},
+    distribute::{ConsensusMemberInput, get_total_emission_per_block, simulate_epoch},
+    weight_control::whole_weight_control,
 };
 use polkadot_sdk::{
     frame_support::traits::Currency,
@@ -14,7 +16,7 @@ use polkadot_sdk::{
     sp_runtime::{BoundedVec, FixedU128, Perbill, Percent},
 };
 use test_utils::{
-    AccountId, Balances, ExistentialDeposit, Test, add_balance, add_stake,
+    AccountId, Balances, ExistentialDeposit, RuntimeEvent, System, Test, add_balance, add_stake,
     pallet_governance::{Allocators, TreasuryEmissionFee},
     pallet_torus0::{
         Agents, FeeConstraints, MaxAllowedValidators, MinAllowedStake, MinValidatorStake, StakedBy,
@@ -126,7 +128,7 @@ fn creates_member_input_correctly() {
                 stakes: vec![],
                 total_stake: 0,
                 normalized_stake: FixedU128::from_inner(0),
-                delegating_to: None,
+                delegating_to: vec![],
                 whitelisted: false
             }
         );
@@ -183,7 +185,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
             delegating_deregistered,
             delegating_unknown,
         ] {
-            WeightControlDelegation::<Test>::set(id, Some(validator));
+            WeightControlDelegation::<Test>::set(id, Some(whole_weight_control::<Test>(validator)));
         }
 
         let members = ConsensusMemberInput::<Test>::all_members();
@@ -198,7 +200,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
                 stakes: vec![(staker, stake * 3)],
                 total_stake: stake * 3,
                 normalized_stake: FixedU128::from_float(0.75f64),
-                delegating_to: None,
+                delegating_to: vec![],
                 whitelisted: true,
             }
         );
@@ -212,7 +214,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
                 stakes: vec![],
                 total_stake: 0,
                 normalized_stake: FixedU128::from_inner(0),
-                delegating_to: None,
+                delegating_to: vec![],
                 whitelisted: true,
             }
         );
@@ -226,7 +228,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
                 stakes: vec![(staker, stake)],
                 total_stake: stake,
                 normalized_stake: FixedU128::from_float(0.25f64),
-                delegating_to: Some(validator),
+                delegating_to: vec![(validator, Percent::one())],
                 whitelisted: true,
             }
         );
@@ -240,7 +242,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
                 stakes: vec![],
                 total_stake: 0,
                 normalized_stake: FixedU128::from_inner(0),
-                delegating_to: Some(validator),
+                delegating_to: vec![(validator, Percent::one())],
                 whitelisted: false,
             }
         );
@@ -254,7 +256,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
                 stakes: vec![],
                 total_stake: 0,
                 normalized_stake: FixedU128::from_inner(0),
-                delegating_to: Some(validator),
+                delegating_to: vec![(validator, Percent::one())],
                 whitelisted: false,
             }
         );
@@ -268,7 +270,7 @@ fn creates_list_of_all_member_inputs_for_rewards() {
                 stakes: vec![],
                 total_stake: 0,
                 normalized_stake: FixedU128::from_inner(0),
-                delegating_to: None,
+                delegating_to: vec![],
                 whitelisted: true,
             }
         );
@@ -316,7 +318,7 @@ fn deregister_old_agents_and_registers_new() {
         ConsensusMembers::<Test>::set(0, Some(Default::default()));
         ConsensusMembers::<Test>::set(1, Some(Default::default()));
 
-        WeightControlDelegation::<Test>::set(1, Some(0));
+        WeightControlDelegation::<Test>::set(1, Some(whole_weight_control::<Test>(0)));
 
         for id in [1, 2] {
             register_empty_agent(id);
@@ -627,6 +629,143 @@ fn pays_weight_delegation_fee_to_validators_without_permits() {
     });
 }
 
+#[test]
+fn pays_weight_delegation_fees_along_delegation_chains() {
+    test_utils::new_test_ext().execute_with(|| {
+        let (min_validator_stake, weight_control_fee) = set_emissions_params();
+
+        let val_1 = 0;
+        let val_2 = 1;
+        let val_3 = 2;
+
+        let miner = 3;
+
+        let mut member = ConsensusMember::<Test>::default();
+        member.update_weights(BoundedVec::truncate_from(vec![(miner, 1)]));
+
+        ConsensusMembers::<Test>::set(val_1, Some(member));
+        ConsensusMembers::<Test>::set(val_2, Some(Default::default()));
+        ConsensusMembers::<Test>::set(val_3, Some(Default::default()));
+        ConsensusMembers::<Test>::set(miner, Some(Default::default()));
+
+        for id in [val_1, val_2, val_3, miner] {
+            let _ =
+                <Balances as Currency<AccountId>>::deposit_creating(&id, ExistentialDeposit::get());
+
+            register_empty_agent(id);
+        }
+
+        Allocators::<Test>::set(val_1, Some(()));
+        Allocators::<Test>::set(val_2, Some(()));
+
+        pallet_emission0::weight_control::delegate_weight_control::<Test>(val_2, val_1)
+            .expect("failed to delegate weight control");
+        pallet_emission0::weight_control::delegate_weight_control::<Test>(val_3, val_2)
+            .expect("failed to delegate weight control");
+
+        add_stake(val_3, val_3, min_validator_stake);
+
+        step_block(100);
+
+        let total_dividends = (get_total_emission_per_block::<Test>() * 100) / 2;
+        let val_2_fee = weight_control_fee.mul_floor(total_dividends);
+        let val_1_fee = weight_control_fee.mul_floor(val_2_fee);
+
+        assert_eq!(
+            StakedBy::<Test>::get(val_1, val_1).unwrap_or_default(),
+            val_1_fee
+        );
+
+        assert_eq!(
+            StakedBy::<Test>::get(val_2, val_2).unwrap_or_default(),
+            val_2_fee - val_1_fee
+        );
+
+        assert_eq!(
+            StakedBy::<Test>::get(val_3, val_3).unwrap_or_default() - min_validator_stake,
+            total_dividends - val_2_fee
+        );
+
+        let miner = ConsensusMembers::<Test>::get(miner).unwrap();
+        assert_eq!(miner.last_incentives, u16::MAX);
+    });
+}
+
+#[test]
+fn splits_weight_control_between_delegatees() {
+    test_utils::new_test_ext().execute_with(|| {
+        let (min_validator_stake, weight_control_fee) = set_emissions_params();
+
+        let val_1 = 0;
+        let val_2 = 1;
+        let val_3 = 2;
+
+        let miner_1 = 3;
+        let miner_2 = 4;
+
+        for (val, miner) in [(val_1, miner_1), (val_2, miner_2)] {
+            let mut member = ConsensusMember::<Test>::default();
+            member.update_weights(BoundedVec::truncate_from(vec![(miner, 1)]));
+            ConsensusMembers::<Test>::set(val, Some(member));
+        }
+
+        for id in [val_3, miner_1, miner_2] {
+            ConsensusMembers::<Test>::set(id, Some(Default::default()));
+        }
+
+        for id in [val_1, val_2, val_3, miner_1, miner_2] {
+            let _ =
+                <Balances as Currency<AccountId>>::deposit_creating(&id, ExistentialDeposit::get());
+
+            register_empty_agent(id);
+        }
+
+        Allocators::<Test>::set(val_1, Some(()));
+        Allocators::<Test>::set(val_2, Some(()));
+
+        let (share_1, share_2) = (Percent::from_percent(70), Percent::from_percent(30));
+        pallet_emission0::weight_control::delegate_weight_control_shares::<Test>(
+            val_3,
+            vec![(val_1, share_1), (val_2, share_2)],
+        )
+        .expect("failed to delegate weight control");
+
+        add_stake(val_3, val_3, min_validator_stake);
+
+        let inputs = ConsensusMemberInput::<Test>::all_members();
+        let input = &inputs[&val_3];
+        assert_eq!(
+            input.delegating_to,
+            vec![(val_1, share_1), (val_2, share_2)]
+        );
+        let weights: Vec<_> = input
+            .weights
+            .iter()
+            .map(|(id, weight)| (*id, (weight.to_float() * 100.).round()))
+            .collect();
+        assert_eq!(weights, vec![(miner_1, 70.), (miner_2, 30.)]);
+
+        step_block(100);
+
+        let total_dividends = (get_total_emission_per_block::<Test>() * 100) / 2;
+        let val_1_fee = weight_control_fee.mul_floor(share_1.mul_floor(total_dividends));
+        let val_2_fee = weight_control_fee.mul_floor(share_2.mul_floor(total_dividends));
+
+        assert_eq!(
+            StakedBy::<Test>::get(val_1, val_1).unwrap_or_default(),
+            val_1_fee
+        );
+        assert_eq!(
+            StakedBy::<Test>::get(val_2, val_2).unwrap_or_default(),
+            val_2_fee
+        );
+        assert_eq!(
+            StakedBy::<Test>::get(val_3, val_3).unwrap_or_default() - min_validator_stake,
+            total_dividends - val_1_fee - val_2_fee
+        );
+    });
+}
+
 #[test]
 fn ignores_agents_delegating_to_non_existing_agents() {
     test_utils::new_test_ext().execute_with(|| {
@@ -643,7 +782,10 @@ fn ignores_agents_delegating_to_non_existing_agents() {
             register_empty_agent(id);
         }
 
-        WeightControlDelegation::<Test>::set(val, Some(non_existing_val));
+        WeightControlDelegation::<Test>::set(
+            val,
+            Some(whole_weight_control::<Test>(non_existing_val)),
+        );
 
         add_stake(val, val, min_validator_stake);
 
@@ -662,6 +804,417 @@ fn ignores_agents_delegating_to_non_existing_agents() {
     });
 }
 
+#[test]
+fn records_epoch_report() {
+    test_utils::new_test_ext().execute_with(|| {
+        EmissionRecyclingPercentage::<Test>::set(Percent::zero());
+        let treasury_fee = Percent::from_percent(10);
+        TreasuryEmissionFee::<Test>::set(treasury_fee);
+
+        let min_stake = MinAllowedStake::<Test>::get();
+        MinValidatorStake::<Test>::set(min_stake);
+
+        let val = 0;
+        let miner = 1;
+
+        let mut member = ConsensusMember::<Test>::default();
+        member.update_weights(BoundedVec::truncate_from(vec![(miner, 1)]));
+
+        ConsensusMembers::<Test>::set(val, Some(member));
+        ConsensusMembers::<Test>::set(miner, Some(Default::default()));
+
+        add_stake(val, val, 0);
+        add_balance(miner, 1);
+
+        for id in [val, miner] {
+            register_empty_agent(id);
+        }
+
+        step_block(100);
+
+        let emitted = get_total_emission_per_block::<Test>() * 100;
+
+        assert_eq!(EpochCount::<Test>::get(), 1);
+        let report = EpochReports::<Test>::get(0).expect("missing epoch report");
+
+        assert_eq!(report.block, 100);
+        assert_eq!(report.emitted, emitted);
+        assert_eq!(report.treasury_fee, treasury_fee.mul_floor(emitted));
+        assert_eq!(report.remaining, PendingEmission::<Test>::get());
+
+        assert_eq!(report.agents.len(), 2);
+        let val_report = &report.agents[0];
+        let miner_report = &report.agents[1];
+
+        assert_eq!(val_report.agent_id, val);
+        assert_eq!(val_report.incentives, 0);
+        assert_eq!(miner_report.agent_id, miner);
+        assert_eq!(miner_report.dividends, 0);
+        assert_eq!(val_report.dividends, miner_report.incentives);
+
+        assert_eq!(
+            StakedBy::<Test>::get(val, val).unwrap_or_default() - min_stake,
+            val_report.dividends
+        );
+        assert_eq!(
+            report.treasury_fee + val_report.dividends + miner_report.incentives + report.remaining,
+            emitted
+        );
+
+        assert!(System::events().into_iter().any(|record| {
+            record.event
+                == RuntimeEvent::Emission0(pallet_emission0::Event::EpochDistributed(
+                    0,
+                    report.clone(),
+                ))
+        }));
+    });
+}
+
+#[test]
+fn prunes_old_epoch_reports() {
+    test_utils::new_test_ext().execute_with(|| {
+        let max_reports = <<Test as Config>::MaxEpochReports as Get<u32>>::get() as u64;
+
+        step_block(100 * (max_reports + 1));
+
+        assert_eq!(EpochCount::<Test>::get(), max_reports + 1);
+        assert!(EpochReports::<Test>::get(0).is_none());
+        assert!(EpochReports::<Test>::get(1).is_some());
+
+        let reports = pallet_emission0::Pallet::<Test>::epoch_reports(u32::MAX);
+        assert_eq!(reports.len() as u64, max_reports);
+        assert_eq!(reports.first().map(|(epoch, _)| *epoch), Some(max_reports));
+        assert_eq!(reports.last().map(|(epoch, _)| *epoch), Some(1));
+
+        let reports = pallet_emission0::Pallet::<Test>::epoch_reports(2);
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[1].1.block, 100 * max_reports);
+    });
+}
+
+#[test]
+fn simulated_epoch_matches_distribution() {
+    test_utils::new_test_ext().execute_with(|| {
+        let (min_validator_stake, _) = set_emissions_params();
+        TreasuryEmissionFee::<Test>::set(Percent::from_percent(10));
+
+        let val = 0;
+        let miner = 1;
+        let staker = 2;
+
+        let mut member = ConsensusMember::<Test>::default();
+        member.update_weights(BoundedVec::truncate_from(vec![(miner, 1)]));
+
+        ConsensusMembers::<Test>::set(val, Some(member));
+        ConsensusMembers::<Test>::set(miner, Some(Default::default()));
+
+        add_stake(val, val, min_validator_stake);
+        add_stake(staker, val, min_validator_stake * 3);
+        add_stake(miner, miner, min_validator_stake);
+
+        step_block(99);
+
+        let pending = PendingEmission::<Test>::get();
+        let simulation = simulate_epoch::<Test>(vec![]);
+
+        assert_eq!(PendingEmission::<Test>::get(), pending);
+        assert_eq!(EpochCount::<Test>::get(), 0);
+        assert_eq!(simulation.emission, pending);
+        assert_eq!(
+            simulation.treasury_fee,
+            TreasuryEmissionFee::<Test>::get().mul_floor(pending)
+        );
+
+        // Runs the next epoch with exactly the simulated pending emission.
+        PendingEmission::<Test>::set(pending - get_total_emission_per_block::<Test>());
+        step_block(1);
+
+        let report = EpochReports::<Test>::get(0).expect("missing epoch report");
+        assert_eq!(report.emitted, simulation.emission);
+        assert_eq!(report.treasury_fee, simulation.treasury_fee);
+
+        let val_simulation = simulation
+            .agents
+            .iter()
+            .find(|agent| agent.agent_id == val)
+            .expect("missing validator");
+        let miner_simulation = simulation
+            .agents
+            .iter()
+            .find(|agent| agent.agent_id == miner)
+            .expect("missing miner");
+
+        assert_eq!(val_simulation.incentives, 0);
+        assert!(val_simulation.dividends > 0);
+        assert_eq!(miner_simulation.incentives, val_simulation.dividends);
+
+        for agent in &report.agents {
+            let simulated = simulation
+                .agents
+                .iter()
+                .find(|simulated| simulated.agent_id == agent.agent_id)
+                .expect("missing agent");
+            assert_eq!(simulated.incentives, agent.incentives);
+            assert_eq!(simulated.dividends, agent.dividends);
+        }
+
+        let staker_payout = val_simulation
+            .stakers
+            .iter()
+            .find(|(id, _)| *id == staker)
+            .map(|(_, amount)| *amount)
+            .expect("missing staker");
+        assert_eq!(
+            StakedBy::<Test>::get(val, staker).unwrap_or_default() - min_validator_stake * 3,
+            staker_payout
+        );
+
+        let own_payout = val_simulation
+            .stakers
+            .iter()
+            .find(|(id, _)| *id == val)
+            .map(|(_, amount)| *amount)
+            .expect("missing validator stake");
+        assert_eq!(
+            StakedBy::<Test>::get(val, val).unwrap_or_default() - min_validator_stake,
+            own_payout + val_simulation.agent_payout
+        );
+
+        assert_eq!(
+            StakedBy::<Test>::get(miner, miner).unwrap_or_default() - min_validator_stake,
+            miner_simulation.agent_payout
+        );
+    });
+}
+
+#[test]
+fn simulates_epoch_with_weight_overrides() {
+    test_utils::new_test_ext().execute_with(|| {
+        let (min_validator_stake, _) = set_emissions_params();
+
+        let val = 0;
+        let miner_1 = 1;
+        let miner_2 = 2;
+
+        let mut member = ConsensusMember::<Test>::default();
+        member.update_weights(BoundedVec::truncate_from(vec![(miner_1, 1)]));
+
+        ConsensusMembers::<Test>::set(val, Some(member));
+        ConsensusMembers::<Test>::set(miner_1, Some(Default::default()));
+        ConsensusMembers::<Test>::set(miner_2, Some(Default::default()));
+
+        add_stake(val, val, min_validator_stake);
+        for id in [miner_1, miner_2] {
+            register_empty_agent(id);
+        }
+
+        step_block(50);
+
+        let incentives_of =
+            |simulation: &pallet_emission0_api::EpochSimulation<AccountId, u128>, id: AccountId| {
+                simulation
+                    .agents
+                    .iter()
+                    .find(|agent| agent.agent_id == id)
+                    .map(|agent| agent.incentives)
+                    .unwrap_or_default()
+            };
+
+        let simulation = simulate_epoch::<Test>(vec![]);
+        assert!(incentives_of(&simulation, miner_1) > 0);
+        assert_eq!(incentives_of(&simulation, miner_2), 0);
+
+        let simulation = simulate_epoch::<Test>(vec![(val, vec![(miner_2, 1)])]);
+        assert_eq!(incentives_of(&simulation, miner_1), 0);
+        assert!(incentives_of(&simulation, miner_2) > 0);
+
+        let member = ConsensusMembers::<Test>::get(val).expect("missing validator");
+        assert_eq!(member.weights.into_inner(), vec![(miner_1, 1)]);
+    });
+}
+
+#[test]
+fn smooths_dividends_with_bonds_moving_average() {
+    test_utils::new_test_ext().execute_with(|| {
+        let (min_validator_stake, _) = set_emissions_params();
+        BondsMovingAverage::<Test>::set(Percent::from_percent(10));
+
+        let val_1 = 0;
+        let val_2 = 1;
+        let miner_1 = 2;
+        let miner_2 = 3;
+
+        let set_weights = |val: AccountId, miner: AccountId| {
+            ConsensusMembers::<Test>::mutate(val, |member| {
+                member
+                    .get_or_insert_with(Default::default)
+                    .update_weights(BoundedVec::truncate_from(vec![(miner, 1)]));
+            });
+        };
+
+        set_weights(val_1, miner_1);
+        set_weights(val_2, miner_2);
+        ConsensusMembers::<Test>::set(miner_1, Some(Default::default()));
+        ConsensusMembers::<Test>::set(miner_2, Some(Default::default()));
+
+        for id in [val_1, val_2, miner_1, miner_2] {
+            register_empty_agent(id);
+        }
+
+        add_stake(val_1, val_1, min_validator_stake);
+        add_stake(val_2, val_2, min_validator_stake);
+
+        step_block(100);
+
+        let bonded_agents = |val: AccountId| {
+            Bonds::<Test>::get(val)
+                .unwrap_or_default()
+                .into_iter()
+                .map(|(id, _)| id)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(bonded_agents(val_1), vec![miner_1]);
+        assert_eq!(bonded_agents(val_2), vec![miner_2]);
+
+        // The second validator moves to the first miner. Its new bond is still
+        // small compared to the one held by the first validator.
+        set_weights(val_2, miner_1);
+
+        step_block(100);
+
+        assert_eq!(bonded_agents(val_1), vec![miner_1]);
+        assert_eq!(bonded_agents(val_2), vec![miner_1, miner_2]);
+
+        let report = EpochReports::<Test>::get(1).expect("missing epoch report");
+        let dividends_of = |id: AccountId| {
+            report
+                .agents
+                .iter()
+                .find(|agent| agent.agent_id == id)
+                .map(|agent| agent.dividends)
+                .unwrap_or_default()
+        };
+
+        assert!(dividends_of(val_2) > 0);
+        assert!(dividends_of(val_1) > dividends_of(val_2) * 10);
+    });
+}
+
+#[test]
+fn full_bonds_moving_average_follows_current_weights() {
+    test_utils::new_test_ext().execute_with(|| {
+        let (min_validator_stake, _) = set_emissions_params();
+        BondsMovingAverage::<Test>::set(Percent::one());
+
+        let val = 0;
+        let miner_1 = 1;
+        let miner_2 = 2;
+
+        let mut member = ConsensusMember::<Test>::default();
+        member.update_weights(BoundedVec::truncate_from(vec![(miner_1, 1)]));
+
+        ConsensusMembers::<Test>::set(val, Some(member));
+        ConsensusMembers::<Test>::set(miner_1, Some(Default::default()));
+        ConsensusMembers::<Test>::set(miner_2, Some(Default::default()));
+
+        for id in [val, miner_1, miner_2] {
+            register_empty_agent(id);
+        }
+
+        add_stake(val, val, min_validator_stake);
+
+        step_block(100);
+
+        assert_eq!(
+            Bonds::<Test>::get(val).map(BoundedVec::into_inner),
+            Some(vec![(miner_1, FixedU128::from_u32(1))])
+        );
+
+        ConsensusMembers::<Test>::mutate(val, |member| {
+            if let Some(member) = member {
+                member.update_weights(BoundedVec::truncate_from(vec![(miner_2, 1)]));
+            }
+        });
+
+        step_block(100);
+
+        assert_eq!(
+            Bonds::<Test>::get(val).map(BoundedVec::into_inner),
+            Some(vec![(miner_2, FixedU128::from_u32(1))])
+        );
+    });
+}
+
+#[test]
+fn clips_weights_to_stake_weighted_consensus() {
+    test_utils::new_test_ext().execute_with(|| {
+        set_emissions_params();
+
+        let val_1 = 0;
+        let val_2 = 1;
+        let whale = 2;
+        let miner_1 = 3;
+        let miner_2 = 4;
+
+        for (val, miner, stake) in [
+            (val_1, miner_1, 300),
+            (val_2, miner_1, 300),
+            (whale, miner_2, 400),
+        ] {
+            let mut member = ConsensusMember::<Test>::default();
+            member.update_weights(BoundedVec::truncate_from(vec![(miner, 1)]));
+            ConsensusMembers::<Test>::set(val, Some(member));
+
+            register_empty_agent(val);
+            add_stake(val, val, stake);
+        }
+
+        for miner in [miner_1, miner_2] {
+            ConsensusMembers::<Test>::set(miner, Some(Default::default()));
+            register_empty_agent(miner);
+        }
+
+        let emission_of = |id: AccountId| {
+            simulate_epoch::<Test>(vec![])
+                .agents
+                .into_iter()
+                .find(|agent| agent.agent_id == id)
+                .map(|agent| (agent.incentives, agent.dividends))
+                .unwrap_or_default()
+        };
+
+        // Without the consensus step, the whale alone gives 40% of the
+        // incentives to its miner.
+        step_block(1);
+        assert!(emission_of(miner_2).0 > 0);
+        assert!(emission_of(whale).1 > 0);
+
+        ConsensusConfig::<Test>::set(ConsensusConfiguration {
+            enabled: true,
+            kappa: Percent::from_percent(50),
+            divergence_penalty: Percent::one(),
+        });
+
+        assert_eq!(emission_of(miner_2), (0, 0));
+        assert_eq!(emission_of(whale).1, 0);
+        assert!(emission_of(miner_1).0 > 0);
+        assert!(emission_of(val_1).1 > 0);
+        assert_eq!(emission_of(val_1), emission_of(val_2));
+
+        step_block(99);
+
+        let report = EpochReports::<Test>::get(0).expect("missing epoch report");
+        assert!(
+            report
+                .agents
+                .iter()
+                .all(|agent| agent.agent_id != miner_2 && agent.agent_id != whale)
+        );
+    });
+}
+
 fn set_emissions_params() -> (u128, Percent) {
     EmissionRecyclingPercentage::<Test>::set(Percent::zero());
     TreasuryEmissionFee::<Test>::set(Percent::zero());
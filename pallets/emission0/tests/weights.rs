@@ -1,10 +1,22 @@
+use std::collections::BTreeSet;
+
 use pallet_emission0::{
-    ConsensusMembers, Error, WeightControlDelegation, Weights,
-    weight_control::{delegate_weight_control, regain_weight_control, set_weights},
+    ConsensusMembers, Error, OpenWeightDelegation, WeightCommits, WeightControlDelegation,
+    WeightControlDelegatorCount, WeightControlDelegators, WeightSetters, Weights,
+    WeightsCommitReveal,
+    distribute::ConsensusMemberInput,
+    weight_control::{
+        commit_weights, delegate_weight_control, delegate_weight_control_shares,
+        regain_weight_control, reveal_weights, rotate_key, set_weights, weights_commit_hash,
+    },
 };
+use pallet_torus0_api::ProxyOperation;
+use polkadot_sdk::sp_runtime::{FixedU128, Percent};
 use test_utils::{
-    Test, add_stake, get_origin, pallet_governance::Allocators, pallet_torus0::MinValidatorStake,
-    register_empty_agent,
+    Test, Torus0, add_stake, assert_ok, get_origin,
+    pallet_governance::Allocators,
+    pallet_torus0::{MinValidatorStake, proxy::ProxyOperations},
+    register_empty_agent, step_block,
 };
 
 #[test]
@@ -61,6 +73,178 @@ fn delegates_and_regains_weight_control() {
     });
 }
 
+#[test]
+fn delegates_weight_control_through_chains_when_open() {
+    test_utils::new_test_ext().execute_with(|| {
+        for id in 0..5 {
+            register_empty_agent(id);
+        }
+
+        delegate_weight_control::<Test>(0, 1).expect_err("cannot delegate to not-allocator");
+
+        OpenWeightDelegation::<Test>::set(true);
+
+        assert_eq!(
+            delegate_weight_control::<Test>(0, 1),
+            Err(Error::<Test>::NotEnoughStakeToSetWeights.into())
+        );
+
+        for id in 1..5 {
+            add_stake(id, id, MinValidatorStake::<Test>::get());
+        }
+
+        assert_eq!(delegate_weight_control::<Test>(0, 1), Ok(()));
+        assert_eq!(delegate_weight_control::<Test>(1, 2), Ok(()));
+
+        assert_eq!(
+            delegate_weight_control::<Test>(2, 0),
+            Err(Error::<Test>::WeightDelegationCycle.into())
+        );
+
+        // 0 -> 1 -> 2 -> 3 is as deep as the test runtime allows.
+        assert_eq!(delegate_weight_control::<Test>(2, 3), Ok(()));
+
+        assert_eq!(
+            delegate_weight_control::<Test>(3, 4),
+            Err(Error::<Test>::WeightDelegationTooDeep.into())
+        );
+        assert!(!WeightControlDelegation::<Test>::contains_key(3));
+    });
+}
+
+#[test]
+fn non_allocators_set_delegated_weights_when_open() {
+    test_utils::new_test_ext().execute_with(|| {
+        let delegator = 0;
+        let leaf = 1;
+        let miner = 2;
+
+        for id in [delegator, leaf, miner] {
+            register_empty_agent(id);
+        }
+        for id in [delegator, leaf] {
+            add_stake(id, id, MinValidatorStake::<Test>::get());
+        }
+
+        assert_eq!(
+            set_weights::<Test>(get_origin(leaf), vec![(miner, 1)]),
+            Err(test_utils::pallet_governance::Error::<Test>::NotAllocator.into()),
+        );
+
+        OpenWeightDelegation::<Test>::set(true);
+
+        assert_eq!(delegate_weight_control::<Test>(delegator, leaf), Ok(()));
+        assert_eq!(
+            set_weights::<Test>(get_origin(leaf), vec![(miner, 1)]),
+            Ok(())
+        );
+
+        let inputs = ConsensusMemberInput::<Test>::all_members();
+        let input = inputs.get(&delegator).expect("delegator is a member");
+        assert_eq!(input.delegating_to, vec![(leaf, Percent::one())]);
+        assert_eq!(input.weights, vec![(miner, FixedU128::from_u32(1))]);
+
+        assert_eq!(regain_weight_control::<Test>(get_origin(delegator)), Ok(()));
+        assert!(!WeightControlDelegation::<Test>::contains_key(delegator));
+    });
+}
+
+#[test]
+fn validates_weight_control_shares() {
+    test_utils::new_test_ext().execute_with(|| {
+        for id in 0..6 {
+            register_empty_agent(id);
+            Allocators::<Test>::set(id, Some(()));
+        }
+
+        let invalid_shares = [
+            vec![],
+            vec![
+                (1, Percent::from_percent(60)),
+                (2, Percent::from_percent(30)),
+            ],
+            vec![(1, Percent::from_percent(100)), (2, Percent::zero())],
+            vec![
+                (1, Percent::from_percent(50)),
+                (1, Percent::from_percent(50)),
+            ],
+            (1..6).map(|id| (id, Percent::from_percent(20))).collect(),
+        ];
+        for shares in invalid_shares {
+            assert_eq!(
+                delegate_weight_control_shares::<Test>(0, shares),
+                Err(Error::<Test>::InvalidWeightControlShares.into())
+            );
+        }
+
+        assert_eq!(
+            delegate_weight_control_shares::<Test>(
+                0,
+                vec![
+                    (0, Percent::from_percent(50)),
+                    (1, Percent::from_percent(50))
+                ]
+            ),
+            Err(Error::<Test>::CannotDelegateWeightControlToSelf.into())
+        );
+
+        assert_eq!(
+            delegate_weight_control_shares::<Test>(
+                0,
+                vec![
+                    (1, Percent::from_percent(50)),
+                    (2, Percent::from_percent(50))
+                ]
+            ),
+            Ok(())
+        );
+
+        assert_eq!(
+            delegate_weight_control::<Test>(2, 0),
+            Err(Error::<Test>::WeightDelegationCycle.into())
+        );
+
+        let shares = WeightControlDelegation::<Test>::get(0).unwrap();
+        assert_eq!(shares.get(&1), Some(&Percent::from_percent(50)));
+        assert_eq!(shares.get(&2), Some(&Percent::from_percent(50)));
+    });
+}
+
+#[test]
+fn indexes_weight_control_delegators() {
+    test_utils::new_test_ext().execute_with(|| {
+        for id in 0..4 {
+            register_empty_agent(id);
+            Allocators::<Test>::set(id, Some(()));
+        }
+
+        assert_eq!(
+            delegate_weight_control_shares::<Test>(
+                0,
+                vec![
+                    (1, Percent::from_percent(50)),
+                    (2, Percent::from_percent(50))
+                ]
+            ),
+            Ok(())
+        );
+        assert!(WeightControlDelegators::<Test>::contains_key(1, 0));
+        assert!(WeightControlDelegators::<Test>::contains_key(2, 0));
+        assert_eq!(WeightControlDelegatorCount::<Test>::get(1), 1);
+        assert_eq!(WeightControlDelegatorCount::<Test>::get(2), 1);
+
+        assert_eq!(delegate_weight_control::<Test>(0, 3), Ok(()));
+        assert!(!WeightControlDelegators::<Test>::contains_key(1, 0));
+        assert!(!WeightControlDelegatorCount::<Test>::contains_key(1));
+        assert!(!WeightControlDelegatorCount::<Test>::contains_key(2));
+        assert_eq!(WeightControlDelegatorCount::<Test>::get(3), 1);
+
+        assert_eq!(regain_weight_control::<Test>(get_origin(0)), Ok(()));
+        assert!(!WeightControlDelegators::<Test>::contains_key(3, 0));
+        assert_eq!(WeightControlDelegatorCount::<Test>::get(3), 0);
+    });
+}
+
 #[test]
 #[allow(unreachable_code)]
 fn sets_weights_correctly() {
@@ -126,3 +310,186 @@ fn sets_weights_correctly() {
         );
     });
 }
+
+#[test]
+fn rotating_a_key_rewrites_the_weights_set_on_it() {
+    test_utils::new_test_ext().execute_with(|| {
+        let validator = 0;
+
+        Allocators::<Test>::insert(validator, ());
+        register_empty_agent(validator);
+        register_empty_agent(1);
+        register_empty_agent(2);
+        add_stake(validator, validator, MinValidatorStake::<Test>::get());
+
+        assert_ok!(set_weights::<Test>(
+            get_origin(validator),
+            vec![(1, 10), (2, 20)]
+        ));
+        assert!(WeightSetters::<Test>::contains_key(1, validator));
+        assert!(WeightSetters::<Test>::contains_key(2, validator));
+
+        rotate_key::<Test>(&1, &3);
+
+        assert_eq!(
+            ConsensusMembers::<Test>::get(validator)
+                .expect("weights were not set")
+                .weights,
+            Weights::<Test>::truncate_from(vec![(2, 20), (3, 10)])
+        );
+        assert!(!WeightSetters::<Test>::contains_key(1, validator));
+        assert!(WeightSetters::<Test>::contains_key(3, validator));
+
+        rotate_key::<Test>(&validator, &4);
+
+        assert!(!ConsensusMembers::<Test>::contains_key(validator));
+        assert!(WeightSetters::<Test>::contains_key(2, 4));
+        assert!(!WeightSetters::<Test>::contains_key(2, validator));
+    });
+}
+
+#[test]
+fn commits_and_reveals_weights() {
+    test_utils::new_test_ext().execute_with(|| {
+        let validator = 0;
+
+        Allocators::<Test>::insert(validator, ());
+        register_empty_agent(validator);
+        register_empty_agent(1);
+        register_empty_agent(2);
+        add_stake(validator, validator, MinValidatorStake::<Test>::get());
+
+        let weights = vec![(1, 10), (2, 20)];
+        let salt = b"salt".to_vec();
+        let hash = weights_commit_hash::<Test>(&validator, &weights, &salt);
+
+        assert_eq!(
+            commit_weights::<Test>(get_origin(validator), hash),
+            Err(Error::<Test>::CommitRevealNotEnabled.into()),
+        );
+
+        WeightsCommitReveal::<Test>::set(true);
+
+        assert_eq!(
+            set_weights::<Test>(get_origin(validator), weights.clone()),
+            Err(Error::<Test>::WeightsMustBeCommitted.into()),
+        );
+
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), weights.clone(), salt.clone()),
+            Err(Error::<Test>::NoWeightCommit.into()),
+        );
+
+        assert_eq!(commit_weights::<Test>(get_origin(validator), hash), Ok(()));
+        assert!(ConsensusMembers::<Test>::get(validator).is_none());
+
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), weights.clone(), salt.clone()),
+            Err(Error::<Test>::RevealTooEarly.into()),
+        );
+
+        step_block(100);
+
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), weights.clone(), b"pepper".to_vec()),
+            Err(Error::<Test>::InvalidWeightsReveal.into()),
+        );
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), vec![(1, 10)], salt.clone()),
+            Err(Error::<Test>::InvalidWeightsReveal.into()),
+        );
+
+        WeightsCommitReveal::<Test>::set(false);
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), weights.clone(), salt.clone()),
+            Err(Error::<Test>::CommitRevealNotEnabled.into()),
+        );
+        WeightsCommitReveal::<Test>::set(true);
+
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), weights.clone(), salt.clone()),
+            Ok(()),
+        );
+
+        assert!(!WeightCommits::<Test>::contains_key(validator));
+        assert_eq!(
+            ConsensusMembers::<Test>::get(validator)
+                .expect("weights were not set")
+                .weights,
+            Weights::<Test>::truncate_from(weights.clone())
+        );
+    });
+}
+
+#[test]
+fn unrevealed_weights_fall_back_to_previous_weights() {
+    test_utils::new_test_ext().execute_with(|| {
+        let validator = 0;
+
+        Allocators::<Test>::insert(validator, ());
+        register_empty_agent(validator);
+        register_empty_agent(1);
+        register_empty_agent(2);
+        add_stake(validator, validator, MinValidatorStake::<Test>::get());
+
+        assert_eq!(
+            set_weights::<Test>(get_origin(validator), vec![(1, 10)]),
+            Ok(()),
+        );
+
+        WeightsCommitReveal::<Test>::set(true);
+
+        let weights = vec![(2, 10)];
+        let salt = b"salt".to_vec();
+        let hash = weights_commit_hash::<Test>(&validator, &weights, &salt);
+        assert_eq!(commit_weights::<Test>(get_origin(validator), hash), Ok(()));
+
+        step_block(200);
+
+        assert!(!WeightCommits::<Test>::contains_key(validator));
+        assert_eq!(
+            reveal_weights::<Test>(get_origin(validator), weights, salt),
+            Err(Error::<Test>::NoWeightCommit.into()),
+        );
+
+        assert_eq!(
+            ConsensusMembers::<Test>::get(validator)
+                .expect("weights were not set")
+                .weights,
+            Weights::<Test>::truncate_from(vec![(1, 10)])
+        );
+    });
+}
+
+#[test]
+fn proxies_set_weights_for_agent() {
+    test_utils::new_test_ext().execute_with(|| {
+        let validator = 0;
+        let proxy = 10;
+
+        Allocators::<Test>::insert(validator, ());
+        register_empty_agent(validator);
+        add_stake(validator, validator, MinValidatorStake::<Test>::get());
+        register_empty_agent(1);
+        register_empty_agent(2);
+
+        assert_ok!(Torus0::add_agent_proxy(
+            get_origin(validator),
+            proxy,
+            ProxyOperations::try_from(BTreeSet::from([ProxyOperation::SetWeights])).unwrap(),
+        ));
+
+        assert_eq!(
+            set_weights::<Test>(get_origin(proxy), vec![(1, 0), (2, 0)]),
+            Ok(()),
+        );
+
+        assert_eq!(
+            ConsensusMembers::<Test>::get(validator)
+                .expect("weights were not set")
+                .weights,
+            Weights::<Test>::truncate_from(vec![(1, 0), (2, 0)])
+        );
+        assert!(!ConsensusMembers::<Test>::contains_key(proxy));
+    });
+}
@@ -0,0 +1,24 @@
+#![allow(clippy::multiple_bound_locations)]
+
+use codec::{Codec, Decode};
+use polkadot_sdk::sp_std::vec::Vec;
+
+use crate::{CuratorActionRecord, ProposalCategory};
+
+polkadot_sdk::sp_api::decl_runtime_apis! {
+    /// RPC related to Governance.
+    pub trait GovernanceRuntimeApi<AccountId: Codec, BlockNumber: Decode, Balance: Decode> {
+        /// Returns up to `count` entries of the curator action log, from the
+        /// most recent to the oldest, alongside their indices. Starts before
+        /// the entry `before`, or at the latest entry if `None`, so the log
+        /// can be paged through. Only the entries still kept on-chain are
+        /// returned.
+        fn curator_actions(before: Option<u64>, count: u32) -> Vec<(u64, CuratorActionRecord<AccountId, BlockNumber>)>;
+
+        /// Returns the stake the account would vote with on proposals of each
+        /// category if it voted now, counting the stake delegated to it
+        /// explicitly or through staking. Stake delegated to the account is
+        /// lost whenever its delegator votes directly.
+        fn voting_power(account: AccountId) -> Vec<(ProposalCategory, Balance)>;
+    }
+}
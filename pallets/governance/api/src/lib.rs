@@ -1,6 +1,65 @@
 #![no_std]
 
-use polkadot_sdk::{frame_support::dispatch::DispatchResult, sp_runtime::Percent};
+use codec::{Decode, Encode, MaxEncodedLen};
+use polkadot_sdk::{frame_support::dispatch::DispatchResult, sp_core::H256, sp_runtime::Percent};
+use scale_info::TypeInfo;
+
+pub mod api;
+
+/// An action performed by a curator.
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, PartialEq, Eq, Debug)]
+pub enum CuratorAction<AccountId> {
+    WhitelistAdded(AccountId),
+    WhitelistRemoved(AccountId),
+    ApplicationAccepted(u32),
+    ApplicationDenied(u32),
+    AgentPenalized {
+        agent: AccountId,
+        penalty: Percent,
+    },
+    /// The agent freezing was toggled to the given state.
+    AgentFreezingToggled(bool),
+    /// The namespace freezing was toggled to the given state.
+    NamespaceFreezingToggled(bool),
+}
+
+/// Entry of the curator action log.
+#[derive(Encode, Decode, TypeInfo, MaxEncodedLen, Clone, PartialEq, Eq, Debug)]
+pub struct CuratorActionRecord<AccountId, BlockNumber> {
+    /// Account that performed the action.
+    pub curator: AccountId,
+    /// Curator permission that authorized the action. `None` for the root
+    /// key.
+    pub permission_id: Option<H256>,
+    pub action: CuratorAction<AccountId>,
+    /// Block in which the action was performed.
+    pub block: BlockNumber,
+}
+
+/// Kind of a governance proposal. Votes can be delegated for some categories
+/// only.
+#[derive(
+    Encode, Decode, TypeInfo, MaxEncodedLen, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug,
+)]
+pub enum ProposalCategory {
+    GlobalParams,
+    GlobalCustom,
+    Emission,
+    TransferDaoTreasury,
+    AddAllocator,
+    RemoveAllocator,
+}
+
+impl ProposalCategory {
+    pub const ALL: [Self; 6] = [
+        Self::GlobalParams,
+        Self::GlobalCustom,
+        Self::Emission,
+        Self::TransferDaoTreasury,
+        Self::AddAllocator,
+        Self::RemoveAllocator,
+    ];
+}
 
 pub trait GovernanceApi<AccountId> {
     fn dao_treasury_address() -> AccountId;
@@ -13,12 +72,32 @@ pub trait GovernanceApi<AccountId> {
 
     fn get_allocators() -> impl Iterator<Item = AccountId>;
 
+    /// Picks the allocator a newly registered agent should delegate weight
+    /// control to, following the allocator assignment policy. Returns `None`
+    /// if no allocator has capacity left or agents pick their own allocator.
+    fn assign_allocator(agent: &AccountId) -> Option<AccountId>;
+
+    /// Weight control fee declared by the allocator when added by the DAO.
+    fn allocator_fee(key: &AccountId) -> Option<Percent>;
+
+    /// Maximum number of agents that can delegate weight control to the
+    /// allocator. `None` if unlimited.
+    fn allocator_capacity(key: &AccountId) -> Option<u32>;
+
     fn set_allocator(key: &AccountId);
 
     fn can_create_namespace(key: &AccountId) -> bool;
 
     fn can_register_agent(key: &AccountId) -> bool;
 
+    /// Number of records of other accounts [`Self::rotate_key`] rewrites
+    /// when rotating the key.
+    fn key_references(key: &AccountId) -> u32;
+
+    /// Moves the whitelist entry, allocator role and voting settings of an
+    /// agent key to the key replacing it.
+    fn rotate_key(old_key: &AccountId, new_key: &AccountId);
+
     #[doc(hidden)]
     #[cfg(feature = "runtime-benchmarks")]
     fn force_set_whitelisted(key: &AccountId);
@@ -0,0 +1,51 @@
+use pallet_governance_api::{CuratorAction, CuratorActionRecord};
+use pallet_permission0_api::PermissionId;
+use polkadot_sdk::{
+    frame_support::traits::Get,
+    frame_system::{self, pallet_prelude::BlockNumberFor},
+    sp_std::vec::Vec,
+};
+
+use crate::{AccountIdOf, Config, CuratorActionCount, CuratorActions};
+
+/// Appends an action to the curator action log, pruning the oldest entry
+/// once more than [`Config::MaxCuratorActions`] are kept.
+pub fn record_curator_action<T: Config>(
+    curator: AccountIdOf<T>,
+    permission_id: Option<PermissionId>,
+    action: CuratorAction<AccountIdOf<T>>,
+) {
+    let index = CuratorActionCount::<T>::get();
+    CuratorActionCount::<T>::set(index.saturating_add(1));
+
+    if let Some(expired) = index.checked_sub(T::MaxCuratorActions::get() as u64) {
+        CuratorActions::<T>::remove(expired);
+    }
+
+    CuratorActions::<T>::insert(
+        index,
+        CuratorActionRecord {
+            curator,
+            permission_id,
+            action,
+            block: <frame_system::Pallet<T>>::block_number(),
+        },
+    );
+}
+
+/// Returns up to `count` logged curator actions before the entry `before`,
+/// or the latest entry if `None`, from the most recent to the oldest.
+pub fn curator_actions<T: Config>(
+    before: Option<u64>,
+    count: u32,
+) -> Vec<(u64, CuratorActionRecord<AccountIdOf<T>, BlockNumberFor<T>>)> {
+    let total = CuratorActionCount::<T>::get();
+    let end = before.map_or(total, |before| before.min(total));
+    let count = count.min(T::MaxCuratorActions::get());
+
+    (0..end)
+        .rev()
+        .take(count as usize)
+        .map_while(|index| Some((index, CuratorActions::<T>::get(index)?)))
+        .collect()
+}
@@ -1,6 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod application;
+pub mod audit;
 pub mod config;
 pub mod ext;
 pub mod migrations;
@@ -35,6 +36,7 @@ use crate::{
     application::AgentApplication,
     config::GovernanceConfiguration,
     proposal::{Proposal, ProposalId, UnrewardedProposal},
+    roles::{AllocatorAssignmentPolicy, AllocatorTerms},
 };
 
 #[frame::pallet]
@@ -43,6 +45,7 @@ pub mod pallet {
 
     const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
+    use pallet_governance_api::{CuratorAction, CuratorActionRecord, ProposalCategory};
     use pallet_permission0_api::{CuratorPermissions, Permission0Api, Permission0CuratorApi};
     use polkadot_sdk::sp_core::ConstBool;
     use proposal::GlobalParamsData;
@@ -96,6 +99,27 @@ pub mod pallet {
     #[pallet::storage]
     pub type Allocators<T: Config> = StorageMap<_, Identity, AccountIdOf<T>, ()>;
 
+    /// Terms declared by the allocators added through DAO proposals.
+    /// Allocators without terms have no capacity limit and use their agent
+    /// weight control fee.
+    #[pallet::storage]
+    pub type DeclaredAllocatorTerms<T: Config> =
+        StorageMap<_, Identity, AccountIdOf<T>, AllocatorTerms>;
+
+    /// Policy used to assign an allocator to newly registered agents.
+    #[pallet::storage]
+    pub type AllocatorAssignment<T: Config> =
+        StorageValue<_, AllocatorAssignmentPolicy, ValueQuery>;
+
+    /// Allocator last assigned by the round-robin policy.
+    #[pallet::storage]
+    pub type LastAssignedAllocator<T: Config> = StorageValue<_, AccountIdOf<T>>;
+
+    /// Removed allocators still holding weight control delegations, which are
+    /// reassigned a page per block.
+    #[pallet::storage]
+    pub type RemovedAllocators<T: Config> = StorageMap<_, Identity, AccountIdOf<T>, ()>;
+
     /// Fee taken from emission distribution and deposited into
     /// [`DaoTreasuryAddress`].
     #[pallet::storage]
@@ -110,6 +134,39 @@ pub mod pallet {
     #[pallet::storage]
     pub type NamespacesFrozen<T: Config> = StorageValue<_, bool, ValueQuery, ConstBool<false>>;
 
+    /// Delegates chosen by accounts to vote on proposals of a category on
+    /// their behalf, indexed by the category and the delegating account.
+    /// Overrides the implicit delegation of voting power to the agents the
+    /// account stakes to.
+    #[pallet::storage]
+    pub type VoteDelegations<T: Config> =
+        StorageDoubleMap<_, Identity, ProposalCategory, Identity, AccountIdOf<T>, AccountIdOf<T>>;
+
+    /// Accounts delegating their votes on proposals of a category to a
+    /// delegate, indexed by the category and the delegate. Reverse index of
+    /// [`VoteDelegations`].
+    #[pallet::storage]
+    pub type VoteDelegators<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, ProposalCategory>,
+            NMapKey<Identity, AccountIdOf<T>>,
+            NMapKey<Identity, AccountIdOf<T>>,
+        ),
+        (),
+    >;
+
+    /// Number of curator actions logged so far. Also the index of the next
+    /// entry of the log.
+    #[pallet::storage]
+    pub type CuratorActionCount<T: Config> = StorageValue<_, u64, ValueQuery>;
+
+    /// Log of the actions performed by curators, indexed by their order. Only
+    /// the last [`Config::MaxCuratorActions`] entries are kept.
+    #[pallet::storage]
+    pub type CuratorActions<T: Config> =
+        StorageMap<_, Identity, u64, CuratorActionRecord<AccountIdOf<T>, BlockNumberFor<T>>>;
+
     #[pallet::config]
     pub trait Config:
         polkadot_sdk::frame_system::Config + pallet_torus0::Config + pallet_emission0::Config
@@ -156,6 +213,20 @@ pub mod pallet {
         #[pallet::constant]
         type DefaultProposalRewardInterval: Get<BlockNumberFor<Self>>;
 
+        /// Number of curator actions kept in the log.
+        #[pallet::constant]
+        type MaxCuratorActions: Get<u32>;
+
+        /// Number of agents delegating to removed allocators that are
+        /// reassigned per block.
+        #[pallet::constant]
+        type MaxRebalancedDelegations: Get<u32>;
+
+        /// Maximum number of accounts delegating their votes on a proposal
+        /// category to the same delegate.
+        #[pallet::constant]
+        type MaxVoteDelegators: Get<u32>;
+
         type Currency: Currency<Self::AccountId, Balance = u128> + Send + Sync;
 
         type Permission0: Permission0Api<OriginFor<Self>>
@@ -176,6 +247,8 @@ pub mod pallet {
             proposal::tick_proposals::<T>(block_number);
             proposal::tick_proposal_rewards::<T>(block_number);
 
+            roles::rebalance_removed_allocators::<T>();
+
             Weight::zero()
         }
     }
@@ -204,11 +277,19 @@ pub mod pallet {
         #[pallet::call_index(4)]
         #[pallet::weight((<T as Config>::WeightInfo::add_to_whitelist(), DispatchClass::Normal, Pays::Yes))]
         pub fn add_to_whitelist(origin: OriginFor<T>, key: AccountIdOf<T>) -> DispatchResult {
-            <T as Config>::Permission0::ensure_curator_permission(
+            let (curator, permission_id) = <T as Config>::Permission0::ensure_curator_permission(
                 origin,
                 CuratorPermissions::WHITELIST_MANAGE,
             )?;
-            whitelist::add_to_whitelist::<T>(key)
+            whitelist::add_to_whitelist::<T>(key.clone())?;
+
+            audit::record_curator_action::<T>(
+                curator,
+                permission_id,
+                CuratorAction::WhitelistAdded(key),
+            );
+
+            Ok(())
         }
 
         /// Forcefully removes an agent from the whitelist. Only available for
@@ -216,11 +297,19 @@ pub mod pallet {
         #[pallet::call_index(5)]
         #[pallet::weight((<T as Config>::WeightInfo::remove_from_whitelist(), DispatchClass::Normal, Pays::Yes))]
         pub fn remove_from_whitelist(origin: OriginFor<T>, key: AccountIdOf<T>) -> DispatchResult {
-            <T as Config>::Permission0::ensure_curator_permission(
+            let (curator, permission_id) = <T as Config>::Permission0::ensure_curator_permission(
                 origin,
                 CuratorPermissions::WHITELIST_MANAGE,
             )?;
-            whitelist::remove_from_whitelist::<T>(key)
+            whitelist::remove_from_whitelist::<T>(key.clone())?;
+
+            audit::record_curator_action::<T>(
+                curator,
+                permission_id,
+                CuratorAction::WhitelistRemoved(key),
+            );
+
+            Ok(())
         }
 
         /// Accepts an agent application. Only available for the root key or
@@ -228,11 +317,19 @@ pub mod pallet {
         #[pallet::call_index(6)]
         #[pallet::weight((<T as Config>::WeightInfo::accept_application(), DispatchClass::Normal, Pays::Yes))]
         pub fn accept_application(origin: OriginFor<T>, application_id: u32) -> DispatchResult {
-            <T as Config>::Permission0::ensure_curator_permission(
+            let (curator, permission_id) = <T as Config>::Permission0::ensure_curator_permission(
                 origin,
                 CuratorPermissions::APPLICATION_REVIEW,
             )?;
-            application::accept_application::<T>(application_id)
+            application::accept_application::<T>(application_id)?;
+
+            audit::record_curator_action::<T>(
+                curator,
+                permission_id,
+                CuratorAction::ApplicationAccepted(application_id),
+            );
+
+            Ok(())
         }
 
         /// Denies an agent application. Only available for the root key or
@@ -240,11 +337,19 @@ pub mod pallet {
         #[pallet::call_index(7)]
         #[pallet::weight((<T as Config>::WeightInfo::deny_application(), DispatchClass::Normal, Pays::Yes))]
         pub fn deny_application(origin: OriginFor<T>, application_id: u32) -> DispatchResult {
-            <T as Config>::Permission0::ensure_curator_permission(
+            let (curator, permission_id) = <T as Config>::Permission0::ensure_curator_permission(
                 origin,
                 CuratorPermissions::APPLICATION_REVIEW,
             )?;
-            application::deny_application::<T>(application_id)
+            application::deny_application::<T>(application_id)?;
+
+            audit::record_curator_action::<T>(
+                curator,
+                permission_id,
+                CuratorAction::ApplicationDenied(application_id),
+            );
+
+            Ok(())
         }
 
         /// Sets a penalty factor to the given agent emissions. Only available
@@ -391,14 +496,21 @@ pub mod pallet {
         #[pallet::call_index(19)]
         #[pallet::weight((<T as Config>::WeightInfo::toggle_agent_freezing(), DispatchClass::Normal, Pays::No))]
         pub fn toggle_agent_freezing(origin: OriginFor<T>) -> DispatchResult {
-            let curator = <T as pallet::Config>::Permission0::ensure_curator_permission(
-                origin,
-                CuratorPermissions::AGENT_FREEZING_TOGGLING,
-            )?;
+            let (curator, permission_id) =
+                <T as pallet::Config>::Permission0::ensure_curator_permission(
+                    origin,
+                    CuratorPermissions::AGENT_FREEZING_TOGGLING,
+                )?;
 
             let new_state = !crate::AgentsFrozen::<T>::get();
             AgentsFrozen::<T>::set(new_state);
 
+            audit::record_curator_action::<T>(
+                curator.clone(),
+                permission_id,
+                CuratorAction::AgentFreezingToggled(new_state),
+            );
+
             crate::Pallet::<T>::deposit_event(crate::Event::AgentFreezingToggled {
                 curator,
                 new_state,
@@ -410,14 +522,21 @@ pub mod pallet {
         #[pallet::call_index(20)]
         #[pallet::weight((<T as Config>::WeightInfo::toggle_namespace_freezing(), DispatchClass::Normal, Pays::No))]
         pub fn toggle_namespace_freezing(origin: OriginFor<T>) -> DispatchResult {
-            let curator = <T as pallet::Config>::Permission0::ensure_curator_permission(
-                origin,
-                CuratorPermissions::NAMESPACE_FREEZING_TOGGLING,
-            )?;
+            let (curator, permission_id) =
+                <T as pallet::Config>::Permission0::ensure_curator_permission(
+                    origin,
+                    CuratorPermissions::NAMESPACE_FREEZING_TOGGLING,
+                )?;
 
             let new_state = !crate::NamespacesFrozen::<T>::get();
             NamespacesFrozen::<T>::set(new_state);
 
+            audit::record_curator_action::<T>(
+                curator.clone(),
+                permission_id,
+                CuratorAction::NamespaceFreezingToggled(new_state),
+            );
+
             crate::Pallet::<T>::deposit_event(crate::Event::NamespaceFreezingToggled {
                 curator,
                 new_state,
@@ -425,6 +544,136 @@ pub mod pallet {
 
             Ok(())
         }
+
+        /// Forcefully sets the weight of the current epoch bonds in the moving
+        /// average of validator bonds. Only available for the root key.
+        #[pallet::call_index(21)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_emission_proposal(), DispatchClass::Normal, Pays::No))]
+        pub fn set_bonds_moving_average(origin: OriginFor<T>, alpha: Percent) -> DispatchResult {
+            ensure_root(origin)?;
+
+            ensure!(
+                alpha > Percent::zero(),
+                Error::<T>::InvalidBondsMovingAverage
+            );
+            pallet_emission0::BondsMovingAverage::<T>::set(alpha);
+
+            Ok(())
+        }
+
+        /// Forcefully sets the consensus step parameters used on emission
+        /// distribution. Only available for the root key.
+        #[pallet::call_index(22)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_emission_proposal(), DispatchClass::Normal, Pays::No))]
+        pub fn set_consensus_config(
+            origin: OriginFor<T>,
+            config: pallet_emission0::ConsensusConfiguration,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            pallet_emission0::ConsensusConfig::<T>::set(config);
+
+            Ok(())
+        }
+
+        /// Forcefully enables or disables weight commit-reveal. Only available
+        /// for the root key.
+        #[pallet::call_index(23)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_emission_proposal(), DispatchClass::Normal, Pays::No))]
+        pub fn set_weights_commit_reveal(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+
+            pallet_emission0::WeightsCommitReveal::<T>::set(enabled);
+
+            Ok(())
+        }
+
+        /// Creates a proposal adding an allocator with the given weight
+        /// control fee and maximum number of delegating agents. The terms of
+        /// an existing allocator are replaced.
+        #[pallet::call_index(24)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_global_custom_proposal(), DispatchClass::Normal, Pays::Yes))]
+        pub fn add_allocator_proposal(
+            origin: OriginFor<T>,
+            key: AccountIdOf<T>,
+            fee: Percent,
+            capacity: u32,
+            metadata: Vec<u8>,
+        ) -> DispatchResult {
+            let proposer = ensure_signed(origin)?;
+            proposal::add_allocator_proposal::<T>(
+                proposer,
+                key,
+                AllocatorTerms { fee, capacity },
+                metadata,
+            )
+        }
+
+        /// Creates a proposal removing an allocator. Agents delegating to it
+        /// are reassigned to the remaining allocators.
+        #[pallet::call_index(25)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_global_custom_proposal(), DispatchClass::Normal, Pays::Yes))]
+        pub fn remove_allocator_proposal(
+            origin: OriginFor<T>,
+            key: AccountIdOf<T>,
+            metadata: Vec<u8>,
+        ) -> DispatchResult {
+            let proposer = ensure_signed(origin)?;
+            proposal::remove_allocator_proposal::<T>(proposer, key, metadata)
+        }
+
+        /// Forcefully sets how newly registered agents are assigned an
+        /// allocator. Only available for the root key.
+        #[pallet::call_index(26)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_emission_proposal(), DispatchClass::Normal, Pays::No))]
+        pub fn set_allocator_assignment_policy(
+            origin: OriginFor<T>,
+            policy: AllocatorAssignmentPolicy,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+
+            AllocatorAssignment::<T>::set(policy);
+
+            Ok(())
+        }
+
+        /// Forcefully allows or disallows delegating weight control to agents
+        /// other than allocators. Only available for the root key.
+        #[pallet::call_index(27)]
+        #[pallet::weight((<T as Config>::WeightInfo::add_emission_proposal(), DispatchClass::Normal, Pays::No))]
+        pub fn set_open_weight_delegation(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+            ensure_root(origin)?;
+
+            pallet_emission0::OpenWeightDelegation::<T>::set(enabled);
+
+            Ok(())
+        }
+
+        /// Delegates the caller's votes on proposals of the given categories,
+        /// or of every category if none is given, to another account. Voting
+        /// directly on a proposal overrides the delegation.
+        #[pallet::call_index(28)]
+        #[pallet::weight((<T as Config>::WeightInfo::enable_vote_delegation(), DispatchClass::Normal, Pays::Yes))]
+        pub fn delegate_votes(
+            origin: OriginFor<T>,
+            delegate: AccountIdOf<T>,
+            categories: voting::ProposalCategories,
+        ) -> DispatchResult {
+            let delegator = ensure_signed(origin)?;
+            voting::delegate_votes::<T>(delegator, delegate, categories)
+        }
+
+        /// Removes the caller's vote delegations on the given categories, or
+        /// on every category if none is given.
+        #[pallet::call_index(29)]
+        #[pallet::weight((<T as Config>::WeightInfo::disable_vote_delegation(), DispatchClass::Normal, Pays::Yes))]
+        pub fn undelegate_votes(
+            origin: OriginFor<T>,
+            categories: voting::ProposalCategories,
+        ) -> DispatchResult {
+            let delegator = ensure_signed(origin)?;
+            voting::undelegate_votes::<T>(delegator, categories)
+        }
     }
 
     #[pallet::event]
@@ -470,6 +719,27 @@ pub mod pallet {
             curator: T::AccountId,
             new_state: bool,
         },
+        /// An allocator was added, or had its terms updated.
+        AllocatorAdded(T::AccountId),
+        /// An allocator was removed.
+        AllocatorRemoved(T::AccountId),
+        /// An agent delegating to a removed allocator could not be reassigned,
+        /// and regained weight control.
+        WeightControlNotReassigned {
+            agent: T::AccountId,
+            allocator: T::AccountId,
+        },
+        /// An account delegated its votes on the given categories.
+        VotesDelegated {
+            delegator: T::AccountId,
+            delegate: T::AccountId,
+            categories: Vec<ProposalCategory>,
+        },
+        /// An account removed its vote delegations on the given categories.
+        VotesUndelegated {
+            delegator: T::AccountId,
+            categories: Vec<ProposalCategory>,
+        },
     }
 
     #[pallet::error]
@@ -558,6 +828,20 @@ pub mod pallet {
         InvalidMinStakingFee,
         /// Invalid params given to Emission proposal
         InvalidEmissionProposalData,
+        /// The bonds moving average must be greater than zero, otherwise bonds
+        /// would never change.
+        InvalidBondsMovingAverage,
+        /// Allocators must accept at least one delegating agent.
+        InvalidAllocatorCapacity,
+        /// The allocator fee is below the minimum weight control fee.
+        InvalidAllocatorFee,
+        /// Tried delegating votes to itself.
+        CannotDelegateVotesToSelf,
+        /// The account does not delegate its votes on the given categories.
+        NotDelegatingVotes,
+        /// The delegate already has the maximum number of vote delegators on
+        /// one of the categories.
+        TooManyVoteDelegators,
     }
 }
 
@@ -582,6 +866,18 @@ impl<T: Config> pallet_governance_api::GovernanceApi<T::AccountId> for Pallet<T>
         Allocators::<T>::iter_keys()
     }
 
+    fn assign_allocator(agent: &T::AccountId) -> Option<T::AccountId> {
+        roles::assign_allocator::<T>(agent)
+    }
+
+    fn allocator_fee(key: &T::AccountId) -> Option<Percent> {
+        DeclaredAllocatorTerms::<T>::get(key).map(|terms| terms.fee)
+    }
+
+    fn allocator_capacity(key: &T::AccountId) -> Option<u32> {
+        DeclaredAllocatorTerms::<T>::get(key).map(|terms| terms.capacity)
+    }
+
     fn set_allocator(key: &T::AccountId) {
         Allocators::<T>::insert(key, ());
     }
@@ -594,6 +890,14 @@ impl<T: Config> pallet_governance_api::GovernanceApi<T::AccountId> for Pallet<T>
         !AgentsFrozen::<T>::get() || Self::is_whitelisted(key)
     }
 
+    fn key_references(key: &T::AccountId) -> u32 {
+        roles::key_references::<T>(key)
+    }
+
+    fn rotate_key(old_key: &T::AccountId, new_key: &T::AccountId) {
+        roles::rotate_key::<T>(old_key, new_key)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn force_set_whitelisted(key: &T::AccountId) {
         Whitelist::<T>::insert(key, ());
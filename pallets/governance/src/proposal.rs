@@ -1,4 +1,5 @@
 use codec::{Decode, Encode, MaxEncodedLen};
+use pallet_governance_api::ProposalCategory;
 use pallet_torus0::namespace::NamespacePricingConfig;
 use polkadot_sdk::{
     frame_election_provider_support::Get,
@@ -17,7 +18,7 @@ use polkadot_sdk::{
 use crate::{
     AccountIdOf, BalanceOf, BoundedBTreeSet, BoundedVec, DaoTreasuryAddress, DebugNoBound, Error,
     GlobalGovernanceConfig, GovernanceConfiguration, NotDelegatingVotingPower, Proposals, TypeInfo,
-    UnrewardedProposals, frame::traits::ExistenceRequirement,
+    UnrewardedProposals, frame::traits::ExistenceRequirement, roles::AllocatorTerms,
 };
 
 pub type ProposalId = u64;
@@ -141,6 +142,17 @@ impl<T: crate::Config> Proposal<T> {
                 pallet_emission0::IncentivesRatio::<T>::set(incentives_ratio);
             }
 
+            ProposalData::AddAllocator { key, terms } => {
+                crate::roles::set_allocator_with_terms::<T>(key, terms);
+            }
+
+            ProposalData::RemoveAllocator { key } => {
+                // The allocator may have been removed since the proposal was created.
+                if crate::Allocators::<T>::contains_key(&key) {
+                    crate::roles::remove_allocator::<T>(key)?;
+                }
+            }
+
             ProposalData::GlobalCustom => {}
         }
 
@@ -294,6 +306,14 @@ pub enum ProposalData<T: crate::Config> {
         account: AccountIdOf<T>,
         amount: BalanceOf<T>,
     },
+    /// Adds an allocator with the declared terms, or replaces the terms of an
+    /// existing one.
+    AddAllocator {
+        key: AccountIdOf<T>,
+        terms: AllocatorTerms,
+    },
+    /// Removes an allocator, reassigning the agents delegating to it.
+    RemoveAllocator { key: AccountIdOf<T> },
 }
 
 impl<T: crate::Config> ProposalData<T> {
@@ -304,7 +324,22 @@ impl<T: crate::Config> ProposalData<T> {
         match self {
             Self::Emission { .. } => Percent::from_parts(10),
             Self::GlobalCustom | Self::TransferDaoTreasury { .. } => Percent::from_parts(50),
-            Self::GlobalParams { .. } => Percent::from_parts(40),
+            Self::GlobalParams { .. }
+            | Self::AddAllocator { .. }
+            | Self::RemoveAllocator { .. } => Percent::from_parts(40),
+        }
+    }
+
+    /// The category of the proposal, used to resolve vote delegations.
+    #[must_use]
+    pub fn category(&self) -> ProposalCategory {
+        match self {
+            Self::GlobalParams(_) => ProposalCategory::GlobalParams,
+            Self::GlobalCustom => ProposalCategory::GlobalCustom,
+            Self::Emission { .. } => ProposalCategory::Emission,
+            Self::TransferDaoTreasury { .. } => ProposalCategory::TransferDaoTreasury,
+            Self::AddAllocator { .. } => ProposalCategory::AddAllocator,
+            Self::RemoveAllocator { .. } => ProposalCategory::RemoveAllocator,
         }
     }
 }
@@ -353,6 +388,48 @@ pub fn add_dao_treasury_transfer_proposal<T: crate::Config>(
     add_proposal::<T>(proposer, data, metadata)
 }
 
+/// Creates a proposal adding an allocator with the given terms. The key must
+/// be a registered agent.
+pub fn add_allocator_proposal<T: crate::Config>(
+    proposer: AccountIdOf<T>,
+    key: AccountIdOf<T>,
+    terms: AllocatorTerms,
+    metadata: Vec<u8>,
+) -> DispatchResult {
+    ensure!(
+        pallet_torus0::Agents::<T>::contains_key(&key),
+        crate::Error::<T>::AgentNotFound
+    );
+    ensure!(
+        terms.capacity > 0,
+        crate::Error::<T>::InvalidAllocatorCapacity
+    );
+    ensure!(
+        terms.fee >= pallet_torus0::FeeConstraints::<T>::get().min_weight_control_fee,
+        crate::Error::<T>::InvalidAllocatorFee
+    );
+
+    let data = ProposalData::<T>::AddAllocator { key, terms };
+
+    add_proposal::<T>(proposer, data, metadata)
+}
+
+/// Creates a proposal removing an existing allocator.
+pub fn remove_allocator_proposal<T: crate::Config>(
+    proposer: AccountIdOf<T>,
+    key: AccountIdOf<T>,
+    metadata: Vec<u8>,
+) -> DispatchResult {
+    ensure!(
+        crate::Allocators::<T>::contains_key(&key),
+        crate::Error::<T>::NotAllocator
+    );
+
+    let data = ProposalData::<T>::RemoveAllocator { key };
+
+    add_proposal::<T>(proposer, data, metadata)
+}
+
 /// Creates a new emissions proposal. Only valid if `recycling_percentage +
 /// treasury_percentage <= u128::MAX`.
 pub fn add_emission_proposal<T: crate::Config>(
@@ -480,11 +557,17 @@ fn tick_proposal<T: crate::Config>(
         return Err(Error::<T>::ProposalIsFinished.into());
     };
 
+    let category = proposal.data.category();
+    let direct_voters: BTreeSet<_> = votes_for.iter().chain(votes_against).cloned().collect();
+    let calc_stake = |voter: &AccountIdOf<T>| {
+        crate::voting::calc_voting_power::<T>(not_delegating, category, &direct_voters, voter)
+    };
+
     let votes_for: Vec<(AccountIdOf<T>, BalanceOf<T>)> = votes_for
         .iter()
         .cloned()
         .map(|id| {
-            let stake = calc_stake::<T>(not_delegating, &id);
+            let stake = calc_stake(&id);
             (id, stake)
         })
         .collect();
@@ -492,7 +575,7 @@ fn tick_proposal<T: crate::Config>(
         .iter()
         .cloned()
         .map(|id| {
-            let stake = calc_stake::<T>(not_delegating, &id);
+            let stake = calc_stake(&id);
             (id, stake)
         })
         .collect();
@@ -566,28 +649,6 @@ fn create_unrewarded_proposal<T: crate::Config>(
     );
 }
 
-/// Calculates the stake for a voter. This function takes into account all
-/// accounts delegating voting power to the voter.
-#[inline]
-fn calc_stake<T: crate::Config>(
-    not_delegating: &BTreeSet<T::AccountId>,
-    voter: &T::AccountId,
-) -> BalanceOf<T> {
-    let own_stake: BalanceOf<T> = if !not_delegating.contains(voter) {
-        0
-    } else {
-        pallet_torus0::stake::sum_staking_to::<T>(voter)
-    };
-
-    let delegated_stake = pallet_torus0::stake::get_staked_by_vector::<T>(voter)
-        .into_iter()
-        .filter(|(staker, _)| !not_delegating.contains(staker))
-        .map(|(_, stake)| stake)
-        .sum();
-
-    own_stake.saturating_add(delegated_stake)
-}
-
 /// Processes the proposal reward queue and distributes rewards for all voters.
 pub fn tick_proposal_rewards<T: crate::Config>(block_number: BlockNumberFor<T>) {
     let governance_config = crate::GlobalGovernanceConfig::<T>::get();
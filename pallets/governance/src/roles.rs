@@ -1,12 +1,41 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use pallet_governance_api::{CuratorAction, ProposalCategory};
 use pallet_permission0_api::{CuratorPermissions, Permission0CuratorApi};
 use polkadot_sdk::{
     frame_election_provider_support::Get,
     frame_support::dispatch::DispatchResult,
     polkadot_sdk_frame::prelude::OriginFor,
-    sp_runtime::{DispatchError, Percent},
+    sp_runtime::{DispatchError, Percent, traits::Saturating},
+    sp_std::{collections::btree_map::BTreeMap, vec::Vec},
 };
+use scale_info::TypeInfo;
 
-use crate::{AccountIdOf, Allocators, Config, Error, Event, ensure};
+use crate::{
+    AccountIdOf, AllocatorAssignment, Allocators, Config, DeclaredAllocatorTerms, Error, Event,
+    LastAssignedAllocator, RemovedAllocators, ensure, voting::set_vote_delegation,
+};
+
+/// Terms an allocator committed to when added by the DAO.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, TypeInfo, Decode, Encode, MaxEncodedLen)]
+pub struct AllocatorTerms {
+    /// Weight control fee taken from the agents delegating to the allocator.
+    pub fee: Percent,
+    /// Maximum number of agents delegating weight control to the allocator.
+    pub capacity: u32,
+}
+
+/// How newly registered agents are assigned an allocator.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, TypeInfo, Decode, Encode, MaxEncodedLen)]
+pub enum AllocatorAssignmentPolicy {
+    /// Assigns the allocator with the fewest delegating agents, the lowest
+    /// key winning ties.
+    #[default]
+    LeastDelegated,
+    /// Cycles through the allocators in key order.
+    RoundRobin,
+    /// Agents are not assigned an allocator and choose one themselves.
+    AgentChoice,
+}
 
 /// Adds a new allocator to the network, checking wether it's already registered.
 #[doc(hidden)]
@@ -15,11 +44,25 @@ pub fn add_allocator<T: Config>(key: AccountIdOf<T>) -> DispatchResult {
         !Allocators::<T>::contains_key(&key),
         Error::<T>::AlreadyAllocator
     );
-    Allocators::<T>::insert(key, ());
+    Allocators::<T>::insert(&key, ());
+    RemovedAllocators::<T>::remove(&key);
+    crate::Pallet::<T>::deposit_event(Event::AllocatorAdded(key));
     Ok(())
 }
 
+/// Adds an allocator with the terms approved by the DAO, replacing the terms
+/// of an existing allocator.
+pub(crate) fn set_allocator_with_terms<T: Config>(key: AccountIdOf<T>, terms: AllocatorTerms) {
+    Allocators::<T>::insert(&key, ());
+    DeclaredAllocatorTerms::<T>::insert(&key, terms);
+    RemovedAllocators::<T>::remove(&key);
+    crate::Pallet::<T>::deposit_event(Event::AllocatorAdded(key));
+}
+
 /// Removes an existing allocator to the network, checking wether it's registered.
+/// Agents delegating weight control to it are reassigned to the remaining
+/// allocators, up to [`Config::MaxRebalancedDelegations`] right away and the
+/// rest over the following blocks.
 #[doc(hidden)]
 pub fn remove_allocator<T: Config>(key: AccountIdOf<T>) -> DispatchResult {
     ensure!(
@@ -27,16 +70,135 @@ pub fn remove_allocator<T: Config>(key: AccountIdOf<T>) -> DispatchResult {
         Error::<T>::NotAllocator
     );
     Allocators::<T>::remove(&key);
+    DeclaredAllocatorTerms::<T>::remove(&key);
+
+    let limit = T::MaxRebalancedDelegations::get();
+    if rebalance_delegations::<T>(&key, limit) >= limit {
+        RemovedAllocators::<T>::insert(&key, ());
+    }
+
+    crate::Pallet::<T>::deposit_event(Event::AllocatorRemoved(key));
     Ok(())
 }
 
+/// Picks the allocator for the agent according to the
+/// [`AllocatorAssignmentPolicy`], skipping allocators with no capacity left.
+pub fn assign_allocator<T: Config>(agent: &AccountIdOf<T>) -> Option<AccountIdOf<T>> {
+    let policy = AllocatorAssignment::<T>::get();
+    if policy == AllocatorAssignmentPolicy::AgentChoice {
+        return None;
+    }
+
+    let mut candidates: Vec<_> = Allocators::<T>::iter_keys()
+        .filter(|allocator| allocator != agent)
+        .map(|allocator| {
+            let count = pallet_emission0::WeightControlDelegatorCount::<T>::get(&allocator);
+            (allocator, count)
+        })
+        .filter(|(allocator, count)| {
+            DeclaredAllocatorTerms::<T>::get(allocator).is_none_or(|terms| *count < terms.capacity)
+        })
+        .collect();
+    candidates.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    match policy {
+        AllocatorAssignmentPolicy::LeastDelegated => candidates
+            .into_iter()
+            .min_by_key(|(_, count)| *count)
+            .map(|(allocator, _)| allocator),
+        AllocatorAssignmentPolicy::RoundRobin => {
+            let last = LastAssignedAllocator::<T>::get();
+            let next = candidates
+                .iter()
+                .position(|(allocator, _)| last.as_ref().is_some_and(|last| allocator > last))
+                .unwrap_or_default();
+
+            let (allocator, _) = candidates.into_iter().nth(next)?;
+            LastAssignedAllocator::<T>::set(Some(allocator.clone()));
+            Some(allocator)
+        }
+        AllocatorAssignmentPolicy::AgentChoice => None,
+    }
+}
+
+/// Reassigns up to [`Config::MaxRebalancedDelegations`] agents delegating to
+/// removed allocators, forgetting the allocators left without delegators.
+pub(crate) fn rebalance_removed_allocators<T: Config>() {
+    let mut limit = T::MaxRebalancedDelegations::get();
+
+    let removed: Vec<_> = RemovedAllocators::<T>::iter_keys().collect();
+    for allocator in removed {
+        if limit == 0 {
+            break;
+        }
+
+        let rebalanced = rebalance_delegations::<T>(&allocator, limit);
+        if rebalanced < limit {
+            RemovedAllocators::<T>::remove(&allocator);
+        }
+        limit = limit.saturating_sub(rebalanced);
+    }
+}
+
+/// Reassigns the weight control share given to a removed allocator by up to
+/// `limit` agents, returning how many were visited. The share goes to a newly
+/// assigned allocator or, failing that, to the delegator's largest remaining
+/// delegatee. Agents that cannot be reassigned regain weight control.
+fn rebalance_delegations<T: Config>(removed: &AccountIdOf<T>, limit: u32) -> u32 {
+    let delegations: Vec<_> =
+        pallet_emission0::WeightControlDelegators::<T>::iter_key_prefix(removed)
+            .take(limit as usize)
+            .filter_map(|delegator| {
+                let shares = pallet_emission0::WeightControlDelegation::<T>::get(&delegator)?;
+                Some((delegator, shares))
+            })
+            .collect();
+    let visited = delegations.len() as u32;
+
+    for (delegator, shares) in delegations {
+        pallet_emission0::weight_control::set_delegation::<T>(&delegator, None);
+
+        let mut shares: BTreeMap<_, _> = shares.into_iter().collect();
+        let Some(removed_share) = shares.remove(removed) else {
+            continue;
+        };
+
+        let reassigned = assign_allocator::<T>(&delegator)
+            .or_else(|| {
+                shares
+                    .iter()
+                    .max_by_key(|(_, share)| **share)
+                    .map(|(delegatee, _)| delegatee.clone())
+            })
+            .is_some_and(|allocator| {
+                let share = shares.entry(allocator).or_default();
+                *share = share.saturating_add(removed_share);
+
+                pallet_emission0::weight_control::delegate_weight_control_shares::<T>(
+                    delegator.clone(),
+                    shares.into_iter().collect(),
+                )
+                .is_ok()
+            });
+
+        if !reassigned {
+            crate::Pallet::<T>::deposit_event(Event::WeightControlNotReassigned {
+                agent: delegator,
+                allocator: removed.clone(),
+            });
+        }
+    }
+
+    visited
+}
+
 /// Sets a penalty ratio for the given agent.
 pub fn penalize_agent<T: Config>(
     origin: OriginFor<T>,
     agent_key: AccountIdOf<T>,
     percentage: u8,
 ) -> DispatchResult {
-    let curator = <T as Config>::Permission0::ensure_curator_permission(
+    let (curator, permission_id) = <T as Config>::Permission0::ensure_curator_permission(
         origin,
         CuratorPermissions::PENALTY_CONTROL,
     )?;
@@ -56,6 +218,15 @@ pub fn penalize_agent<T: Config>(
         Ok::<(), DispatchError>(())
     })?;
 
+    crate::audit::record_curator_action::<T>(
+        curator.clone(),
+        permission_id,
+        CuratorAction::AgentPenalized {
+            agent: agent_key.clone(),
+            penalty: percentage,
+        },
+    );
+
     crate::Pallet::<T>::deposit_event(Event::PenaltyApplied {
         curator,
         agent: agent_key,
@@ -73,3 +244,56 @@ pub fn ensure_allocator<T: Config>(key: &AccountIdOf<T>) -> DispatchResult {
 
     Ok(())
 }
+
+/// Moves the whitelist entry, the allocator role and terms, and the voting
+/// settings of an agent key to the key replacing it. Votes delegated to the
+/// old key follow it to the new one.
+pub(crate) fn rotate_key<T: Config>(old_key: &AccountIdOf<T>, new_key: &AccountIdOf<T>) {
+    if crate::Whitelist::<T>::take(old_key).is_some() {
+        crate::Whitelist::<T>::insert(new_key, ());
+    }
+
+    if Allocators::<T>::take(old_key).is_some() {
+        Allocators::<T>::insert(new_key, ());
+    }
+
+    if let Some(terms) = DeclaredAllocatorTerms::<T>::take(old_key) {
+        DeclaredAllocatorTerms::<T>::insert(new_key, terms);
+    }
+
+    LastAssignedAllocator::<T>::mutate(|last| {
+        if last.as_ref() == Some(old_key) {
+            *last = Some(new_key.clone());
+        }
+    });
+
+    crate::NotDelegatingVotingPower::<T>::mutate(|delegators| {
+        if delegators.remove(old_key) {
+            let _ = delegators.try_insert(new_key.clone());
+        }
+    });
+
+    for category in ProposalCategory::ALL {
+        if let Some(delegate) = set_vote_delegation::<T>(category, old_key, None)
+            && delegate != *new_key
+        {
+            set_vote_delegation::<T>(category, new_key, Some(&delegate));
+        }
+
+        let delegators: Vec<_> =
+            crate::VoteDelegators::<T>::iter_key_prefix((category, old_key)).collect();
+        for delegator in delegators {
+            let delegate = (delegator != *new_key).then_some(new_key);
+            set_vote_delegation::<T>(category, &delegator, delegate);
+        }
+    }
+}
+
+/// Number of records of other accounts [`rotate_key`] rewrites when rotating
+/// the key: the votes delegated to it.
+pub(crate) fn key_references<T: Config>(key: &AccountIdOf<T>) -> u32 {
+    ProposalCategory::ALL
+        .into_iter()
+        .map(|category| crate::VoteDelegators::<T>::iter_key_prefix((category, key)).count() as u32)
+        .fold(0, u32::saturating_add)
+}
@@ -1,6 +1,20 @@
-use polkadot_sdk::frame_support::{dispatch::DispatchResult, ensure};
+use pallet_governance_api::ProposalCategory;
+use polkadot_sdk::{
+    frame_support::{dispatch::DispatchResult, ensure, traits::Get},
+    sp_core::ConstU32,
+    sp_runtime::BoundedVec,
+    sp_std::{collections::btree_set::BTreeSet, vec::Vec},
+};
 
-use crate::{AccountIdOf, Error, Event, Proposals, proposal::ProposalStatus};
+use crate::{
+    AccountIdOf, BalanceOf, Error, Event, Proposals, VoteDelegations, VoteDelegators,
+    proposal::ProposalStatus,
+};
+
+/// Proposal categories a vote delegation call applies to. Empty means every
+/// category.
+pub type ProposalCategories =
+    BoundedVec<ProposalCategory, ConstU32<{ ProposalCategory::ALL.len() as u32 }>>;
 
 /// Casts a vote on behalf of a voter.
 pub fn add_vote<T: crate::Config>(
@@ -26,15 +40,25 @@ pub fn add_vote<T: crate::Config>(
         crate::Error::<T>::AlreadyVoted
     );
 
+    let category = proposal.data.category();
+    let is_delegate = VoteDelegators::<T>::iter_key_prefix((category, &voter))
+        .next()
+        .is_some();
+
     let voter_delegated_stake = pallet_torus0::stake::sum_staked_by::<T>(&voter);
     let voter_owned_stake = pallet_torus0::stake::sum_staking_to::<T>(&voter);
 
     ensure!(
-        voter_delegated_stake > 0 || voter_owned_stake > 0,
+        voter_delegated_stake > 0 || voter_owned_stake > 0 || is_delegate,
         crate::Error::<T>::InsufficientStake
     );
 
-    if !crate::NotDelegatingVotingPower::<T>::get().contains(&voter) && voter_delegated_stake == 0 {
+    // Voting directly overrides an explicit vote delegation.
+    if !crate::NotDelegatingVotingPower::<T>::get().contains(&voter)
+        && !VoteDelegations::<T>::contains_key(category, &voter)
+        && voter_delegated_stake == 0
+        && !is_delegate
+    {
         return Err(Error::<T>::VoterIsDelegatingVotingPower.into());
     }
 
@@ -97,3 +121,156 @@ pub fn disable_delegation<T: crate::Config>(delegator: AccountIdOf<T>) -> Dispat
             .map_err(|_| Error::<T>::InternalError.into())
     })
 }
+
+/// Delegates the voting power of the delegator to `delegate` on proposals of
+/// the given categories, or of every category if none is given. The stake of
+/// the delegator stops counting for the agents it stakes to, and a direct
+/// vote of the delegator overrides the delegation.
+pub fn delegate_votes<T: crate::Config>(
+    delegator: AccountIdOf<T>,
+    delegate: AccountIdOf<T>,
+    categories: ProposalCategories,
+) -> DispatchResult {
+    ensure!(delegator != delegate, Error::<T>::CannotDelegateVotesToSelf);
+    ensure!(
+        pallet_torus0::stake::sum_staking_to::<T>(&delegator) > 0,
+        Error::<T>::InsufficientStake
+    );
+
+    let categories = resolve_categories(categories);
+    for category in &categories {
+        // Bounds the delegators read when tallying the votes of the delegate
+        let delegating =
+            VoteDelegations::<T>::get(*category, &delegator).as_ref() == Some(&delegate);
+        ensure!(
+            delegating
+                || VoteDelegators::<T>::iter_key_prefix((*category, &delegate)).count()
+                    < T::MaxVoteDelegators::get() as usize,
+            Error::<T>::TooManyVoteDelegators
+        );
+    }
+
+    for category in &categories {
+        set_vote_delegation::<T>(*category, &delegator, Some(&delegate));
+    }
+
+    crate::Pallet::<T>::deposit_event(Event::<T>::VotesDelegated {
+        delegator,
+        delegate,
+        categories,
+    });
+
+    Ok(())
+}
+
+/// Removes the explicit vote delegations of the delegator on the given
+/// categories, or on every category if none is given.
+pub fn undelegate_votes<T: crate::Config>(
+    delegator: AccountIdOf<T>,
+    categories: ProposalCategories,
+) -> DispatchResult {
+    let categories: Vec<_> = resolve_categories(categories)
+        .into_iter()
+        .filter(|category| set_vote_delegation::<T>(*category, &delegator, None).is_some())
+        .collect();
+
+    ensure!(!categories.is_empty(), Error::<T>::NotDelegatingVotes);
+
+    crate::Pallet::<T>::deposit_event(Event::<T>::VotesUndelegated {
+        delegator,
+        categories,
+    });
+
+    Ok(())
+}
+
+fn resolve_categories(categories: ProposalCategories) -> Vec<ProposalCategory> {
+    let mut categories = categories.into_inner();
+    if categories.is_empty() {
+        return ProposalCategory::ALL.into();
+    }
+
+    categories.sort();
+    categories.dedup();
+    categories
+}
+
+/// Sets or clears the explicit vote delegation of the delegator on the
+/// category, keeping the [`VoteDelegators`] index in sync. Returns the
+/// previous delegate.
+pub(crate) fn set_vote_delegation<T: crate::Config>(
+    category: ProposalCategory,
+    delegator: &AccountIdOf<T>,
+    delegate: Option<&AccountIdOf<T>>,
+) -> Option<AccountIdOf<T>> {
+    let previous = VoteDelegations::<T>::take(category, delegator);
+    if let Some(previous) = &previous {
+        VoteDelegators::<T>::remove((category, previous, delegator));
+    }
+
+    if let Some(delegate) = delegate {
+        VoteDelegations::<T>::insert(category, delegator, delegate);
+        VoteDelegators::<T>::insert((category, delegate, delegator), ());
+    }
+
+    previous
+}
+
+/// Calculates the stake a voter votes with. This takes into account the
+/// accounts explicitly delegating their votes to the voter, and the stakers
+/// implicitly delegating voting power to it. Explicit delegations of accounts
+/// in `direct_voters` do not apply, as voting directly overrides them.
+pub(crate) fn calc_voting_power<T: crate::Config>(
+    not_delegating: &BTreeSet<AccountIdOf<T>>,
+    category: ProposalCategory,
+    direct_voters: &BTreeSet<AccountIdOf<T>>,
+    voter: &AccountIdOf<T>,
+) -> BalanceOf<T> {
+    let own_stake = if VoteDelegations::<T>::contains_key(category, voter) {
+        if direct_voters.contains(voter) {
+            pallet_torus0::stake::sum_staking_to::<T>(voter)
+        } else {
+            0
+        }
+    } else if not_delegating.contains(voter) {
+        pallet_torus0::stake::sum_staking_to::<T>(voter)
+    } else {
+        0
+    };
+
+    let implicitly_delegated: BalanceOf<T> = pallet_torus0::stake::get_staked_by_vector::<T>(voter)
+        .into_iter()
+        .filter(|(staker, _)| {
+            !not_delegating.contains(staker)
+                && !VoteDelegations::<T>::contains_key(category, staker)
+        })
+        .map(|(_, stake)| stake)
+        .sum();
+
+    let explicitly_delegated: BalanceOf<T> =
+        VoteDelegators::<T>::iter_key_prefix((category, voter))
+            .filter(|delegator| !direct_voters.contains(delegator))
+            .map(|delegator| pallet_torus0::stake::sum_staking_to::<T>(&delegator))
+            .sum();
+
+    own_stake
+        .saturating_add(implicitly_delegated)
+        .saturating_add(explicitly_delegated)
+}
+
+/// Stake the account would vote with on proposals of each category if it
+/// voted now.
+pub fn voting_power<T: crate::Config>(
+    account: &AccountIdOf<T>,
+) -> Vec<(ProposalCategory, BalanceOf<T>)> {
+    let not_delegating = crate::NotDelegatingVotingPower::<T>::get().into_inner();
+    let direct_voters = BTreeSet::from([account.clone()]);
+
+    ProposalCategory::ALL
+        .into_iter()
+        .map(|category| {
+            let power = calc_voting_power::<T>(&not_delegating, category, &direct_voters, account);
+            (category, power)
+        })
+        .collect()
+}
@@ -38,3 +38,84 @@ fn set_emission_params_non_root() {
         );
     });
 }
+
+#[test]
+fn set_bonds_moving_average() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(pallet_governance::Pallet::<Test>::set_bonds_moving_average(
+            RuntimeOrigin::root(),
+            Percent::from_percent(10),
+        ));
+
+        assert_eq!(
+            pallet_emission0::BondsMovingAverage::<Test>::get(),
+            Percent::from_percent(10)
+        );
+
+        assert_err!(
+            pallet_governance::Pallet::<Test>::set_bonds_moving_average(
+                RuntimeOrigin::root(),
+                Percent::zero(),
+            ),
+            pallet_governance::Error::<Test>::InvalidBondsMovingAverage
+        );
+
+        assert_err!(
+            pallet_governance::Pallet::<Test>::set_bonds_moving_average(
+                get_origin(0),
+                Percent::from_percent(15),
+            ),
+            BadOrigin
+        );
+    });
+}
+
+#[test]
+fn set_consensus_config() {
+    new_test_ext().execute_with(|| {
+        let config = pallet_emission0::ConsensusConfiguration {
+            enabled: true,
+            kappa: Percent::from_percent(60),
+            divergence_penalty: Percent::from_percent(50),
+        };
+
+        assert_err!(
+            pallet_governance::Pallet::<Test>::set_consensus_config(get_origin(0), config.clone()),
+            BadOrigin
+        );
+        assert!(!pallet_emission0::ConsensusConfig::<Test>::get().enabled);
+
+        assert_ok!(pallet_governance::Pallet::<Test>::set_consensus_config(
+            RuntimeOrigin::root(),
+            config.clone(),
+        ));
+
+        assert_eq!(pallet_emission0::ConsensusConfig::<Test>::get(), config);
+    });
+}
+
+#[test]
+fn set_weights_commit_reveal() {
+    new_test_ext().execute_with(|| {
+        assert_err!(
+            pallet_governance::Pallet::<Test>::set_weights_commit_reveal(get_origin(0), true),
+            BadOrigin
+        );
+
+        assert_ok!(
+            pallet_governance::Pallet::<Test>::set_weights_commit_reveal(
+                RuntimeOrigin::root(),
+                true,
+            )
+        );
+        assert!(pallet_emission0::WeightsCommitReveal::<Test>::get());
+
+        assert_ok!(
+            pallet_governance::Pallet::<Test>::set_weights_commit_reveal(
+                RuntimeOrigin::root(),
+                false,
+            )
+        );
+        assert!(!pallet_emission0::WeightsCommitReveal::<Test>::get());
+    });
+}
@@ -4,6 +4,7 @@ use pallet_governance::{
     Config, DaoTreasuryAddress, Error, GlobalGovernanceConfig, Proposals,
     config::GovernanceConfiguration,
     proposal::{GlobalParamsData, ProposalStatus},
+    roles::AllocatorTerms,
 };
 use polkadot_sdk::{
     frame_support::{assert_err, assert_ok, traits::Get},
@@ -568,3 +569,100 @@ fn creates_emission_proposal_with_invalid_params_and_it_fails() {
         );
     });
 }
+
+#[test]
+fn allocator_proposals_add_and_remove_allocators() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        config(1, 100);
+
+        add_balance(0, as_tors(3));
+        for agent in [0, 1, 2, 3] {
+            register(agent, 0, agent, 0);
+        }
+
+        let origin = get_origin(0);
+        let metadata = vec![b'0'; 64];
+
+        assert_err!(
+            pallet_governance::Pallet::<Test>::add_allocator_proposal(
+                origin.clone(),
+                1,
+                Percent::from_percent(5),
+                0,
+                metadata.clone()
+            ),
+            Error::<Test>::InvalidAllocatorCapacity
+        );
+        assert_err!(
+            pallet_governance::Pallet::<Test>::add_allocator_proposal(
+                origin.clone(),
+                1,
+                Percent::from_percent(1),
+                1,
+                metadata.clone()
+            ),
+            Error::<Test>::InvalidAllocatorFee
+        );
+        assert_err!(
+            pallet_governance::Pallet::<Test>::remove_allocator_proposal(
+                origin.clone(),
+                1,
+                metadata.clone()
+            ),
+            Error::<Test>::NotAllocator
+        );
+
+        assert_ok!(pallet_governance::Pallet::<Test>::add_allocator_proposal(
+            origin.clone(),
+            1,
+            Percent::from_percent(10),
+            1,
+            metadata.clone()
+        ));
+        vote(0, 0, true);
+
+        step_block(100);
+
+        let terms = AllocatorTerms {
+            fee: Percent::from_percent(10),
+            capacity: 1,
+        };
+        assert!(pallet_governance::Allocators::<Test>::contains_key(1));
+        assert_eq!(
+            pallet_governance::DeclaredAllocatorTerms::<Test>::get(1),
+            Some(terms)
+        );
+
+        assert_ok!(pallet_emission0::Pallet::<Test>::delegate_weight_control(
+            get_origin(2),
+            1
+        ));
+        assert_err!(
+            pallet_emission0::Pallet::<Test>::delegate_weight_control(get_origin(0), 1),
+            pallet_emission0::Error::<Test>::AllocatorAtCapacity
+        );
+
+        assert_ok!(pallet_governance::Pallet::<Test>::add_allocator(
+            RawOrigin::Root.into(),
+            3
+        ));
+
+        assert_ok!(
+            pallet_governance::Pallet::<Test>::remove_allocator_proposal(origin, 1, metadata)
+        );
+        vote(0, 1, true);
+
+        step_block(100);
+
+        assert!(!pallet_governance::Allocators::<Test>::contains_key(1));
+        assert_eq!(
+            pallet_governance::DeclaredAllocatorTerms::<Test>::get(1),
+            None
+        );
+        assert_eq!(
+            pallet_emission0::WeightControlDelegation::<Test>::get(2),
+            Some(pallet_emission0::weight_control::whole_weight_control::<Test>(3))
+        );
+    });
+}
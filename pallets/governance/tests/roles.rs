@@ -1,8 +1,17 @@
 #![allow(unused, clippy::arithmetic_side_effects)]
 
-use pallet_governance::{GlobalGovernanceConfig, Whitelist};
+use pallet_governance::{
+    GlobalGovernanceConfig, Whitelist,
+    roles::{AllocatorAssignmentPolicy, AllocatorTerms, assign_allocator},
+};
+use pallet_governance_api::{CuratorAction, CuratorActionRecord};
 use pallet_permission0::CuratorPermissions;
-use polkadot_sdk::{frame_support::assert_err, sp_runtime::Percent};
+use pallet_permission0_api::Permission0CuratorApi;
+use polkadot_sdk::{
+    frame_support::{assert_err, traits::Get},
+    frame_system::RawOrigin,
+    sp_runtime::{Percent, traits::AccountIdConversion},
+};
 use test_utils::*;
 
 use crate::pallet_governance::Error;
@@ -60,6 +69,124 @@ fn add_and_remove_allocator() {
     });
 }
 
+#[test]
+fn assigns_allocators_by_policy() {
+    new_test_ext().execute_with(|| {
+        for allocator in [10, 11, 12] {
+            pallet_governance::Allocators::<Test>::insert(allocator, ());
+        }
+
+        pallet_emission0::weight_control::set_delegation::<Test>(
+            &0,
+            Some(pallet_emission0::weight_control::whole_weight_control::<Test>(10)),
+        );
+        assert_eq!(assign_allocator::<Test>(&1), Some(11));
+
+        pallet_governance::DeclaredAllocatorTerms::<Test>::insert(
+            11,
+            AllocatorTerms {
+                fee: Percent::from_percent(5),
+                capacity: 1,
+            },
+        );
+        pallet_emission0::weight_control::set_delegation::<Test>(
+            &1,
+            Some(pallet_emission0::weight_control::whole_weight_control::<Test>(11)),
+        );
+
+        // Allocators at capacity are skipped, and never assigned to themselves.
+        assert_eq!(assign_allocator::<Test>(&2), Some(12));
+        assert_eq!(assign_allocator::<Test>(&12), Some(10));
+
+        assert_ok!(
+            pallet_governance::Pallet::<Test>::set_allocator_assignment_policy(
+                RuntimeOrigin::root(),
+                AllocatorAssignmentPolicy::RoundRobin
+            )
+        );
+
+        assert_eq!(assign_allocator::<Test>(&2), Some(10));
+        assert_eq!(assign_allocator::<Test>(&3), Some(12));
+        assert_eq!(assign_allocator::<Test>(&4), Some(10));
+
+        assert_ok!(
+            pallet_governance::Pallet::<Test>::set_allocator_assignment_policy(
+                RuntimeOrigin::root(),
+                AllocatorAssignmentPolicy::AgentChoice
+            )
+        );
+
+        assert_eq!(assign_allocator::<Test>(&2), None);
+    });
+}
+
+#[test]
+fn removed_allocators_are_rebalanced_in_pages() {
+    new_test_ext_with_block(1).execute_with(|| {
+        let (removed, remaining) = (10, 11);
+        for id in [0, 1, 2, removed, remaining] {
+            register_empty_agent(id);
+        }
+        for allocator in [removed, remaining] {
+            pallet_governance::Allocators::<Test>::insert(allocator, ());
+        }
+        pallet_governance::DeclaredAllocatorTerms::<Test>::insert(
+            remaining,
+            AllocatorTerms {
+                fee: Percent::from_percent(5),
+                capacity: 1,
+            },
+        );
+
+        for delegator in [0, 1, 2] {
+            pallet_emission0::weight_control::set_delegation::<Test>(
+                &delegator,
+                Some(pallet_emission0::weight_control::whole_weight_control::<Test>(removed)),
+            );
+        }
+
+        let not_reassigned = |agent: AccountId| {
+            System::events().into_iter().any(|record| {
+                record.event
+                    == RuntimeEvent::Governance(
+                        pallet_governance::Event::WeightControlNotReassigned {
+                            agent,
+                            allocator: removed,
+                        },
+                    )
+            })
+        };
+
+        assert_ok!(pallet_governance::Pallet::<Test>::remove_allocator(
+            RuntimeOrigin::root(),
+            removed
+        ));
+
+        // Only a page of delegators is reassigned right away. The remaining
+        // allocator takes the first, and the second regains weight control.
+        let page = <Test as pallet_governance::Config>::MaxRebalancedDelegations::get();
+        assert_eq!(page, 2);
+        assert_eq!(
+            pallet_emission0::WeightControlDelegation::<Test>::get(0),
+            Some(pallet_emission0::weight_control::whole_weight_control::<Test>(remaining))
+        );
+        assert!(!pallet_emission0::WeightControlDelegation::<Test>::contains_key(1));
+        assert!(not_reassigned(1));
+        assert!(pallet_emission0::WeightControlDelegators::<Test>::contains_key(removed, 2));
+        assert!(pallet_governance::RemovedAllocators::<Test>::contains_key(
+            removed
+        ));
+
+        step_block(1);
+
+        assert!(!pallet_emission0::WeightControlDelegation::<Test>::contains_key(2));
+        assert!(not_reassigned(2));
+        assert!(!pallet_governance::RemovedAllocators::<Test>::contains_key(
+            removed
+        ));
+    });
+}
+
 #[test]
 fn add_and_remove_from_whitelist() {
     new_test_ext().execute_with(|| {
@@ -179,3 +306,91 @@ fn penalize_agent_successfully() {
         );
     });
 }
+
+#[test]
+fn curator_actions_are_logged() {
+    new_test_ext().execute_with(|| {
+        let curator_key = 0;
+        let module_key = 1;
+
+        assert_ok!(pallet_governance::Pallet::<Test>::add_to_whitelist(
+            RawOrigin::Root.into(),
+            module_key
+        ));
+
+        delegate_curator_permission(curator_key, CuratorPermissions::WHITELIST_MANAGE, None);
+        let permission_id = Permission0::get_curator_permission(&curator_key).unwrap();
+
+        step_block(1);
+
+        assert_ok!(pallet_governance::Pallet::<Test>::remove_from_whitelist(
+            get_origin(curator_key),
+            module_key
+        ));
+
+        // Failed actions are not logged.
+        assert_err!(
+            pallet_governance::Pallet::<Test>::remove_from_whitelist(
+                get_origin(curator_key),
+                module_key
+            ),
+            Error::<Test>::NotWhitelisted
+        );
+
+        assert_eq!(
+            pallet_governance::audit::curator_actions::<Test>(None, 10),
+            vec![
+                (
+                    1,
+                    CuratorActionRecord {
+                        curator: curator_key,
+                        permission_id: Some(permission_id),
+                        action: CuratorAction::WhitelistRemoved(module_key),
+                        block: 1,
+                    }
+                ),
+                (
+                    0,
+                    CuratorActionRecord {
+                        curator: PermissionPalletId::get().into_account_truncating(),
+                        permission_id: None,
+                        action: CuratorAction::WhitelistAdded(module_key),
+                        block: 0,
+                    }
+                ),
+            ]
+        );
+    });
+}
+
+#[test]
+fn curator_action_log_is_bounded_and_paged() {
+    new_test_ext().execute_with(|| {
+        let max = <Test as pallet_governance::Config>::MaxCuratorActions::get() as u64;
+
+        for _ in 0..max + 2 {
+            assert_ok!(pallet_governance::Pallet::<Test>::toggle_agent_freezing(
+                RawOrigin::Root.into()
+            ));
+        }
+
+        assert_eq!(
+            pallet_governance::CuratorActionCount::<Test>::get(),
+            max + 2
+        );
+        assert!(!pallet_governance::CuratorActions::<Test>::contains_key(1));
+        assert!(pallet_governance::CuratorActions::<Test>::contains_key(2));
+
+        let indices = |before, count| {
+            pallet_governance::audit::curator_actions::<Test>(before, count)
+                .into_iter()
+                .map(|(index, _)| index)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(indices(None, 3), vec![max + 1, max, max - 1]);
+        assert_eq!(indices(Some(max - 1), 3), vec![max - 2, max - 3, max - 4]);
+        assert_eq!(indices(Some(4), 5), vec![3, 2]);
+        assert_eq!(indices(None, u32::MAX).len() as u64, max);
+    });
+}
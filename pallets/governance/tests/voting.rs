@@ -3,9 +3,10 @@
 use pallet_emission0::PendingEmission;
 use pallet_governance::{
     DaoTreasuryAddress, Error, GlobalGovernanceConfig, Proposals, TreasuryEmissionFee,
+    VoteDelegators,
     proposal::{Proposal, ProposalData, ProposalStatus},
 };
-use pallet_governance_api::GovernanceApi;
+use pallet_governance_api::{GovernanceApi, ProposalCategory};
 use polkadot_sdk::{frame_support::assert_err, frame_system::RawOrigin};
 use polkadot_sdk::{frame_support::assert_ok, sp_runtime::Percent};
 use polkadot_sdk::{frame_support::traits::Get, sp_runtime::BoundedVec};
@@ -368,3 +369,223 @@ fn ensures_module_has_voted() {
         );
     });
 }
+
+#[test]
+fn delegates_votes_by_category() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        config(1, 100);
+
+        const VALIDATOR: u32 = 0;
+        const STAKER: u32 = 1;
+        const DELEGATE: u32 = 2;
+
+        register(VALIDATOR, VALIDATOR, as_tors(10));
+        stake(STAKER, VALIDATOR, as_tors(5));
+
+        assert_err!(
+            pallet_governance::Pallet::<Test>::delegate_votes(
+                get_origin(STAKER),
+                STAKER,
+                BoundedVec::new()
+            ),
+            Error::<Test>::CannotDelegateVotesToSelf
+        );
+
+        assert_ok!(pallet_governance::Pallet::<Test>::delegate_votes(
+            get_origin(STAKER),
+            DELEGATE,
+            BoundedVec::truncate_from(vec![ProposalCategory::GlobalCustom])
+        ));
+        assert!(VoteDelegators::<Test>::contains_key((
+            ProposalCategory::GlobalCustom,
+            DELEGATE,
+            STAKER
+        )));
+
+        let power = |account| {
+            pallet_governance::voting::voting_power::<Test>(&account)
+                .into_iter()
+                .collect::<std::collections::BTreeMap<_, _>>()
+        };
+        assert_eq!(power(DELEGATE)[&ProposalCategory::GlobalCustom], as_tors(5));
+        assert_eq!(power(DELEGATE)[&ProposalCategory::Emission], 0);
+        assert_eq!(
+            power(VALIDATOR)[&ProposalCategory::GlobalCustom],
+            as_tors(10)
+        );
+        assert_eq!(power(VALIDATOR)[&ProposalCategory::Emission], as_tors(15));
+
+        add_balance(VALIDATOR, 2);
+        for _ in 0..2 {
+            assert_ok!(
+                pallet_governance::Pallet::<Test>::add_global_custom_proposal(
+                    get_origin(VALIDATOR),
+                    b"metadata".to_vec()
+                )
+            );
+        }
+
+        for proposal_id in [0, 1] {
+            assert_ok!(pallet_governance::voting::add_vote::<Test>(
+                DELEGATE,
+                proposal_id,
+                true
+            ));
+            assert_ok!(pallet_governance::voting::add_vote::<Test>(
+                VALIDATOR,
+                proposal_id,
+                false
+            ));
+        }
+
+        // Voting directly overrides the delegation.
+        assert_ok!(pallet_governance::voting::add_vote::<Test>(
+            STAKER, 1, false
+        ));
+
+        step_block(100);
+
+        assert_eq!(
+            Proposals::<Test>::get(0).unwrap().status,
+            ProposalStatus::Refused {
+                block: 100,
+                stake_for: as_tors(5),
+                stake_against: as_tors(10),
+            }
+        );
+        assert_eq!(
+            Proposals::<Test>::get(1).unwrap().status,
+            ProposalStatus::Refused {
+                block: 100,
+                stake_for: 0,
+                stake_against: as_tors(15),
+            }
+        );
+
+        assert_ok!(pallet_governance::Pallet::<Test>::undelegate_votes(
+            get_origin(STAKER),
+            BoundedVec::new()
+        ));
+        assert_err!(
+            pallet_governance::Pallet::<Test>::undelegate_votes(
+                get_origin(STAKER),
+                BoundedVec::new()
+            ),
+            Error::<Test>::NotDelegatingVotes
+        );
+        assert_eq!(power(DELEGATE)[&ProposalCategory::GlobalCustom], 0);
+        assert!(
+            VoteDelegators::<Test>::iter_key_prefix((ProposalCategory::GlobalCustom, DELEGATE))
+                .next()
+                .is_none()
+        );
+    });
+}
+
+#[test]
+fn vote_delegation_requires_stake() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+
+        const VALIDATOR: u32 = 0;
+        const STAKER: u32 = 1;
+        const DELEGATE: u32 = 2;
+
+        register(VALIDATOR, VALIDATOR, as_tors(10));
+        add_balance(STAKER, as_tors(5));
+
+        assert_err!(
+            pallet_governance::Pallet::<Test>::delegate_votes(
+                get_origin(STAKER),
+                DELEGATE,
+                BoundedVec::new()
+            ),
+            Error::<Test>::InsufficientStake
+        );
+        assert!(
+            VoteDelegators::<Test>::iter_key_prefix((ProposalCategory::GlobalCustom, DELEGATE))
+                .next()
+                .is_none()
+        );
+
+        stake(STAKER, VALIDATOR, as_tors(5));
+        assert_ok!(pallet_governance::Pallet::<Test>::delegate_votes(
+            get_origin(STAKER),
+            DELEGATE,
+            BoundedVec::new()
+        ));
+    });
+}
+
+#[test]
+fn vote_delegators_are_capped_per_delegate_and_category() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+
+        const VALIDATOR: u32 = 0;
+        const DELEGATE: u32 = 1;
+
+        let max_delegators: u32 = <Test as pallet_governance::Config>::MaxVoteDelegators::get();
+
+        register(VALIDATOR, VALIDATOR, as_tors(10));
+
+        let delegate = |staker: u32, categories: Vec<ProposalCategory>| {
+            pallet_governance::Pallet::<Test>::delegate_votes(
+                get_origin(staker),
+                DELEGATE,
+                BoundedVec::truncate_from(categories),
+            )
+        };
+
+        let stakers: Vec<u32> = (10..10 + max_delegators + 1).collect();
+        for staker in &stakers {
+            stake(*staker, VALIDATOR, as_tors(1));
+        }
+
+        let (last, stakers) = stakers.split_last().unwrap();
+        let first = *stakers.first().unwrap();
+        for staker in stakers {
+            assert_ok!(delegate(*staker, vec![ProposalCategory::GlobalCustom]));
+        }
+
+        assert_err!(
+            delegate(*last, vec![ProposalCategory::GlobalCustom]),
+            Error::<Test>::TooManyVoteDelegators
+        );
+
+        // Delegating on every category fails as a whole.
+        assert_err!(
+            delegate(*last, vec![]),
+            Error::<Test>::TooManyVoteDelegators
+        );
+        assert!(
+            VoteDelegators::<Test>::iter_key_prefix((ProposalCategory::Emission, DELEGATE))
+                .next()
+                .is_none()
+        );
+
+        // The cap applies to each category on its own.
+        assert_ok!(delegate(*last, vec![ProposalCategory::Emission]));
+
+        // Existing delegators can delegate again.
+        assert_ok!(delegate(first, vec![ProposalCategory::GlobalCustom]));
+
+        // The voting power of the delegate is tallied from the capped
+        // delegators only.
+        let power = pallet_governance::voting::voting_power::<Test>(&DELEGATE)
+            .into_iter()
+            .collect::<std::collections::BTreeMap<_, _>>();
+        assert_eq!(
+            power[&ProposalCategory::GlobalCustom],
+            as_tors(max_delegators.into())
+        );
+        assert_eq!(power[&ProposalCategory::Emission], as_tors(1));
+
+        assert_ok!(pallet_governance::Pallet::<Test>::undelegate_votes(
+            get_origin(first),
+            BoundedVec::new()
+        ));
+        assert_ok!(delegate(*last, vec![ProposalCategory::GlobalCustom]));
+    });
+}
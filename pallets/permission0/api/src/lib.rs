@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::multiple_bound_locations)]
 
-use codec::{Decode, Encode};
+use codec::{Codec, Decode, Encode};
 use polkadot_sdk::{
     frame_support::dispatch::DispatchResult,
     sp_core::{H256, blake2_256},
@@ -88,6 +88,37 @@ pub enum EnforcementAuthority<AccountId> {
     },
 }
 
+/// An edge of the stream graph: `delegator` streams `stream_id` to `recipient`
+/// through the given number of permissions
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct StreamEdge<AccountId> {
+    pub stream_id: StreamId,
+    pub delegator: AccountId,
+    pub recipient: AccountId,
+    pub permissions: u32,
+}
+
+/// An enforcement action controllers can vote on
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub enum EnforcementReferendum<AccountId> {
+    EmissionAccumulation(bool),
+    Execution,
+    Pause,
+    Resume,
+    Revoke,
+    SetRecipients(Vec<(AccountId, u16)>),
+}
+
+/// An open enforcement referendum and the controllers that voted for it
+#[derive(Encode, Decode, TypeInfo, Clone, PartialEq, Eq, Debug)]
+pub struct EnforcementReferendumInfo<AccountId> {
+    pub referendum: EnforcementReferendum<AccountId>,
+    pub voters: Vec<AccountId>,
+    pub required_votes: u32,
+    /// Block at which the votes expire
+    pub expires_at: u64,
+}
+
 /// The Permission0 API trait
 pub trait Permission0Api<Origin> {
     /// Check if a permission exists
@@ -142,12 +173,13 @@ bitflags::bitflags! {
 }
 
 pub trait Permission0CuratorApi<AccountId, Origin, BlockNumber> {
-    /// Verifies the recipient's curator permission and returns the registered
-    /// cooldown between actions.
+    /// Verifies the recipient's curator permission, recording the action
+    /// against it. Returns the curator and the permission authorizing the
+    /// action, which is `None` for the root key.
     fn ensure_curator_permission(
         recipient: Origin,
         flags: CuratorPermissions,
-    ) -> Result<AccountId, DispatchError>;
+    ) -> Result<(AccountId, Option<PermissionId>), DispatchError>;
 
     /// Finds the curator permission delegated to [`recipient`].
     fn get_curator_permission(recipient: &AccountId) -> Option<PermissionId>;
@@ -184,13 +216,28 @@ pub trait Permission0WalletApi<AccountId> {
     ) -> impl Iterator<Item = (PermissionId, WalletPermission<AccountId>)>;
 }
 
+pub trait Permission0KeyRotationApi<AccountId> {
+    /// Moves the permissions delegated, received or streamed through by an
+    /// agent key to the key replacing it.
+    fn rotate_key(old_key: &AccountId, new_key: &AccountId) -> DispatchResult;
+}
+
 polkadot_sdk::sp_api::decl_runtime_apis! {
     /// A set of helper functions for permission and streams
     /// queries.
-    pub trait Permission0RuntimeApi<AccountId: Encode> {
+    pub trait Permission0RuntimeApi<AccountId: Codec> {
         /// Generates a root stream ID for the given account.
         /// The root stream ID is assigned by the system when emitting
         /// tokens from the STAKE as rewards.
         fn root_stream_id_for_account(account_id: AccountId) -> StreamId;
+
+        /// Lists which delegators stream to which recipients, for a single
+        /// stream or for all of them.
+        fn stream_graph(stream_id: Option<StreamId>) -> Vec<StreamEdge<AccountId>>;
+
+        /// Lists the open enforcement referenda of a permission.
+        fn enforcement_referenda(
+            permission_id: PermissionId,
+        ) -> Vec<EnforcementReferendumInfo<AccountId>>;
     }
 }
@@ -9,6 +9,140 @@ use polkadot_sdk::{
 
 use crate::*;
 
+/// Registers `n` recipients and delegates a stream permission to each of them,
+/// over a distinct stream per permission.
+fn delegate_stream_permissions<T: Config>(
+    delegator: &T::AccountId,
+    n: u32,
+) -> polkadot_sdk::sp_std::vec::Vec<PermissionId> {
+    use polkadot_sdk::sp_std::vec;
+
+    T::Torus::force_register_agent(delegator, b"delegator".to_vec(), vec![], vec![])
+        .expect("failed to register delegator");
+
+    let amount = 10_000_000u32.into();
+    let _ = <T::Currency>::deposit_creating(delegator, amount);
+
+    (0..n)
+        .map(|i| {
+            let delegation = stream_delegation::<T>(i);
+            ext::stream_impl::delegate_stream_permission_impl::<T>(
+                delegator.clone(),
+                delegation.recipients,
+                delegation.allocation,
+                delegation.distribution,
+                delegation.duration,
+                delegation.revocation,
+                delegation.enforcement,
+                None,
+                None,
+            )
+            .expect("failed to delegate permission")
+        })
+        .collect()
+}
+
+/// Registers the `i`-th recipient and builds a stream delegation to it.
+fn stream_delegation<T: Config>(i: u32) -> StreamDelegation<T> {
+    use polkadot_sdk::sp_std::{collections::btree_map::BTreeMap, vec};
+
+    let recipient: T::AccountId = account("recipient", i, 0);
+    let name = [b"recipient".as_slice(), &[b'a'.saturating_add(i as u8)]].concat();
+    T::Torus::force_register_agent(&recipient, name, vec![], vec![])
+        .expect("failed to register recipient");
+
+    let stream_id: StreamId = [i as u8; 32].into();
+    let streams = BTreeMap::from([(stream_id, Percent::from_percent(30))]);
+
+    StreamDelegation {
+        recipients: BTreeMap::from([(recipient, 100)]).try_into().unwrap(),
+        allocation: StreamAllocation::Streams(streams.try_into().unwrap()),
+        distribution: DistributionControl::Manual,
+        duration: PermissionDuration::Indefinite,
+        revocation: RevocationTerms::RevocableByDelegator,
+        enforcement: EnforcementAuthority::None,
+        recipient_manager: None,
+        weight_setter: None,
+    }
+}
+
+/// Delegates a namespace permission from alice to bob, who delegates `n`
+/// children of it to distinct recipients. Returns the parent permission.
+fn namespace_permission_with_children<T: Config>(n: u32) -> PermissionId {
+    use pallet_torus0_api::NamespacePathInner;
+    use polkadot_sdk::sp_std::{collections::btree_set::BTreeSet, vec};
+
+    let alice: T::AccountId = account("alice", 0, 0);
+    let bob: T::AccountId = account("bob", 1, 0);
+
+    T::Torus::force_register_agent(&alice, b"alice".to_vec(), vec![], vec![])
+        .expect("failed to register alice");
+    T::Torus::force_register_agent(&bob, b"bob".to_vec(), vec![], vec![])
+        .expect("failed to register bob");
+
+    for namespace_bytes in [
+        b"agent.alice.compute".to_vec(),
+        b"agent.alice.compute.gpu".to_vec(),
+    ] {
+        T::Torus::force_register_namespace(&alice, namespace_bytes)
+            .expect("failed to register namespace");
+    }
+
+    let paths = |parent: Option<PermissionId>, path: &[u8]| {
+        let set: BTreeSet<NamespacePathInner> = BTreeSet::from([path.to_vec().try_into().unwrap()]);
+        let mut paths = BoundedBTreeMap::new();
+        paths
+            .try_insert(
+                parent,
+                set.try_into().expect("failed to create bounded set"),
+            )
+            .expect("failed to insert paths");
+        paths
+    };
+
+    let permission_id = ext::namespace_impl::delegate_namespace_permission_impl::<T>(
+        RawOrigin::Signed(alice).into(),
+        bob.clone(),
+        paths(None, b"agent.alice.compute"),
+        PermissionDuration::Indefinite,
+        RevocationTerms::RevocableByDelegator,
+        T::MaxChildrenPerPermission::get().max(1),
+    )
+    .expect("failed to create alice->bob permission");
+
+    for i in 0..n {
+        let child: T::AccountId = account("child", i, 0);
+        let name = [b"child".as_slice(), &[b'a'.saturating_add(i as u8)]].concat();
+        T::Torus::force_register_agent(&child, name, vec![], vec![])
+            .expect("failed to register child");
+
+        ext::namespace_impl::delegate_namespace_permission_impl::<T>(
+            RawOrigin::Signed(bob.clone()).into(),
+            child,
+            paths(Some(permission_id), b"agent.alice.compute.gpu"),
+            PermissionDuration::Indefinite,
+            RevocationTerms::RevocableByDelegator,
+            1,
+        )
+        .expect("failed to create child permission");
+    }
+
+    permission_id
+}
+
+/// Registers the agent receiving permission transfers.
+fn new_recipient<T: Config>() -> T::AccountId {
+    let eve: T::AccountId = account("eve", 4, 0);
+    T::Torus::force_register_agent(
+        &eve,
+        b"eve".to_vec(),
+        Default::default(),
+        Default::default(),
+    )
+    .expect("failed to register eve");
+    eve
+}
+
 #[benchmarks]
 mod benchmarks {
     use polkadot_sdk::{
@@ -285,6 +419,7 @@ mod benchmarks {
             duration,
             revocation,
             1,
+            Default::default(),
         )
     }
 
@@ -492,4 +627,131 @@ mod benchmarks {
             10, // Final instance count
         )
     }
+
+    #[benchmark]
+    fn update_stream_limits() {
+        let delegator: T::AccountId = account("delegator", 0, 0);
+        let permission_id = delegate_stream_permissions::<T>(&delegator, 1)
+            .into_iter()
+            .next()
+            .expect("failed to delegate permission");
+
+        let limits = StreamLimits {
+            lifetime_cap: Some(1_000_000u32.into()),
+            epoch_cap: Some(EpochCap {
+                length: 100u32.into(),
+                amount: 100_000u32.into(),
+            }),
+            overflow: StreamOverflow::Forward([u8::MAX; 32].into()),
+        };
+
+        #[extrinsic_call]
+        update_stream_limits(RawOrigin::Signed(delegator), permission_id, limits)
+    }
+
+    #[benchmark]
+    fn set_stream_payout() {
+        let delegator: T::AccountId = account("delegator", 0, 0);
+        let recipient: T::AccountId = account("recipient", 0, 0);
+        let permission_id = delegate_stream_permissions::<T>(&delegator, 1)
+            .into_iter()
+            .next()
+            .expect("failed to delegate permission");
+
+        // Staking to another agent also checks its registration.
+        let payout = StreamPayout::StakeTo(delegator);
+
+        #[extrinsic_call]
+        set_stream_payout(RawOrigin::Signed(recipient), permission_id, payout)
+    }
+
+    #[benchmark]
+    fn set_permission_transferable() {
+        let alice: T::AccountId = account("alice", 0, 0);
+        let permission_id = namespace_permission_with_children::<T>(0);
+        PendingRecipientTransfers::<T>::insert(permission_id, new_recipient::<T>());
+
+        #[extrinsic_call]
+        set_permission_transferable(
+            RawOrigin::Signed(alice),
+            permission_id,
+            Some(RecipientTransfer::RequiresApproval),
+        )
+    }
+
+    #[benchmark]
+    fn transfer_permission_recipient(n: Linear<0, { T::MaxChildrenPerPermission::get() }>) {
+        let bob: T::AccountId = account("bob", 1, 0);
+        let permission_id = namespace_permission_with_children::<T>(n);
+        let eve = new_recipient::<T>();
+        TransferablePermissions::<T>::insert(permission_id, RecipientTransfer::Allowed);
+
+        #[extrinsic_call]
+        transfer_permission_recipient(RawOrigin::Signed(bob), permission_id, eve)
+    }
+
+    #[benchmark]
+    fn approve_permission_transfer(n: Linear<0, { T::MaxChildrenPerPermission::get() }>) {
+        let alice: T::AccountId = account("alice", 0, 0);
+        let permission_id = namespace_permission_with_children::<T>(n);
+        let eve = new_recipient::<T>();
+        TransferablePermissions::<T>::insert(permission_id, RecipientTransfer::RequiresApproval);
+        PendingRecipientTransfers::<T>::insert(permission_id, &eve);
+
+        #[extrinsic_call]
+        approve_permission_transfer(RawOrigin::Signed(alice), permission_id, eve)
+    }
+
+    #[benchmark]
+    fn bulk_delegate_stream_permission(n: Linear<1, { T::MaxBulkOperationsPerCall::get() }>) {
+        let delegator: T::AccountId = account("delegator", 0, 0);
+
+        T::Torus::force_register_agent(&delegator, b"delegator".to_vec(), vec![], vec![])
+            .expect("failed to register delegator");
+
+        let amount = 10_000_000u32.into();
+        let _ = <T::Currency>::deposit_creating(&delegator, amount);
+
+        let delegations: BoundedVec<_, T::MaxBulkOperationsPerCall> = (0..n)
+            .map(stream_delegation::<T>)
+            .try_collect()
+            .expect("failed to collect delegations");
+
+        #[extrinsic_call]
+        bulk_delegate_stream_permission(RawOrigin::Signed(delegator), delegations)
+    }
+
+    #[benchmark]
+    fn bulk_revoke_permissions(n: Linear<1, { T::MaxBulkOperationsPerCall::get() }>) {
+        let delegator: T::AccountId = account("delegator", 0, 0);
+
+        let permission_ids: BoundedVec<_, T::MaxBulkOperationsPerCall> =
+            delegate_stream_permissions::<T>(&delegator, n)
+                .try_into()
+                .expect("failed to collect permissions");
+
+        #[extrinsic_call]
+        bulk_revoke_permissions(RawOrigin::Signed(delegator), permission_ids)
+    }
+
+    #[benchmark]
+    fn bulk_execute_permissions(n: Linear<1, { T::MaxBulkOperationsPerCall::get() }>) {
+        let delegator: T::AccountId = account("delegator", 0, 0);
+
+        let permission_ids = delegate_stream_permissions::<T>(&delegator, n);
+        for (i, permission_id) in permission_ids.iter().enumerate() {
+            let stream_id: StreamId = [i as u8; 32].into();
+            AccumulatedStreamAmounts::<T>::set(
+                (&delegator, stream_id, permission_id),
+                Some(1_000u32.into()),
+            );
+        }
+
+        let permission_ids: BoundedVec<_, T::MaxBulkOperationsPerCall> = permission_ids
+            .try_into()
+            .expect("failed to collect permissions");
+
+        #[extrinsic_call]
+        bulk_execute_permissions(RawOrigin::Signed(delegator), permission_ids)
+    }
 }
@@ -1,22 +1,27 @@
-use crate::permission::EnforcementReferendum;
+use crate::permission::{BulkOperation, EnforcementReferendum, EnforcementVotes};
 use crate::{
-    Config, EnforcementAuthority, EnforcementTracking, Error, Event, Pallet, PermissionDuration,
-    PermissionId, PermissionScope, Permissions, RevocationTerms, pallet,
+    Config, EnforcementAuthority, EnforcementTracking, Error, Event, Pallet, PausedPermissions,
+    PermissionContract, PermissionDuration, PermissionId, PermissionScope, Permissions,
+    RevocationTerms, pallet,
 };
 use pallet_permission0_api::{
     EnforcementAuthority as ApiEnforcementAuthority, Permission0Api,
     PermissionDuration as ApiPermissionDuration, RevocationTerms as ApiRevocationTerms,
 };
+use pallet_torus0_api::{ProxyOperation, Torus0Api};
 use polkadot_sdk::{
-    frame_support::ensure,
-    frame_system::ensure_signed_or_root,
+    frame_support::{ensure, storage::with_storage_layer, traits::Get},
+    frame_system::{self, RawOrigin, ensure_signed_or_root},
     polkadot_sdk_frame::prelude::{BlockNumberFor, OriginFor},
-    sp_runtime::{DispatchError, DispatchResult},
+    sp_runtime::{DispatchError, DispatchResult, traits::Saturating},
 };
 
 pub mod curator_impl;
+pub mod escrow_impl;
 pub mod namespace_impl;
+pub mod rotation_impl;
 pub mod stream_impl;
+pub mod transfer_impl;
 pub mod wallet_impl;
 
 /// Implementation of the Permission0Api trait to be used externally
@@ -130,7 +135,8 @@ pub(crate) fn execute_permission_impl<T: Config>(
     who: OriginFor<T>,
     permission_id: &PermissionId,
 ) -> DispatchResult {
-    let who = ensure_signed_or_root(who)?;
+    let who = ensure_signed_or_root(who)?
+        .map(|who| <T::Torus>::resolve_proxy(&who, ProxyOperation::ExecutePermissions));
 
     let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
 
@@ -140,6 +146,10 @@ pub(crate) fn execute_permission_impl<T: Config>(
         who.is_none() || who.as_ref() == Some(&delegator),
         Error::<T>::NotPermissionDelegator
     );
+    ensure!(
+        !PausedPermissions::<T>::contains_key(permission_id),
+        Error::<T>::PermissionPaused
+    );
 
     match &contract.scope {
         PermissionScope::Stream(stream_scope) => {
@@ -148,6 +158,7 @@ pub(crate) fn execute_permission_impl<T: Config>(
         PermissionScope::Curator(_) => curator_impl::execute_permission_impl::<T>(permission_id),
         PermissionScope::Namespace(_) => Ok(()),
         PermissionScope::Wallet(_) => Ok(()),
+        PermissionScope::Escrow(_) => Ok(()),
     }
 }
 
@@ -160,46 +171,21 @@ pub fn enforcement_execute_permission_impl<T: Config>(
 
     let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
 
+    ensure!(
+        !PausedPermissions::<T>::contains_key(permission_id),
+        Error::<T>::PermissionPaused
+    );
+
     // If not root, check enforcement authority
-    if let Some(who) = &who {
-        match &contract.enforcement {
-            EnforcementAuthority::None => {
-                return Err(Error::<T>::NotAuthorizedToToggle.into());
-            }
-            EnforcementAuthority::ControlledBy {
-                controllers,
-                required_votes,
-            } => {
-                ensure!(controllers.contains(who), Error::<T>::NotAuthorizedToToggle);
-
-                let referendum = EnforcementReferendum::Execution;
-                let votes = EnforcementTracking::<T>::get(permission_id, &referendum)
-                    .into_iter()
-                    .filter(|id| id != who)
-                    .filter(|id| controllers.contains(id))
-                    .count();
-
-                if votes.saturating_add(1) < *required_votes as usize {
-                    return EnforcementTracking::<T>::mutate(
-                        permission_id,
-                        referendum.clone(),
-                        |votes| {
-                            votes
-                                .try_insert(who.clone())
-                                .map_err(|_| Error::<T>::TooManyControllers)?;
-
-                            <Pallet<T>>::deposit_event(Event::EnforcementVoteCast {
-                                permission_id,
-                                voter: who.clone(),
-                                referendum,
-                            });
-
-                            Ok(())
-                        },
-                    );
-                }
-            }
-        }
+    if let Some(who) = &who
+        && !vote_enforcement_referendum(
+            permission_id,
+            &contract,
+            who,
+            EnforcementReferendum::Execution,
+        )?
+    {
+        return Ok(());
     }
 
     match &contract.scope {
@@ -211,9 +197,10 @@ pub fn enforcement_execute_permission_impl<T: Config>(
         }
         PermissionScope::Namespace(_) => return Ok(()),
         PermissionScope::Wallet(_) => return Ok(()),
+        PermissionScope::Escrow(_) => return Ok(()),
     }
 
-    EnforcementTracking::<T>::remove(permission_id, EnforcementReferendum::Execution);
+    EnforcementTracking::<T>::remove(permission_id, EnforcementReferendum::<T>::Execution);
 
     <Pallet<T>>::deposit_event(Event::PermissionEnforcementExecuted {
         permission_id,
@@ -222,3 +209,168 @@ pub fn enforcement_execute_permission_impl<T: Config>(
 
     Ok(())
 }
+
+/// Records the vote of a controller on an enforcement referendum, returning
+/// whether the referendum reached the required votes.
+///
+/// Votes expire after `EnforcementVotingPeriod` blocks, after which the
+/// referendum starts over with the next vote.
+pub(crate) fn vote_enforcement_referendum<T: Config>(
+    permission_id: PermissionId,
+    contract: &PermissionContract<T>,
+    who: &T::AccountId,
+    referendum: EnforcementReferendum<T>,
+) -> Result<bool, DispatchError> {
+    let EnforcementAuthority::ControlledBy {
+        controllers,
+        required_votes,
+    } = &contract.enforcement
+    else {
+        return Err(Error::<T>::NotAuthorizedToToggle.into());
+    };
+
+    ensure!(controllers.contains(who), Error::<T>::NotAuthorizedToToggle);
+
+    let current_block = frame_system::Pallet::<T>::block_number();
+    let mut votes = EnforcementTracking::<T>::get(permission_id, &referendum);
+    if votes.is_expired(current_block) {
+        votes = EnforcementVotes {
+            voters: Default::default(),
+            expires_at: current_block.saturating_add(T::EnforcementVotingPeriod::get()),
+        };
+    }
+
+    let count = votes
+        .voters
+        .iter()
+        .filter(|id| *id != who)
+        .filter(|id| controllers.contains(id))
+        .count()
+        .saturating_add(1);
+
+    if count < *required_votes as usize {
+        votes
+            .voters
+            .try_insert(who.clone())
+            .map_err(|_| Error::<T>::TooManyControllers)?;
+        EnforcementTracking::<T>::insert(permission_id, &referendum, votes);
+
+        <Pallet<T>>::deposit_event(Event::EnforcementVoteCast {
+            permission_id,
+            voter: who.clone(),
+            referendum,
+        });
+
+        return Ok(false);
+    }
+
+    EnforcementTracking::<T>::remove(permission_id, &referendum);
+
+    <Pallet<T>>::deposit_event(Event::EnforcementReferendumRatified {
+        permission_id,
+        referendum,
+        votes: count as u32,
+    });
+
+    Ok(true)
+}
+
+/// Vote on an enforcement referendum, applying it once ratified. Root applies
+/// referenda right away.
+pub(crate) fn enforcement_referendum_impl<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: PermissionId,
+    referendum: EnforcementReferendum<T>,
+) -> DispatchResult {
+    match referendum {
+        EnforcementReferendum::EmissionAccumulation(accumulating) => {
+            return stream_impl::toggle_permission_accumulation_impl::<T>(
+                origin,
+                permission_id,
+                accumulating,
+            );
+        }
+        EnforcementReferendum::Execution => {
+            return enforcement_execute_permission_impl::<T>(origin, permission_id);
+        }
+        _ => {}
+    }
+
+    let who = ensure_signed_or_root(origin)?;
+
+    let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
+
+    match &referendum {
+        EnforcementReferendum::Pause => ensure!(
+            !PausedPermissions::<T>::contains_key(permission_id),
+            Error::<T>::PermissionPaused
+        ),
+        EnforcementReferendum::Resume => ensure!(
+            PausedPermissions::<T>::contains_key(permission_id),
+            Error::<T>::PermissionNotPaused
+        ),
+        EnforcementReferendum::SetRecipients(_) => ensure!(
+            matches!(contract.scope, PermissionScope::Stream(_)),
+            Error::<T>::UnsupportedPermissionType
+        ),
+        _ => {}
+    }
+
+    if let Some(who) = &who
+        && !vote_enforcement_referendum(permission_id, &contract, who, referendum.clone())?
+    {
+        return Ok(());
+    }
+
+    match referendum {
+        EnforcementReferendum::Pause => {
+            PausedPermissions::<T>::insert(
+                permission_id,
+                frame_system::Pallet::<T>::block_number(),
+            );
+            <Pallet<T>>::deposit_event(Event::PermissionPaused { permission_id });
+        }
+        EnforcementReferendum::Resume => {
+            PausedPermissions::<T>::remove(permission_id);
+            <Pallet<T>>::deposit_event(Event::PermissionResumed { permission_id });
+        }
+        EnforcementReferendum::Revoke => {
+            contract.revoke(RawOrigin::Root.into(), permission_id)?;
+        }
+        EnforcementReferendum::SetRecipients(recipients) => {
+            stream_impl::enforce_stream_recipients::<T>(permission_id, contract, recipients)?;
+        }
+        EnforcementReferendum::EmissionAccumulation(_) | EnforcementReferendum::Execution => {}
+    }
+
+    Ok(())
+}
+
+/// Applies `apply` to every item of a bulk call, emitting an event for each of
+/// them. Items are applied in a storage layer, so a failing item reverts the
+/// whole call.
+pub(crate) fn bulk_operation_impl<T: Config, I>(
+    operation: BulkOperation,
+    items: impl IntoIterator<Item = I>,
+    mut apply: impl FnMut(I) -> Result<PermissionId, DispatchError>,
+) -> DispatchResult {
+    with_storage_layer(|| {
+        let mut count = 0u32;
+
+        for item in items {
+            let permission_id = apply(item)?;
+
+            <Pallet<T>>::deposit_event(Event::BulkItemApplied {
+                operation,
+                index: count,
+                permission_id,
+            });
+
+            count = count.saturating_add(1);
+        }
+
+        <Pallet<T>>::deposit_event(Event::BulkOperationCompleted { operation, count });
+
+        Ok(())
+    })
+}
@@ -1,7 +1,8 @@
 use crate::{
     Config, CuratorPermissions, CuratorScope, Error, Event, Pallet, PermissionContract,
     PermissionDuration, PermissionScope, Permissions, PermissionsByRecipient, RevocationTerms,
-    generate_permission_id, pallet, permission::add_permission_indices,
+    generate_permission_id, pallet,
+    permission::{add_permission_indices, curator::CuratorQuotas},
 };
 
 use pallet_permission0_api::{
@@ -10,7 +11,7 @@ use pallet_permission0_api::{
 use polkadot_sdk::frame_system::ensure_signed_or_root;
 use polkadot_sdk::sp_core::Get;
 use polkadot_sdk::sp_runtime::BoundedBTreeMap;
-use polkadot_sdk::sp_runtime::traits::{AccountIdConversion, Saturating};
+use polkadot_sdk::sp_runtime::traits::{AccountIdConversion, Saturating, Zero};
 use polkadot_sdk::{
     frame_support::ensure,
     frame_system,
@@ -24,9 +25,9 @@ impl<T: Config> Permission0CuratorApi<T::AccountId, OriginFor<T>, BlockNumberFor
     fn ensure_curator_permission(
         recipient: OriginFor<T>,
         flags: ApiCuratorPermissions,
-    ) -> Result<T::AccountId, DispatchError> {
+    ) -> Result<(T::AccountId, Option<PermissionId>), DispatchError> {
         let Some(recipient) = ensure_signed_or_root(recipient)? else {
-            return Ok(T::PalletId::get().into_account_truncating());
+            return Ok((T::PalletId::get().into_account_truncating(), None));
         };
 
         let flags = CuratorPermissions::from_bits_truncate(flags.bits());
@@ -63,7 +64,20 @@ impl<T: Config> Permission0CuratorApi<T::AccountId, OriginFor<T>, BlockNumberFor
                 continue;
             }
 
-            return Ok(recipient);
+            if let Err(err) = ensure_quota_available(scope, flags, now) {
+                cur_error = err;
+                continue;
+            }
+
+            use_curator_permission(permission_id, contract, flags, now);
+
+            <Pallet<T>>::deposit_event(Event::CuratorPermissionUsed {
+                permission_id,
+                recipient: recipient.clone(),
+                flags,
+            });
+
+            return Ok((recipient, Some(permission_id)));
         }
 
         Err(cur_error.into())
@@ -103,11 +117,95 @@ impl<T: Config> Permission0CuratorApi<T::AccountId, OriginFor<T>, BlockNumberFor
             PermissionDuration::<T>::Indefinite,
             RevocationTerms::<T>::Irrevocable,
             1,
+            Default::default(),
         )
         .unwrap();
     }
 }
 
+/// Ensures the scope, and every curator permission the action was delegated
+/// through, have quota left for the actions.
+fn ensure_quota_available<T: Config>(
+    scope: &CuratorScope<T>,
+    flags: CuratorPermissions,
+    now: BlockNumberFor<T>,
+) -> Result<(), Error<T>> {
+    for action in flags.iter() {
+        ensure!(
+            scope.remaining_quota(action, now) != Some(0),
+            Error::<T>::CuratorQuotaExceeded
+        );
+
+        let mut parent = scope.parent_of(action);
+        while let Some(parent_id) = parent {
+            let Some(PermissionScope::Curator(parent_scope)) =
+                Permissions::<T>::get(parent_id).map(|contract| contract.scope)
+            else {
+                break;
+            };
+
+            ensure!(
+                parent_scope.remaining_quota(action, now) != Some(0),
+                Error::<T>::CuratorQuotaExceeded
+            );
+
+            parent = parent_scope.parent_of(action);
+        }
+    }
+
+    Ok(())
+}
+
+/// Number of times the curator children delegated the action by the parent
+/// can still perform it.
+fn reserved_quota<T: Config>(
+    parent_id: &PermissionId,
+    parent: &CuratorScope<T>,
+    action: CuratorPermissions,
+    now: BlockNumberFor<T>,
+) -> u32 {
+    parent
+        .children
+        .iter()
+        .filter_map(|child_id| match Permissions::<T>::get(child_id)?.scope {
+            PermissionScope::Curator(child) if child.parent_of(action) == Some(*parent_id) => {
+                child.remaining_quota(action, now)
+            }
+            _ => None,
+        })
+        .fold(0, u32::saturating_add)
+}
+
+/// Records the actions against the permission quotas and the quotas of the
+/// permissions it was delegated from.
+fn use_curator_permission<T: Config>(
+    permission_id: PermissionId,
+    mut contract: PermissionContract<T>,
+    flags: CuratorPermissions,
+    now: BlockNumberFor<T>,
+) {
+    let mut parents = polkadot_sdk::sp_std::vec::Vec::new();
+    if let PermissionScope::Curator(scope) = &mut contract.scope {
+        for action in flags.iter() {
+            scope.consume_quota(action, now);
+            parents.push((action, scope.parent_of(action)));
+        }
+    }
+
+    Permissions::<T>::insert(permission_id, &contract);
+
+    for (action, mut parent) in parents {
+        while let Some(parent_id) = parent.take() {
+            Permissions::<T>::mutate_extant(parent_id, |contract| {
+                if let PermissionScope::Curator(scope) = &mut contract.scope {
+                    scope.consume_quota(action, now);
+                    parent = scope.parent_of(action);
+                }
+            });
+        }
+    }
+}
+
 pub fn delegate_curator_permission_impl<T: Config>(
     delegator: OriginFor<T>,
     recipient: T::AccountId,
@@ -120,6 +218,7 @@ pub fn delegate_curator_permission_impl<T: Config>(
     duration: PermissionDuration<T>,
     revocation: RevocationTerms<T>,
     instances: u32,
+    quotas: CuratorQuotas<T>,
 ) -> Result<PermissionId, DispatchError> {
     let signer = ensure_signed_or_root(delegator)?;
 
@@ -127,6 +226,17 @@ pub fn delegate_curator_permission_impl<T: Config>(
     let delegator =
         signer.unwrap_or_else(|| <T as Config>::PalletId::get().into_account_truncating());
 
+    let now = <frame_system::Pallet<T>>::block_number();
+
+    for (action, quota) in &quotas {
+        ensure!(
+            !action.contains(CuratorPermissions::ROOT)
+                && quota.max_actions > 0
+                && quota.period > Zero::zero(),
+            Error::<T>::InvalidCuratorQuota
+        );
+    }
+
     let mut parents = polkadot_sdk::sp_std::vec::Vec::with_capacity(flags.len());
 
     for (parent_pid, flags) in &mut flags {
@@ -164,6 +274,26 @@ pub fn delegate_curator_permission_impl<T: Config>(
                 Error::<T>::RevocationTermsTooStrong
             );
 
+            // Actions limited by the parent must be limited to at most what
+            // is left of its quota, over periods at least as long. What the
+            // other children can still use is reserved, so siblings cannot
+            // together be granted more than the parent has.
+            for action in flags.iter() {
+                let (Some(remaining), Some(parent_quota)) = (
+                    scope.remaining_quota(action, now),
+                    scope.quotas.get(&action),
+                ) else {
+                    continue;
+                };
+
+                let available =
+                    remaining.saturating_sub(reserved_quota::<T>(parent_pid, scope, action, now));
+                let within_parent = quotas.get(&action).is_some_and(|quota| {
+                    quota.max_actions <= available && quota.period >= parent_quota.period
+                });
+                ensure!(within_parent, Error::<T>::CuratorQuotaExceedsParent);
+            }
+
             parents.push(*parent_pid);
         } else {
             // We do not check for the ROOT curator permission at the moment.
@@ -175,12 +305,21 @@ pub fn delegate_curator_permission_impl<T: Config>(
         }
     }
 
+    ensure!(
+        quotas
+            .keys()
+            .all(|action| flags.values().any(|flags| flags.contains(*action))),
+        Error::<T>::InvalidCuratorQuota
+    );
+
     let scope = PermissionScope::Curator(CuratorScope {
         recipient: recipient.clone(),
         flags,
         cooldown,
         children: Default::default(),
         max_instances: 1,
+        quotas,
+        quota_usage: Default::default(),
     });
     let permission_id = generate_permission_id::<T>(&delegator, &scope)?;
 
@@ -0,0 +1,145 @@
+use polkadot_sdk::{
+    frame_support::{dispatch::DispatchResult, ensure, traits::ReservableCurrency},
+    frame_system::ensure_signed,
+    polkadot_sdk_frame::prelude::{BlockNumberFor, OriginFor},
+    sp_runtime::{BoundedBTreeSet, BoundedVec, traits::Zero},
+};
+
+use pallet_torus0_api::Torus0Api;
+
+use crate::{
+    BalanceOf, Config, EnforcementAuthority, Error, EscrowApprovals, Event, Pallet,
+    PausedPermissions, PermissionContract, PermissionDuration, PermissionScope, Permissions,
+    RevocationTerms, generate_permission_id,
+    permission::{
+        add_permission_indices,
+        escrow::{EscrowScope, release_milestone},
+    },
+};
+
+/// Delegate a permission escrowing the sum of the milestones
+pub(crate) fn delegate_escrow_permission_impl<T: Config>(
+    origin: OriginFor<T>,
+    recipient: T::AccountId,
+    milestones: BoundedVec<BalanceOf<T>, T::MaxEscrowMilestones>,
+    arbiters: BoundedBTreeSet<T::AccountId, T::MaxRevokersPerPermission>,
+    required_votes: u32,
+    dispute_timeout: BlockNumberFor<T>,
+    duration: PermissionDuration<T>,
+    revocation: RevocationTerms<T>,
+) -> DispatchResult {
+    let delegator = ensure_signed(origin)?;
+    ensure!(delegator != recipient, Error::<T>::SelfPermissionNotAllowed);
+    ensure!(
+        T::Torus::is_agent_registered(&recipient),
+        Error::<T>::NotRegisteredAgent
+    );
+
+    ensure!(
+        !milestones.is_empty() && milestones.iter().all(|amount| !amount.is_zero()),
+        Error::<T>::InvalidEscrowTerms
+    );
+    ensure!(!dispute_timeout.is_zero(), Error::<T>::InvalidEscrowTerms);
+
+    // Without arbiters, only the delegator approves milestones
+    if arbiters.is_empty() {
+        ensure!(required_votes == 0, Error::<T>::InvalidEscrowTerms);
+    } else {
+        ensure!(
+            required_votes > 0 && required_votes as usize <= arbiters.len(),
+            Error::<T>::InvalidEscrowTerms
+        );
+    }
+
+    let scope = EscrowScope {
+        recipient: recipient.clone(),
+        milestones,
+        released: 0,
+        arbiters,
+        required_votes,
+        dispute_timeout,
+    };
+
+    let amount = scope.unreleased_amount();
+    ensure!(
+        T::Currency::can_reserve(&delegator, amount),
+        Error::<T>::InsufficientBalance
+    );
+
+    let scope = PermissionScope::Escrow(scope);
+    let permission_id = generate_permission_id::<T>(&delegator, &scope)?;
+
+    let contract = PermissionContract::<T>::new(
+        delegator.clone(),
+        scope,
+        duration,
+        revocation,
+        EnforcementAuthority::None,
+    );
+
+    Permissions::<T>::insert(permission_id, contract);
+    add_permission_indices::<T>(&delegator, core::iter::once(&recipient), permission_id)?;
+
+    T::Currency::reserve(&delegator, amount)?;
+
+    <Pallet<T>>::deposit_event(Event::PermissionDelegated {
+        delegator,
+        permission_id,
+    });
+
+    Ok(())
+}
+
+/// Approve the release of the next escrow milestone. The delegator releases it
+/// right away, arbiters vote until the required number of approvals is met.
+pub(crate) fn approve_escrow_milestone_impl<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: crate::PermissionId,
+) -> DispatchResult {
+    let who = ensure_signed(origin)?;
+
+    let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
+    let PermissionScope::Escrow(escrow) = &contract.scope else {
+        return Err(Error::<T>::UnsupportedPermissionType.into());
+    };
+
+    ensure!(
+        !PausedPermissions::<T>::contains_key(permission_id),
+        Error::<T>::PermissionPaused
+    );
+
+    let Some((milestone, _)) = escrow.next_milestone() else {
+        return Err(Error::<T>::InvalidEscrowTerms.into());
+    };
+
+    if who != contract.delegator {
+        ensure!(
+            escrow.arbiters.contains(&who),
+            Error::<T>::NotAuthorizedToApprove
+        );
+
+        let votes = EscrowApprovals::<T>::get(permission_id)
+            .into_iter()
+            .filter(|id| id != &who)
+            .filter(|id| escrow.arbiters.contains(id))
+            .count();
+
+        if votes.saturating_add(1) < escrow.required_votes as usize {
+            EscrowApprovals::<T>::try_mutate(permission_id, |votes| {
+                votes
+                    .try_insert(who.clone())
+                    .map_err(|_| Error::<T>::TooManyRevokers)
+            })?;
+
+            <Pallet<T>>::deposit_event(Event::EscrowMilestoneApproved {
+                permission_id,
+                milestone,
+                approver: who,
+            });
+
+            return Ok(());
+        }
+    }
+
+    release_milestone::<T>(permission_id, contract)
+}
@@ -0,0 +1,252 @@
+use pallet_permission0_api::Permission0KeyRotationApi;
+use polkadot_sdk::{
+    frame_support::{
+        dispatch::DispatchResult,
+        ensure,
+        traits::{BalanceStatus, ReservableCurrency},
+    },
+    frame_system,
+    sp_core::Get,
+    sp_runtime::{BoundedBTreeMap, BoundedBTreeSet, traits::Zero},
+    sp_std::{collections::btree_set::BTreeSet, vec, vec::Vec},
+};
+
+use crate::{
+    AccumulatedStreamAmounts, BalanceOf, Config, EnforcementAuthority, Error, EscrowApprovals,
+    Pallet, PendingRecipientTransfers, PermissionContract, PermissionId, PermissionScope,
+    Permissions, PermissionsByDelegator, PermissionsByParticipants, PermissionsByRecipient,
+    RevocationTerms, RevocationTracking, StreamAllocation, StreamGraph, StreamId, StreamPayout,
+    SubscriptionFunding, generate_root_stream_id,
+    permission::{
+        CuratorScope, EscrowScope, NamespaceScope, StreamScope, add_permission_indices,
+        remove_permission_from_indices,
+        stream::{add_stream_edges, remove_stream_edges},
+        wallet::WalletScope,
+    },
+};
+
+impl<T: Config> Permission0KeyRotationApi<T::AccountId> for Pallet<T> {
+    fn rotate_key(old_key: &T::AccountId, new_key: &T::AccountId) -> DispatchResult {
+        rotate_key_impl::<T>(old_key, new_key)
+    }
+}
+
+/// Moves every permission delegated or received by `old_key`, or streaming
+/// its root stream, over to `new_key`. Indices, stream graph edges and
+/// accumulated amounts are rebuilt under the new key and its root stream, so
+/// emissions keep flowing through the same permissions. Funds reserved by
+/// the old key for its permissions are reserved on the new key.
+pub(crate) fn rotate_key_impl<T: Config>(
+    old_key: &T::AccountId,
+    new_key: &T::AccountId,
+) -> DispatchResult {
+    let old_stream = generate_root_stream_id(old_key);
+    let new_stream = generate_root_stream_id(new_key);
+
+    let mut permission_ids: BTreeSet<PermissionId> = PermissionsByDelegator::<T>::get(old_key)
+        .into_iter()
+        .collect();
+    permission_ids.extend(PermissionsByRecipient::<T>::get(old_key));
+    for participants in StreamGraph::<T>::iter_key_prefix((old_stream,)) {
+        permission_ids.extend(PermissionsByParticipants::<T>::get(participants));
+    }
+
+    let rotate = |key: &mut T::AccountId| {
+        if key == old_key {
+            *key = new_key.clone();
+        }
+    };
+
+    for permission_id in permission_ids {
+        let Some(mut contract) = Permissions::<T>::get(permission_id) else {
+            continue;
+        };
+
+        let streams = scope_streams::<T>(&contract.scope);
+        let recipients = scope_recipients::<T>(&contract.scope);
+
+        remove_permission_from_indices::<T>(&contract.delegator, recipients.iter(), permission_id);
+        remove_stream_edges::<T>(&contract.delegator, streams.iter(), recipients.iter());
+
+        let accumulated: Vec<_> = streams
+            .iter()
+            .filter_map(|stream| {
+                AccumulatedStreamAmounts::<T>::take((&contract.delegator, stream, permission_id))
+                    .map(|amount| (*stream, amount))
+            })
+            .collect();
+
+        if &contract.delegator == old_key {
+            let reserved = reserved_amount::<T>(&contract);
+            let missing = T::Currency::repatriate_reserved(
+                old_key,
+                new_key,
+                reserved,
+                BalanceStatus::Reserved,
+            )?;
+            ensure!(missing.is_zero(), Error::<T>::InsufficientBalance);
+        }
+
+        rotate(&mut contract.delegator);
+        rotate_scope::<T>(
+            &mut contract.scope,
+            old_key,
+            new_key,
+            old_stream,
+            new_stream,
+        );
+
+        if let RevocationTerms::RevocableByArbiters { accounts, .. } = &mut contract.revocation {
+            accounts.iter_mut().for_each(rotate);
+        }
+
+        if let EnforcementAuthority::ControlledBy { controllers, .. } = &mut contract.enforcement {
+            controllers.iter_mut().for_each(rotate);
+        }
+
+        let streams = scope_streams::<T>(&contract.scope);
+        let recipients = scope_recipients::<T>(&contract.scope);
+
+        add_permission_indices::<T>(&contract.delegator, recipients.iter(), permission_id)?;
+        add_stream_edges::<T>(&contract.delegator, streams.iter(), recipients.iter());
+
+        for (stream, amount) in accumulated {
+            let stream = if stream == old_stream {
+                new_stream
+            } else {
+                stream
+            };
+            AccumulatedStreamAmounts::<T>::insert(
+                (&contract.delegator, stream, permission_id),
+                amount,
+            );
+        }
+
+        RevocationTracking::<T>::mutate_exists(permission_id, |voters| {
+            if let Some(voters) = voters {
+                rotate_set::<T, _>(voters, old_key, new_key);
+            }
+        });
+        EscrowApprovals::<T>::mutate_exists(permission_id, |approvals| {
+            if let Some(approvals) = approvals {
+                rotate_set::<T, _>(approvals, old_key, new_key);
+            }
+        });
+        PendingRecipientTransfers::<T>::mutate_exists(permission_id, |pending| {
+            if let Some(pending) = pending {
+                rotate(pending);
+            }
+        });
+
+        contract.last_update = frame_system::Pallet::<T>::block_number();
+        Permissions::<T>::insert(permission_id, contract);
+    }
+
+    Ok(())
+}
+
+fn rotate_scope<T: Config>(
+    scope: &mut PermissionScope<T>,
+    old_key: &T::AccountId,
+    new_key: &T::AccountId,
+    old_stream: StreamId,
+    new_stream: StreamId,
+) {
+    let rotate = |key: &mut T::AccountId| {
+        if key == old_key {
+            *key = new_key.clone();
+        }
+    };
+
+    match scope {
+        PermissionScope::Stream(scope) => {
+            rotate_map_key::<T, _, _>(&mut scope.recipients, old_key, new_key);
+            rotate_map_key::<T, _, _>(&mut scope.recipient_payouts, old_key, new_key);
+            rotate_set::<T, _>(&mut scope.recipient_managers, old_key, new_key);
+            rotate_set::<T, _>(&mut scope.weight_setters, old_key, new_key);
+
+            for (_, payout) in scope.recipient_payouts.iter_mut() {
+                if let StreamPayout::StakeTo(agent) = payout {
+                    rotate(agent);
+                }
+            }
+
+            if let StreamAllocation::Streams(streams) = &mut scope.allocation
+                && let Some(percentage) = streams.remove(&old_stream)
+            {
+                let _ = streams.try_insert(new_stream, percentage);
+            }
+        }
+        PermissionScope::Curator(CuratorScope { recipient, .. })
+        | PermissionScope::Namespace(NamespaceScope { recipient, .. })
+        | PermissionScope::Wallet(WalletScope { recipient, .. }) => rotate(recipient),
+        PermissionScope::Escrow(EscrowScope {
+            recipient,
+            arbiters,
+            ..
+        }) => {
+            rotate(recipient);
+            rotate_set::<T, _>(arbiters, old_key, new_key);
+        }
+    }
+}
+
+/// Funds the delegator keeps reserved for the permission, released on
+/// cleanup.
+fn reserved_amount<T: Config>(contract: &PermissionContract<T>) -> BalanceOf<T> {
+    match &contract.scope {
+        PermissionScope::Stream(StreamScope { allocation, .. }) => match allocation {
+            StreamAllocation::FixedAmount(amount) if contract.last_execution().is_none() => *amount,
+            StreamAllocation::Subscription(terms)
+                if terms.funding == SubscriptionFunding::Reserved =>
+            {
+                terms.remaining_amount(contract.execution_count())
+            }
+            _ => Zero::zero(),
+        },
+        PermissionScope::Escrow(escrow) => escrow.unreleased_amount(),
+        _ => Zero::zero(),
+    }
+}
+
+fn rotate_set<T: Config, S: Get<u32>>(
+    set: &mut BoundedBTreeSet<T::AccountId, S>,
+    old_key: &T::AccountId,
+    new_key: &T::AccountId,
+) {
+    if set.remove(old_key) {
+        let _ = set.try_insert(new_key.clone());
+    }
+}
+
+fn rotate_map_key<T: Config, V, S: Get<u32>>(
+    map: &mut BoundedBTreeMap<T::AccountId, V, S>,
+    old_key: &T::AccountId,
+    new_key: &T::AccountId,
+) {
+    if let Some(value) = map.remove(old_key) {
+        let _ = map.try_insert(new_key.clone(), value);
+    }
+}
+
+fn scope_recipients<T: Config>(scope: &PermissionScope<T>) -> Vec<T::AccountId> {
+    match scope {
+        PermissionScope::Stream(StreamScope { recipients, .. }) => {
+            recipients.keys().cloned().collect()
+        }
+        PermissionScope::Curator(CuratorScope { recipient, .. })
+        | PermissionScope::Namespace(NamespaceScope { recipient, .. })
+        | PermissionScope::Wallet(WalletScope { recipient, .. })
+        | PermissionScope::Escrow(EscrowScope { recipient, .. }) => vec![recipient.clone()],
+    }
+}
+
+fn scope_streams<T: Config>(scope: &PermissionScope<T>) -> Vec<StreamId> {
+    match scope {
+        PermissionScope::Stream(StreamScope {
+            allocation: StreamAllocation::Streams(streams),
+            ..
+        }) => streams.keys().copied().collect(),
+        _ => Vec::new(),
+    }
+}
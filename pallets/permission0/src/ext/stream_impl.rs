@@ -1,8 +1,8 @@
 use crate::{
     AccumulatedStreamAmounts, BalanceOf, Config, DistributionControl, EnforcementTracking, Error,
     Event, NegativeImbalanceOf, Pallet, PermissionContract, PermissionDuration, PermissionId,
-    PermissionScope, Permissions, StreamAllocation, StreamScope, generate_permission_id,
-    get_total_allocated_percentage, pallet,
+    PermissionScope, Permissions, StreamAllocation, StreamLimits, StreamOverflow, StreamScope,
+    SubscriptionFunding, generate_permission_id, get_total_allocated_percentage, pallet,
     permission::{stream::*, *},
 };
 
@@ -134,6 +134,7 @@ pub(crate) fn delegate_stream_permission_impl<T: Config>(
     match &allocation {
         StreamAllocation::Streams(streams) => {
             validate_stream_permission_streams::<T>(streams, &delegator)?;
+            ensure_valid_stream_graph::<T>(&delegator, streams.keys(), recipients.keys())?;
         }
         StreamAllocation::FixedAmount(amount) => {
             ensure!(*amount > BalanceOf::<T>::zero(), Error::<T>::InvalidAmount);
@@ -141,17 +142,23 @@ pub(crate) fn delegate_stream_permission_impl<T: Config>(
                 T::Currency::can_reserve(&delegator, *amount),
                 Error::<T>::InsufficientBalance
             );
+        }
+        StreamAllocation::Subscription(terms) => {
             ensure!(
-                matches!(
-                    &distribution,
-                    DistributionControl::Manual | DistributionControl::AtBlock(_)
-                ),
-                Error::<T>::FixedAmountCanOnlyBeTriggeredOnce
+                !terms.amount.is_zero() && terms.max_payments > 0,
+                Error::<T>::InvalidSubscriptionTerms
             );
+
+            if terms.funding == SubscriptionFunding::Reserved {
+                ensure!(
+                    T::Currency::can_reserve(&delegator, terms.remaining_amount(0)),
+                    Error::<T>::InsufficientBalance
+                );
+            }
         }
     }
 
-    validate_stream_permission_distribution::<T>(&distribution)?;
+    validate_stream_permission_distribution::<T>(&allocation, &distribution)?;
 
     let recipients_ids: Vec<_> = recipients.keys().cloned().collect();
 
@@ -162,6 +169,8 @@ pub(crate) fn delegate_stream_permission_impl<T: Config>(
         accumulating: true, // Start with accumulation enabled by default
         recipient_managers: validate_stream_managers::<T>(&delegator, recipient_manager)?,
         weight_setters: validate_stream_managers::<T>(&delegator, weight_setter)?,
+        limits: Default::default(),
+        recipient_payouts: Default::default(),
     });
 
     let permission_id = generate_permission_id::<T>(&delegator, &scope)?;
@@ -184,7 +193,14 @@ pub(crate) fn delegate_stream_permission_impl<T: Config>(
         StreamAllocation::FixedAmount(amount) => {
             T::Currency::reserve(&delegator, amount)?;
         }
+        StreamAllocation::Subscription(terms) => {
+            if terms.funding == SubscriptionFunding::Reserved {
+                T::Currency::reserve(&delegator, terms.remaining_amount(0))?;
+            }
+        }
         StreamAllocation::Streams(streams) => {
+            add_stream_edges::<T>(&delegator, streams.keys(), recipients_ids.iter());
+
             for stream in streams.keys() {
                 AccumulatedStreamAmounts::<T>::set(
                     (&delegator, stream, permission_id),
@@ -217,6 +233,7 @@ pub fn execute_permission_impl<T: Config>(
                     })
                     .fold(BalanceOf::<T>::zero(), |acc, e| acc.saturating_add(e)), // The Balance AST does not enforce the Sum trait
                 StreamAllocation::FixedAmount(amount) => *amount,
+                StreamAllocation::Subscription(terms) => terms.amount,
             };
 
             ensure!(!accumulated.is_zero(), Error::<T>::NoAccumulatedAmount);
@@ -244,46 +261,16 @@ pub fn toggle_permission_accumulation_impl<T: Config>(
     let mut contract =
         Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
 
-    if let Some(who) = &who {
-        match &contract.enforcement {
-            _ if who == &contract.delegator => {}
-            EnforcementAuthority::None => {
-                return Err(Error::<T>::NotAuthorizedToToggle.into());
-            }
-            EnforcementAuthority::ControlledBy {
-                controllers,
-                required_votes,
-            } => {
-                ensure!(controllers.contains(who), Error::<T>::NotAuthorizedToToggle);
-
-                let referendum = EnforcementReferendum::EmissionAccumulation(accumulating);
-                let votes = EnforcementTracking::<T>::get(permission_id, &referendum)
-                    .into_iter()
-                    .filter(|id| id != who)
-                    .filter(|id| controllers.contains(id))
-                    .count();
-
-                if votes.saturating_add(1) < *required_votes as usize {
-                    return EnforcementTracking::<T>::mutate(
-                        permission_id,
-                        referendum.clone(),
-                        |votes| {
-                            votes
-                                .try_insert(who.clone())
-                                .map_err(|_| Error::<T>::TooManyControllers)?;
-
-                            <Pallet<T>>::deposit_event(Event::EnforcementVoteCast {
-                                permission_id,
-                                voter: who.clone(),
-                                referendum,
-                            });
-
-                            Ok(())
-                        },
-                    );
-                }
-            }
-        }
+    if let Some(who) = &who
+        && who != &contract.delegator
+        && !crate::ext::vote_enforcement_referendum(
+            permission_id,
+            &contract,
+            who,
+            EnforcementReferendum::EmissionAccumulation(accumulating),
+        )?
+    {
+        return Ok(());
     }
 
     match &mut contract.scope {
@@ -296,7 +283,7 @@ pub fn toggle_permission_accumulation_impl<T: Config>(
     // Clear any votes for this referendum
     EnforcementTracking::<T>::remove(
         permission_id,
-        EnforcementReferendum::EmissionAccumulation(accumulating),
+        EnforcementReferendum::<T>::EmissionAccumulation(accumulating),
     );
 
     <Pallet<T>>::deposit_event(Event::PermissionAccumulationToggled {
@@ -339,6 +326,8 @@ pub(crate) fn update_stream_permission<T: Config>(
         return Err(Error::<T>::NotAuthorizedToEdit.into());
     }
 
+    let old_scope = scope.clone();
+
     if let Some(new_recipients) = new_recipients {
         if !allowed_recipients
             && (new_recipients.len() != scope.recipients.len()
@@ -362,8 +351,11 @@ pub(crate) fn update_stream_permission<T: Config>(
             permission_id,
         );
 
-        // Update recipients
+        // Update recipients, dropping the payouts of removed ones
         scope.recipients = new_recipients;
+        scope
+            .recipient_payouts
+            .retain(|recipient, _| scope.recipients.contains_key(recipient));
 
         // Add new indices for updated recipients
         crate::permission::add_permission_indices::<T>(
@@ -405,7 +397,7 @@ pub(crate) fn update_stream_permission<T: Config>(
     if let Some(new_distribution_control) = new_distribution_control {
         ensure!(allowed_delegator, Error::<T>::NotAuthorizedToEdit);
 
-        validate_stream_permission_distribution::<T>(&new_distribution_control)?;
+        validate_stream_permission_distribution::<T>(&scope.allocation, &new_distribution_control)?;
         scope.distribution = new_distribution_control;
     }
 
@@ -421,6 +413,23 @@ pub(crate) fn update_stream_permission<T: Config>(
             validate_stream_managers::<T>(&permission.delegator, new_weight_setter)?;
     }
 
+    if let (StreamAllocation::Streams(old_streams), StreamAllocation::Streams(streams)) =
+        (&old_scope.allocation, &scope.allocation)
+        && (old_streams != streams || old_scope.recipients != scope.recipients)
+    {
+        let delegator = &permission.delegator;
+        remove_stream_edges::<T>(delegator, old_streams.keys(), old_scope.recipients.keys());
+
+        if let Err(err) =
+            ensure_valid_stream_graph::<T>(delegator, streams.keys(), scope.recipients.keys())
+        {
+            add_stream_edges::<T>(delegator, old_streams.keys(), old_scope.recipients.keys());
+            return Err(err);
+        }
+
+        add_stream_edges::<T>(delegator, streams.keys(), scope.recipients.keys());
+    }
+
     permission.scope = PermissionScope::Stream(scope);
     permission.last_update = frame_system::Pallet::<T>::block_number();
     Permissions::<T>::set(permission_id, Some(permission));
@@ -428,6 +437,158 @@ pub(crate) fn update_stream_permission<T: Config>(
     Ok(())
 }
 
+/// Replaces the recipients of a stream permission on behalf of its
+/// enforcement authority, regardless of the revocation terms.
+pub(crate) fn enforce_stream_recipients<T: Config>(
+    permission_id: PermissionId,
+    mut permission: PermissionContract<T>,
+    new_recipients: BoundedBTreeMap<T::AccountId, u16, T::MaxRecipientsPerPermission>,
+) -> DispatchResult {
+    let PermissionScope::Stream(scope) = &mut permission.scope else {
+        return Err(Error::<T>::UnsupportedPermissionType.into());
+    };
+
+    validate_stream_permission_recipients::<T>(
+        &permission.delegator,
+        &permission.revocation,
+        &new_recipients,
+    )?;
+
+    let delegator = &permission.delegator;
+    if let StreamAllocation::Streams(streams) = &scope.allocation {
+        remove_stream_edges::<T>(delegator, streams.keys(), scope.recipients.keys());
+
+        if let Err(err) =
+            ensure_valid_stream_graph::<T>(delegator, streams.keys(), new_recipients.keys())
+        {
+            add_stream_edges::<T>(delegator, streams.keys(), scope.recipients.keys());
+            return Err(err);
+        }
+
+        add_stream_edges::<T>(delegator, streams.keys(), new_recipients.keys());
+    }
+
+    crate::permission::remove_permission_from_indices::<T>(
+        delegator,
+        scope.recipients.keys(),
+        permission_id,
+    );
+    crate::permission::add_permission_indices::<T>(
+        delegator,
+        new_recipients.keys(),
+        permission_id,
+    )?;
+
+    scope.recipients = new_recipients;
+    scope
+        .recipient_payouts
+        .retain(|recipient, _| scope.recipients.contains_key(recipient));
+
+    permission.last_update = frame_system::Pallet::<T>::block_number();
+    Permissions::<T>::set(permission_id, Some(permission));
+
+    Ok(())
+}
+
+pub(crate) fn update_stream_limits<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: PermissionId,
+    limits: StreamLimits<T>,
+) -> DispatchResult {
+    let caller = ensure_signed(origin)?;
+
+    let Some(mut permission) = Permissions::<T>::get(permission_id) else {
+        return Err(Error::<T>::PermissionNotFound.into());
+    };
+
+    ensure!(
+        permission.delegator == caller,
+        Error::<T>::NotPermissionDelegator
+    );
+    ensure!(permission.is_updatable(), Error::<T>::NotAuthorizedToEdit);
+
+    let PermissionScope::Stream(scope) = &mut permission.scope else {
+        return Err(Error::<T>::NotEditable.into());
+    };
+
+    let StreamAllocation::Streams(streams) = &scope.allocation else {
+        return Err(Error::<T>::NotEditable.into());
+    };
+
+    if let Some(cap) = limits.lifetime_cap {
+        ensure!(!cap.is_zero(), Error::<T>::InvalidStreamLimits);
+    }
+
+    if let Some(EpochCap { length, amount }) = &limits.epoch_cap {
+        ensure!(
+            !length.is_zero() && !amount.is_zero(),
+            Error::<T>::InvalidStreamLimits
+        );
+    }
+
+    if let StreamOverflow::Forward(fallback) = &limits.overflow {
+        ensure!(
+            !streams.contains_key(fallback),
+            Error::<T>::InvalidStreamLimits
+        );
+    }
+
+    scope.limits = limits;
+    permission.last_update = frame_system::Pallet::<T>::block_number();
+    Permissions::<T>::set(permission_id, Some(permission));
+
+    <Pallet<T>>::deposit_event(Event::StreamLimitsUpdated { permission_id });
+
+    Ok(())
+}
+
+pub(crate) fn set_stream_payout<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: PermissionId,
+    payout: StreamPayout<T::AccountId>,
+) -> DispatchResult {
+    let recipient = ensure_signed(origin)?;
+
+    if let StreamPayout::StakeTo(agent) = &payout {
+        ensure!(
+            T::Torus::is_agent_registered(agent),
+            Error::<T>::NotRegisteredAgent
+        );
+    }
+
+    Permissions::<T>::try_mutate(permission_id, |permission| {
+        let Some(permission) = permission else {
+            return Err(Error::<T>::PermissionNotFound.into());
+        };
+
+        let PermissionScope::Stream(scope) = &mut permission.scope else {
+            return Err(Error::<T>::UnsupportedPermissionType.into());
+        };
+
+        ensure!(
+            scope.recipients.contains_key(&recipient),
+            Error::<T>::NotPermissionRecipient
+        );
+
+        if payout == StreamPayout::FreeBalance {
+            scope.recipient_payouts.remove(&recipient);
+        } else {
+            scope
+                .recipient_payouts
+                .try_insert(recipient.clone(), payout.clone())
+                .map_err(|_| Error::<T>::TooManyRecipients)?;
+        }
+
+        <Pallet<T>>::deposit_event(Event::StreamPayoutSet {
+            permission_id,
+            recipient,
+            payout,
+        });
+
+        Ok(())
+    })
+}
+
 fn validate_stream_managers<T: Config>(
     delegator: &T::AccountId,
     entry: Option<T::AccountId>,
@@ -484,8 +645,24 @@ fn validate_stream_permission_streams<T: Config>(
 }
 
 fn validate_stream_permission_distribution<T: Config>(
+    allocation: &StreamAllocation<T>,
     distribution: &DistributionControl<T>,
 ) -> DispatchResult {
+    match allocation {
+        StreamAllocation::FixedAmount(_) => ensure!(
+            matches!(
+                distribution,
+                DistributionControl::Manual | DistributionControl::AtBlock(_)
+            ),
+            Error::<T>::FixedAmountCanOnlyBeTriggeredOnce
+        ),
+        StreamAllocation::Subscription(_) => ensure!(
+            matches!(distribution, DistributionControl::Interval(_)),
+            Error::<T>::InvalidSubscriptionTerms
+        ),
+        StreamAllocation::Streams(_) => {}
+    }
+
     match distribution {
         DistributionControl::Automatic(threshold) => {
             ensure!(
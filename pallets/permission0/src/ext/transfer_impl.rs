@@ -0,0 +1,214 @@
+use polkadot_sdk::{
+    frame_support::{dispatch::DispatchResult, ensure, storage::with_storage_layer},
+    frame_system::{self, ensure_signed},
+    polkadot_sdk_frame::prelude::OriginFor,
+    sp_std::vec::Vec,
+};
+
+use pallet_torus0_api::Torus0Api;
+
+use crate::{
+    Config, Error, Event, Pallet, PendingRecipientTransfers, PermissionId, PermissionScope,
+    Permissions, RecipientTransfer, TransferablePermissions,
+    permission::{
+        NamespaceScope, add_permission_indices, remove_permission_from_indices,
+        remove_recipient_from_indices, wallet::WalletScope,
+    },
+};
+
+/// Opt a namespace or wallet permission in or out of recipient transfers
+pub(crate) fn set_permission_transferable_impl<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: PermissionId,
+    transfer: Option<RecipientTransfer>,
+) -> DispatchResult {
+    let who = ensure_signed(origin)?;
+
+    let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
+    ensure!(
+        contract.delegator == who,
+        Error::<T>::NotPermissionDelegator
+    );
+    ensure!(
+        matches!(
+            contract.scope,
+            PermissionScope::Namespace(_) | PermissionScope::Wallet(_)
+        ),
+        Error::<T>::UnsupportedPermissionType
+    );
+
+    TransferablePermissions::<T>::set(permission_id, transfer.clone());
+    PendingRecipientTransfers::<T>::remove(permission_id);
+
+    <Pallet<T>>::deposit_event(Event::PermissionTransferabilitySet {
+        permission_id,
+        transfer,
+    });
+
+    Ok(())
+}
+
+/// Hand the recipient role of a permission over to another agent. Permissions
+/// requiring approval only record the request until the delegator approves it.
+pub(crate) fn transfer_permission_recipient_impl<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: PermissionId,
+    new_recipient: T::AccountId,
+) -> DispatchResult {
+    let who = ensure_signed(origin)?;
+
+    let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
+    let transfer =
+        TransferablePermissions::<T>::get(permission_id).ok_or(Error::<T>::NotTransferable)?;
+
+    let recipient = single_recipient::<T>(&contract.scope)?;
+    ensure!(recipient == &who, Error::<T>::NotPermissionRecipient);
+
+    ensure_valid_new_recipient::<T>(&contract.delegator, recipient, &new_recipient)?;
+
+    match transfer {
+        RecipientTransfer::Allowed => do_transfer_recipient::<T>(permission_id, new_recipient),
+        RecipientTransfer::RequiresApproval => {
+            PendingRecipientTransfers::<T>::insert(permission_id, &new_recipient);
+
+            <Pallet<T>>::deposit_event(Event::PermissionTransferRequested {
+                permission_id,
+                from: who,
+                to: new_recipient,
+            });
+
+            Ok(())
+        }
+    }
+}
+
+/// Approve a pending recipient transfer. `new_recipient` must match the
+/// requested one, so the delegator approves exactly what it has seen.
+pub(crate) fn approve_permission_transfer_impl<T: Config>(
+    origin: OriginFor<T>,
+    permission_id: PermissionId,
+    new_recipient: T::AccountId,
+) -> DispatchResult {
+    let who = ensure_signed(origin)?;
+
+    let contract = Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
+    ensure!(
+        contract.delegator == who,
+        Error::<T>::NotPermissionDelegator
+    );
+
+    ensure!(
+        PendingRecipientTransfers::<T>::get(permission_id).as_ref() == Some(&new_recipient),
+        Error::<T>::TransferNotRequested
+    );
+
+    let recipient = single_recipient::<T>(&contract.scope)?;
+    ensure_valid_new_recipient::<T>(&contract.delegator, recipient, &new_recipient)?;
+
+    do_transfer_recipient::<T>(permission_id, new_recipient)
+}
+
+fn single_recipient<T: Config>(scope: &PermissionScope<T>) -> Result<&T::AccountId, Error<T>> {
+    match scope {
+        PermissionScope::Namespace(NamespaceScope { recipient, .. })
+        | PermissionScope::Wallet(WalletScope { recipient, .. }) => Ok(recipient),
+        _ => Err(Error::<T>::UnsupportedPermissionType),
+    }
+}
+
+fn ensure_valid_new_recipient<T: Config>(
+    delegator: &T::AccountId,
+    recipient: &T::AccountId,
+    new_recipient: &T::AccountId,
+) -> DispatchResult {
+    ensure!(
+        new_recipient != delegator,
+        Error::<T>::SelfPermissionNotAllowed
+    );
+    ensure!(new_recipient != recipient, Error::<T>::InvalidNewRecipient);
+    ensure!(
+        T::Torus::is_agent_registered(new_recipient),
+        Error::<T>::NotRegisteredAgent
+    );
+
+    Ok(())
+}
+
+/// Moves the recipient role and its indices to `new_recipient`, keeping the
+/// children and execution history of the permission. Children are delegated
+/// by the recipient of their parent, so they are handed over to
+/// `new_recipient` as well.
+fn do_transfer_recipient<T: Config>(
+    permission_id: PermissionId,
+    new_recipient: T::AccountId,
+) -> DispatchResult {
+    with_storage_layer(|| {
+        let mut contract =
+            Permissions::<T>::get(permission_id).ok_or(Error::<T>::PermissionNotFound)?;
+
+        let children: Vec<_> = contract.children().into_iter().flatten().copied().collect();
+
+        let recipient = match &mut contract.scope {
+            PermissionScope::Namespace(NamespaceScope { recipient, .. })
+            | PermissionScope::Wallet(WalletScope { recipient, .. }) => recipient,
+            _ => return Err(Error::<T>::UnsupportedPermissionType.into()),
+        };
+
+        let old_recipient = core::mem::replace(recipient, new_recipient.clone());
+
+        remove_recipient_from_indices::<T>(&contract.delegator, &old_recipient, permission_id);
+        add_permission_indices::<T>(
+            &contract.delegator,
+            core::iter::once(&new_recipient),
+            permission_id,
+        )?;
+
+        for child_id in children {
+            transfer_child_delegator::<T>(child_id, &old_recipient, &new_recipient)?;
+        }
+
+        contract.last_update = frame_system::Pallet::<T>::block_number();
+        Permissions::<T>::insert(permission_id, contract);
+        PendingRecipientTransfers::<T>::remove(permission_id);
+
+        <Pallet<T>>::deposit_event(Event::PermissionRecipientTransferred {
+            permission_id,
+            from: old_recipient,
+            to: new_recipient,
+        });
+
+        Ok(())
+    })
+}
+
+/// Makes `new_delegator` the delegator of a child permission delegated by
+/// `old_delegator`. Grandchildren are delegated by the child recipient, which
+/// does not change.
+fn transfer_child_delegator<T: Config>(
+    child_id: PermissionId,
+    old_delegator: &T::AccountId,
+    new_delegator: &T::AccountId,
+) -> DispatchResult {
+    let Some(mut child) = Permissions::<T>::get(child_id) else {
+        return Ok(());
+    };
+
+    if &child.delegator != old_delegator {
+        return Ok(());
+    }
+
+    let recipient = single_recipient::<T>(&child.scope)?.clone();
+    ensure!(
+        &recipient != new_delegator,
+        Error::<T>::SelfPermissionNotAllowed
+    );
+
+    remove_permission_from_indices::<T>(old_delegator, core::iter::once(&recipient), child_id);
+    add_permission_indices::<T>(new_delegator, core::iter::once(&recipient), child_id)?;
+
+    child.delegator = new_delegator.clone();
+    child.last_update = frame_system::Pallet::<T>::block_number();
+    Permissions::<T>::insert(child_id, child);
+
+    Ok(())
+}
@@ -12,9 +12,11 @@ pub mod permission;
 pub use pallet::*;
 
 pub use permission::{
-    CuratorPermissions, CuratorScope, DistributionControl, EnforcementAuthority,
-    EnforcementReferendum, PermissionContract, PermissionDuration, PermissionId, PermissionScope,
-    RevocationTerms, StreamAllocation, StreamScope, generate_permission_id,
+    BulkOperation, CuratorPermissions, CuratorQuota, CuratorScope, DistributionControl,
+    EnforcementAuthority, EnforcementReferendum, EnforcementVotes, EpochCap, EscrowScope,
+    PermissionContract, PermissionDuration, PermissionId, PermissionScope, RecipientTransfer,
+    RevocationTerms, StreamAllocation, StreamDelegation, StreamLimits, StreamOverflow,
+    StreamPayout, StreamScope, SubscriptionFunding, SubscriptionTerms, generate_permission_id,
 };
 
 pub use pallet_permission0_api::{StreamId, generate_root_stream_id};
@@ -39,7 +41,7 @@ pub mod pallet {
 
     use super::*;
 
-    const STORAGE_VERSION: StorageVersion = StorageVersion::new(7);
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(10);
 
     /// Configure the pallet by specifying the parameters and types on which it depends.
     #[pallet::config]
@@ -92,6 +94,23 @@ pub mod pallet {
         /// Max operations a bulk extrinsic can perform per extrinsic call.
         #[pallet::constant]
         type MaxBulkOperationsPerCall: Get<u32>;
+
+        /// Maximum length of a chain of stream delegations over the same stream.
+        #[pallet::constant]
+        type MaxStreamDelegationDepth: Get<u32>;
+
+        /// Maximum number of accounts visited in the stream graph when
+        /// checking the chains through new stream delegations.
+        #[pallet::constant]
+        type MaxStreamGraphWalk: Get<u32>;
+
+        /// Maximum number of milestones of an escrow permission.
+        #[pallet::constant]
+        type MaxEscrowMilestones: Get<u32>;
+
+        /// Blocks after which the votes of an enforcement referendum expire.
+        #[pallet::constant]
+        type EnforcementVotingPeriod: Get<BlockNumberFor<Self>>;
     }
 
     pub type BalanceOf<T> =
@@ -151,6 +170,27 @@ pub mod pallet {
         ValueQuery,
     >;
 
+    /// Arbiter approvals of the next escrow milestone
+    #[pallet::storage]
+    pub type EscrowApprovals<T: Config> = StorageMap<
+        _,
+        Identity,
+        PermissionId,
+        BoundedBTreeSet<T::AccountId, T::MaxRevokersPerPermission>,
+        ValueQuery,
+    >;
+
+    /// Permissions whose recipient can be transferred, and whether the
+    /// delegator must approve transfers
+    #[pallet::storage]
+    pub type TransferablePermissions<T: Config> =
+        StorageMap<_, Identity, PermissionId, RecipientTransfer>;
+
+    /// Recipient transfers waiting for the approval of the delegator
+    #[pallet::storage]
+    pub type PendingRecipientTransfers<T: Config> =
+        StorageMap<_, Identity, PermissionId, T::AccountId>;
+
     /// Enforcement votes in progress and the voters
     #[pallet::storage]
     pub type EnforcementTracking<T: Config> = StorageDoubleMap<
@@ -158,11 +198,17 @@ pub mod pallet {
         Identity,
         PermissionId,
         Identity,
-        EnforcementReferendum,
-        BoundedBTreeSet<T::AccountId, T::MaxControllersPerPermission>,
+        EnforcementReferendum<T>,
+        EnforcementVotes<T>,
         ValueQuery,
     >;
 
+    /// Permissions paused by their enforcement authority and the block they
+    /// were paused at
+    #[pallet::storage]
+    pub type PausedPermissions<T: Config> =
+        StorageMap<_, Identity, PermissionId, BlockNumberFor<T>>;
+
     /// Accumulated amounts for each stream
     #[pallet::storage]
     pub type AccumulatedStreamAmounts<T: Config> = StorageNMap<
@@ -175,6 +221,43 @@ pub mod pallet {
         BalanceOf<T>,
     >;
 
+    /// Stream graph: number of permissions through which a delegator streams
+    /// a stream to a recipient
+    #[pallet::storage]
+    pub type StreamGraph<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, StreamId>,
+            NMapKey<Identity, T::AccountId>,
+            NMapKey<Identity, T::AccountId>,
+        ),
+        u32,
+        ValueQuery,
+    >;
+
+    /// Stream graph edges indexed by recipient. Reverse index of
+    /// [`StreamGraph`].
+    #[pallet::storage]
+    pub type ReverseStreamGraph<T: Config> = StorageNMap<
+        _,
+        (
+            NMapKey<Identity, StreamId>,
+            NMapKey<Identity, T::AccountId>,
+            NMapKey<Identity, T::AccountId>,
+        ),
+        (),
+    >;
+
+    /// Amounts accumulated by stream permissions with limits
+    #[pallet::storage]
+    pub type StreamLimitUsage<T: Config> =
+        StorageMap<_, Identity, PermissionId, permission::stream::StreamUsage<T>, ValueQuery>;
+
+    /// Block at which a subscription payment first failed for lack of funds
+    #[pallet::storage]
+    pub type SubscriptionArrears<T: Config> =
+        StorageMap<_, Identity, PermissionId, BlockNumberFor<T>>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
@@ -209,7 +292,45 @@ pub mod pallet {
         EnforcementVoteCast {
             permission_id: PermissionId,
             voter: T::AccountId,
-            referendum: EnforcementReferendum,
+            referendum: EnforcementReferendum<T>,
+        },
+        /// An enforcement referendum reached the required votes
+        EnforcementReferendumRatified {
+            permission_id: PermissionId,
+            referendum: EnforcementReferendum<T>,
+            votes: u32,
+        },
+        /// Permission paused by its enforcement authority
+        PermissionPaused { permission_id: PermissionId },
+        /// Permission resumed by its enforcement authority
+        PermissionResumed { permission_id: PermissionId },
+        /// An item of a bulk operation was applied
+        BulkItemApplied {
+            operation: BulkOperation,
+            index: u32,
+            permission_id: PermissionId,
+        },
+        /// Every item of a bulk operation was applied
+        BulkOperationCompleted {
+            operation: BulkOperation,
+            count: u32,
+        },
+        /// Delegator changed whether the permission recipient can be transferred
+        PermissionTransferabilitySet {
+            permission_id: PermissionId,
+            transfer: Option<RecipientTransfer>,
+        },
+        /// Recipient requested a transfer that the delegator must approve
+        PermissionTransferRequested {
+            permission_id: PermissionId,
+            from: T::AccountId,
+            to: T::AccountId,
+        },
+        /// The recipient role of a permission was transferred
+        PermissionRecipientTransferred {
+            permission_id: PermissionId,
+            from: T::AccountId,
+            to: T::AccountId,
         },
         /// Enforcement authority set for permission
         EnforcementAuthoritySet {
@@ -231,6 +352,61 @@ pub mod pallet {
             stream_id: StreamId,
             amount: BalanceOf<T>,
         },
+        /// A curator action was authorized by the permission
+        CuratorPermissionUsed {
+            permission_id: PermissionId,
+            recipient: T::AccountId,
+            flags: CuratorPermissions,
+        },
+        /// A subscription payment could not be made, it will be retried
+        /// until the grace period ends
+        SubscriptionPaymentMissed {
+            permission_id: PermissionId,
+            amount: BalanceOf<T>,
+            grace_period_ends: BlockNumberFor<T>,
+        },
+        /// A missed subscription payment lapsed after its grace period
+        SubscriptionPaymentLapsed {
+            permission_id: PermissionId,
+            revoked: bool,
+        },
+        /// Stream limits of a permission were updated
+        StreamLimitsUpdated { permission_id: PermissionId },
+        /// Emissions above the stream limits were returned to the delegator
+        /// or forwarded to a fallback stream
+        StreamOverflowed {
+            permission_id: PermissionId,
+            stream_id: StreamId,
+            amount: BalanceOf<T>,
+            forwarded_to: Option<StreamId>,
+        },
+        /// A stream permission distributed after using up its lifetime cap
+        StreamLifetimeCapReached { permission_id: PermissionId },
+        /// A stream recipient changed how it receives its share
+        StreamPayoutSet {
+            permission_id: PermissionId,
+            recipient: T::AccountId,
+            payout: StreamPayout<T::AccountId>,
+        },
+        /// An arbiter approved the next escrow milestone
+        EscrowMilestoneApproved {
+            permission_id: PermissionId,
+            milestone: u32,
+            approver: T::AccountId,
+        },
+        /// An escrow milestone was released to the recipient
+        EscrowMilestoneReleased {
+            permission_id: PermissionId,
+            milestone: u32,
+            amount: BalanceOf<T>,
+        },
+        /// An escrow was settled, either after its last milestone or after
+        /// the dispute timeout, refunding the unreleased funds
+        EscrowSettled {
+            permission_id: PermissionId,
+            released: u32,
+            refunded: BalanceOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -329,6 +505,40 @@ pub mod pallet {
         TooManyCuratorPermissions,
         /// Namespace delegation depth exceeded the maximum allowed limit.
         DelegationDepthExceeded,
+        /// Curator quotas must target a single delegated flag, allow at least one
+        /// action and have a non-zero period.
+        InvalidCuratorQuota,
+        /// The curator quota for this action is used up for the current period.
+        CuratorQuotaExceeded,
+        /// Sub-curator quotas must fit in the remaining quota of the parent permission.
+        CuratorQuotaExceedsParent,
+        /// Subscriptions must pay a non-zero amount at least once, on a fixed interval
+        InvalidSubscriptionTerms,
+        /// Stream caps must be non-zero and overflow cannot be forwarded to
+        /// one of the permission's own streams
+        InvalidStreamLimits,
+        /// Stream delegations cannot flow back to one of their delegators.
+        StreamCycleDetected,
+        /// Stream delegation chain exceeded the maximum allowed depth.
+        StreamDepthExceeded,
+        /// Escrows need non-zero milestones and dispute timeout, and arbiters
+        /// must be able to reach the required votes.
+        InvalidEscrowTerms,
+        /// Only the delegator and the arbiters can approve escrow milestones.
+        NotAuthorizedToApprove,
+        /// Permission is paused by its enforcement authority.
+        PermissionPaused,
+        /// Permission is not paused.
+        PermissionNotPaused,
+        /// Permission recipient cannot be transferred.
+        NotTransferable,
+        /// No transfer to the given account was requested.
+        TransferNotRequested,
+        /// The new recipient must differ from the current one.
+        InvalidNewRecipient,
+        /// Checking the stream delegation visits more accounts of the stream
+        /// graph than allowed.
+        StreamGraphTooLarge,
     }
 
     #[pallet::hooks]
@@ -342,7 +552,7 @@ pub mod pallet {
     impl<T: Config> Pallet<T> {
         /// Delegate a permission for stream delegation
         #[pallet::call_index(0)]
-        #[pallet::weight(T::WeightInfo::delegate_stream_permission())]
+        #[pallet::weight(T::WeightInfo::delegate_stream_permission().saturating_add(permission::stream::stream_graph_walk_weight::<T>()))]
         pub fn delegate_stream_permission(
             origin: OriginFor<T>,
             recipients: BoundedBTreeMap<T::AccountId, u16, T::MaxRecipientsPerPermission>,
@@ -382,6 +592,8 @@ pub mod pallet {
         }
 
         /// Execute a manual distribution based on permission
+        /// Proxies allowed to execute permissions for the delegator can also
+        /// call it
         #[pallet::call_index(2)]
         #[pallet::weight(T::WeightInfo::execute_permission())]
         pub fn execute_permission(
@@ -442,7 +654,9 @@ pub mod pallet {
             contract.update_enforcement(permission_id, enforcement)
         }
 
-        /// Delegate a permission for curator delegation
+        /// Delegate a permission for curator delegation. Quotas are keyed by
+        /// single curator flags and limit how often the action can be used per
+        /// period.
         #[pallet::call_index(6)]
         #[pallet::weight(T::WeightInfo::delegate_curator_permission())]
         pub fn delegate_curator_permission(
@@ -457,14 +671,31 @@ pub mod pallet {
             duration: PermissionDuration<T>,
             revocation: RevocationTerms<T>,
             instances: u32,
+            quotas: BoundedBTreeMap<
+                u32,
+                CuratorQuota<T>,
+                ConstU32<{ permission::curator::MAX_CURATOR_QUOTAS }>,
+            >,
         ) -> DispatchResult {
             let flags = flags
                 .into_iter()
                 .map(|(pid, flags)| (pid, CuratorPermissions::from_bits_truncate(flags)))
                 .try_collect()?;
 
+            let quotas = quotas
+                .into_iter()
+                .map(|(flag, quota)| {
+                    CuratorPermissions::from_bits(flag)
+                        .filter(|flag| flag.bits().count_ones() == 1)
+                        .map(|flag| (flag, quota))
+                        .ok_or(Error::<T>::InvalidCuratorQuota)
+                })
+                .collect::<Result<Vec<_>, _>>()?
+                .into_iter()
+                .try_collect()?;
+
             ext::curator_impl::delegate_curator_permission_impl::<T>(
-                origin, recipient, flags, cooldown, duration, revocation, instances,
+                origin, recipient, flags, cooldown, duration, revocation, instances, quotas,
             )?;
 
             Ok(())
@@ -549,7 +780,7 @@ pub mod pallet {
 
         /// Allows Delegator/Recipient to edit stream permission
         #[pallet::call_index(8)]
-        #[pallet::weight(T::WeightInfo::delegate_curator_permission())]
+        #[pallet::weight(T::WeightInfo::delegate_curator_permission().saturating_add(permission::stream::stream_graph_walk_weight::<T>()))]
         pub fn update_stream_permission(
             origin: OriginFor<T>,
             permission_id: PermissionId,
@@ -600,6 +831,186 @@ pub mod pallet {
         ) -> DispatchResult {
             ext::wallet_impl::execute_wallet_stake_permission(caller, permission_id, op)
         }
+
+        /// Allows the delegator to cap how much a percentage stream permission
+        /// accumulates, over its lifetime and per epoch
+        #[pallet::call_index(13)]
+        #[pallet::weight(T::WeightInfo::update_stream_limits())]
+        pub fn update_stream_limits(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+            limits: StreamLimits<T>,
+        ) -> DispatchResult {
+            ext::stream_impl::update_stream_limits::<T>(origin, permission_id, limits)
+        }
+
+        /// Allows a stream recipient to choose whether its share is paid as free
+        /// balance or staked, to itself or to another agent
+        #[pallet::call_index(14)]
+        #[pallet::weight(T::WeightInfo::set_stream_payout())]
+        pub fn set_stream_payout(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+            payout: StreamPayout<T::AccountId>,
+        ) -> DispatchResult {
+            ext::stream_impl::set_stream_payout::<T>(origin, permission_id, payout)
+        }
+
+        /// Delegate an escrow of the sum of the milestones, released to the
+        /// recipient one milestone at a time
+        #[pallet::call_index(15)]
+        #[pallet::weight(T::WeightInfo::delegate_stream_permission())]
+        pub fn delegate_escrow_permission(
+            origin: OriginFor<T>,
+            recipient: T::AccountId,
+            milestones: BoundedVec<BalanceOf<T>, T::MaxEscrowMilestones>,
+            arbiters: BoundedBTreeSet<T::AccountId, T::MaxRevokersPerPermission>,
+            required_votes: u32,
+            dispute_timeout: BlockNumberFor<T>,
+            duration: PermissionDuration<T>,
+            revocation: RevocationTerms<T>,
+        ) -> DispatchResult {
+            ext::escrow_impl::delegate_escrow_permission_impl::<T>(
+                origin,
+                recipient,
+                milestones,
+                arbiters,
+                required_votes,
+                dispute_timeout,
+                duration,
+                revocation,
+            )
+        }
+
+        /// Approve the release of the next escrow milestone
+        /// The caller must be the delegator or one of the arbiters
+        #[pallet::call_index(16)]
+        #[pallet::weight(T::WeightInfo::execute_permission())]
+        pub fn approve_escrow_milestone(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+        ) -> DispatchResult {
+            ext::escrow_impl::approve_escrow_milestone_impl::<T>(origin, permission_id)
+        }
+
+        /// Vote on an enforcement referendum for a permission, applying it
+        /// once the required votes are met
+        /// The caller must be authorized as a controller or be the root key
+        #[pallet::call_index(17)]
+        #[pallet::weight(T::WeightInfo::enforcement_execute_permission().saturating_add(permission::stream::stream_graph_walk_weight::<T>()))]
+        pub fn vote_enforcement_referendum(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+            referendum: EnforcementReferendum<T>,
+        ) -> DispatchResult {
+            ext::enforcement_referendum_impl::<T>(origin, permission_id, referendum)
+        }
+
+        /// Delegate multiple stream permissions at once.
+        /// Either every delegation is applied or none is.
+        #[pallet::call_index(18)]
+        #[pallet::weight(T::WeightInfo::bulk_delegate_stream_permission(delegations.len() as u32)
+            .saturating_add(permission::stream::stream_graph_walk_weight::<T>().saturating_mul(delegations.len() as u64)))]
+        pub fn bulk_delegate_stream_permission(
+            origin: OriginFor<T>,
+            delegations: BoundedVec<StreamDelegation<T>, T::MaxBulkOperationsPerCall>,
+        ) -> DispatchResult {
+            let delegator = ensure_signed(origin)?;
+
+            ext::bulk_operation_impl::<T, _>(BulkOperation::DelegateStream, delegations, |d| {
+                ext::stream_impl::delegate_stream_permission_impl::<T>(
+                    delegator.clone(),
+                    d.recipients,
+                    d.allocation,
+                    d.distribution,
+                    d.duration,
+                    d.revocation,
+                    d.enforcement,
+                    d.recipient_manager,
+                    d.weight_setter,
+                )
+            })
+        }
+
+        /// Revoke multiple permissions at once, subject to their revocation terms.
+        /// Either every permission is revoked or none is.
+        #[pallet::call_index(19)]
+        #[pallet::weight(T::WeightInfo::bulk_revoke_permissions(permission_ids.len() as u32))]
+        pub fn bulk_revoke_permissions(
+            origin: OriginFor<T>,
+            permission_ids: BoundedVec<PermissionId, T::MaxBulkOperationsPerCall>,
+        ) -> DispatchResult {
+            ext::bulk_operation_impl::<T, _>(BulkOperation::Revoke, permission_ids, |id| {
+                ext::revoke_permission_impl::<T>(origin.clone(), &id).map(|_| id)
+            })
+        }
+
+        /// Execute multiple permissions at once.
+        /// Either every permission is executed or none is.
+        #[pallet::call_index(20)]
+        #[pallet::weight(T::WeightInfo::bulk_execute_permissions(permission_ids.len() as u32))]
+        pub fn bulk_execute_permissions(
+            origin: OriginFor<T>,
+            permission_ids: BoundedVec<PermissionId, T::MaxBulkOperationsPerCall>,
+        ) -> DispatchResult {
+            ext::bulk_operation_impl::<T, _>(BulkOperation::Execute, permission_ids, |id| {
+                ext::execute_permission_impl::<T>(origin.clone(), &id).map(|_| id)
+            })
+        }
+
+        /// Opt a namespace or wallet permission in or out of recipient
+        /// transfers. Only the delegator can change it.
+        #[pallet::call_index(21)]
+        #[pallet::weight(T::WeightInfo::set_permission_transferable())]
+        pub fn set_permission_transferable(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+            transfer: Option<RecipientTransfer>,
+        ) -> DispatchResult {
+            ext::transfer_impl::set_permission_transferable_impl::<T>(
+                origin,
+                permission_id,
+                transfer,
+            )
+        }
+
+        /// Transfer the recipient role of a transferable permission to another
+        /// agent, keeping its children and execution history
+        /// Children delegated by the current recipient are handed over too
+        /// The caller must be the current recipient
+        #[pallet::call_index(22)]
+        #[pallet::weight(T::WeightInfo::transfer_permission_recipient(
+            T::MaxChildrenPerPermission::get()
+        ))]
+        pub fn transfer_permission_recipient(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+            new_recipient: T::AccountId,
+        ) -> DispatchResult {
+            ext::transfer_impl::transfer_permission_recipient_impl::<T>(
+                origin,
+                permission_id,
+                new_recipient,
+            )
+        }
+
+        /// Approve a recipient transfer requested by the current recipient
+        /// The caller must be the delegator
+        #[pallet::call_index(23)]
+        #[pallet::weight(T::WeightInfo::approve_permission_transfer(
+            T::MaxChildrenPerPermission::get()
+        ))]
+        pub fn approve_permission_transfer(
+            origin: OriginFor<T>,
+            permission_id: PermissionId,
+            new_recipient: T::AccountId,
+        ) -> DispatchResult {
+            ext::transfer_impl::approve_permission_transfer_impl::<T>(
+                origin,
+                permission_id,
+                new_recipient,
+            )
+        }
     }
 }
 
@@ -1 +1,207 @@
+use polkadot_sdk::frame_support::{
+    migrations::VersionedMigration, traits::UncheckedOnRuntimeUpgrade, weights::Weight,
+};
 
+use crate::{Config, Pallet};
+
+pub mod v8 {
+    use codec::{Decode, Encode};
+    use polkadot_sdk::{
+        frame_system::pallet_prelude::BlockNumberFor,
+        sp_runtime::{BoundedBTreeMap, BoundedBTreeSet},
+        sp_std::marker::PhantomData,
+        sp_tracing::info,
+    };
+
+    use super::*;
+    use crate::{
+        CuratorPermissions, CuratorScope, DistributionControl, EnforcementAuthority,
+        PermissionContract, PermissionDuration, PermissionId, PermissionScope, Permissions,
+        RevocationTerms, StreamAllocation, StreamScope,
+        permission::{NamespaceScope, wallet::WalletScope},
+    };
+
+    /// Adds empty per-action quotas to curator permissions, and empty limits
+    /// and recipient payouts to stream permissions.
+    pub type Migration<T, W> = VersionedMigration<7, 8, MigrateToV8<T>, Pallet<T>, W>;
+
+    pub struct MigrateToV8<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for MigrateToV8<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut curators = 0u32;
+            let mut streams = 0u32;
+
+            Permissions::<T>::translate::<OldPermissionContract<T>, _>(|_, old| {
+                let scope = match old.scope {
+                    OldPermissionScope::Stream(scope) => {
+                        streams = streams.saturating_add(1);
+                        PermissionScope::Stream(StreamScope {
+                            recipients: scope.recipients,
+                            allocation: scope.allocation,
+                            distribution: scope.distribution,
+                            accumulating: scope.accumulating,
+                            recipient_managers: scope.recipient_managers,
+                            weight_setters: scope.weight_setters,
+                            limits: Default::default(),
+                            recipient_payouts: Default::default(),
+                        })
+                    }
+                    OldPermissionScope::Curator(scope) => {
+                        curators = curators.saturating_add(1);
+                        PermissionScope::Curator(CuratorScope {
+                            recipient: scope.recipient,
+                            flags: scope.flags,
+                            cooldown: scope.cooldown,
+                            max_instances: scope.max_instances,
+                            children: scope.children,
+                            quotas: Default::default(),
+                            quota_usage: Default::default(),
+                        })
+                    }
+                    OldPermissionScope::Namespace(scope) => PermissionScope::Namespace(scope),
+                    OldPermissionScope::Wallet(scope) => PermissionScope::Wallet(scope),
+                };
+
+                Some(PermissionContract {
+                    delegator: old.delegator,
+                    scope,
+                    duration: old.duration,
+                    revocation: old.revocation,
+                    enforcement: old.enforcement,
+                    last_update: old.last_update,
+                    last_execution: old.last_execution,
+                    execution_count: old.execution_count,
+                    created_at: old.created_at,
+                })
+            });
+
+            info!("migrated {curators} curator and {streams} stream permissions");
+
+            Weight::zero()
+        }
+    }
+
+    #[derive(Encode, Decode)]
+    struct OldCuratorScope<T: Config> {
+        recipient: T::AccountId,
+        flags: BoundedBTreeMap<
+            Option<PermissionId>,
+            CuratorPermissions,
+            T::MaxCuratorSubpermissionsPerPermission,
+        >,
+        cooldown: Option<BlockNumberFor<T>>,
+        max_instances: u32,
+        children: BoundedBTreeSet<PermissionId, T::MaxChildrenPerPermission>,
+    }
+
+    #[derive(Encode, Decode)]
+    struct OldStreamScope<T: Config> {
+        recipients: BoundedBTreeMap<T::AccountId, u16, T::MaxRecipientsPerPermission>,
+        allocation: StreamAllocation<T>,
+        distribution: DistributionControl<T>,
+        accumulating: bool,
+        recipient_managers: BoundedBTreeSet<T::AccountId, T::MaxControllersPerPermission>,
+        weight_setters: BoundedBTreeSet<T::AccountId, T::MaxControllersPerPermission>,
+    }
+
+    #[derive(Encode, Decode)]
+    enum OldPermissionScope<T: Config> {
+        Stream(OldStreamScope<T>),
+        Curator(OldCuratorScope<T>),
+        Namespace(NamespaceScope<T>),
+        Wallet(WalletScope<T>),
+    }
+
+    #[derive(Encode, Decode)]
+    struct OldPermissionContract<T: Config> {
+        delegator: T::AccountId,
+        scope: OldPermissionScope<T>,
+        duration: PermissionDuration<T>,
+        revocation: RevocationTerms<T>,
+        enforcement: EnforcementAuthority<T>,
+        last_update: BlockNumberFor<T>,
+        last_execution: Option<BlockNumberFor<T>>,
+        execution_count: u32,
+        created_at: BlockNumberFor<T>,
+    }
+}
+
+pub mod v9 {
+    use polkadot_sdk::{sp_std::marker::PhantomData, sp_tracing::info};
+
+    use super::*;
+    use crate::{PermissionScope, Permissions, StreamAllocation, StreamScope};
+
+    /// Builds the stream graph index, and its reverse index, from existing
+    /// stream permissions.
+    pub type Migration<T, W> = VersionedMigration<8, 9, MigrateToV9<T>, Pallet<T>, W>;
+
+    pub struct MigrateToV9<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for MigrateToV9<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut streams = 0u32;
+
+            for contract in Permissions::<T>::iter_values() {
+                let PermissionScope::Stream(StreamScope {
+                    recipients,
+                    allocation: StreamAllocation::Streams(allocation),
+                    ..
+                }) = &contract.scope
+                else {
+                    continue;
+                };
+
+                streams = streams.saturating_add(1);
+                crate::permission::stream::add_stream_edges::<T>(
+                    &contract.delegator,
+                    allocation.keys(),
+                    recipients.keys(),
+                );
+            }
+
+            info!("indexed {streams} stream permissions");
+
+            Weight::zero()
+        }
+    }
+}
+
+pub mod v10 {
+    use polkadot_sdk::{
+        frame_support::traits::Get,
+        frame_system,
+        sp_runtime::{BoundedBTreeSet, traits::Saturating},
+        sp_std::marker::PhantomData,
+        sp_tracing::info,
+    };
+
+    use super::*;
+    use crate::{EnforcementTracking, EnforcementVotes};
+
+    /// Enforcement votes in progress start their voting period at the upgrade.
+    pub type Migration<T, W> = VersionedMigration<9, 10, MigrateToV10<T>, Pallet<T>, W>;
+
+    pub struct MigrateToV10<T>(PhantomData<T>);
+
+    impl<T: Config> UncheckedOnRuntimeUpgrade for MigrateToV10<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let expires_at = frame_system::Pallet::<T>::block_number()
+                .saturating_add(T::EnforcementVotingPeriod::get());
+
+            let mut referenda = 0u32;
+
+            EnforcementTracking::<T>::translate_values(
+                |voters: BoundedBTreeSet<T::AccountId, T::MaxControllersPerPermission>| {
+                    referenda = referenda.saturating_add(1);
+                    Some(EnforcementVotes { voters, expires_at })
+                },
+            );
+
+            info!("migrated {referenda} enforcement referenda");
+
+            Weight::zero()
+        }
+    }
+}
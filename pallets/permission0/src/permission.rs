@@ -1,4 +1,7 @@
 use codec::{Decode, Encode, MaxEncodedLen};
+use pallet_permission0_api::{
+    EnforcementReferendum as ApiEnforcementReferendum, EnforcementReferendumInfo,
+};
 use polkadot_sdk::{
     frame_support::{
         CloneNoBound, DebugNoBound, DefaultNoBound, EqNoBound, PartialEqNoBound,
@@ -9,7 +12,7 @@ use polkadot_sdk::{
     sp_core::{H256, U256},
     sp_runtime::{
         BoundedBTreeMap, BoundedVec, DispatchError, Percent,
-        traits::{BlakeTwo256, Hash},
+        traits::{BlakeTwo256, Hash, UniqueSaturatedInto},
     },
     sp_std::{vec, vec::Vec},
     sp_tracing::{error, info, trace},
@@ -19,11 +22,16 @@ use wallet::WalletScope;
 
 use crate::*;
 
-pub use curator::{CuratorPermissions, CuratorScope};
+pub use curator::{CuratorPermissions, CuratorQuota, CuratorScope};
+pub use escrow::EscrowScope;
 pub use namespace::NamespaceScope;
-pub use stream::{DistributionControl, StreamAllocation, StreamScope};
+pub use stream::{
+    DistributionControl, EpochCap, StreamAllocation, StreamDelegation, StreamLimits,
+    StreamOverflow, StreamPayout, StreamScope, SubscriptionFunding, SubscriptionTerms,
+};
 
 pub mod curator;
+pub mod escrow;
 pub mod namespace;
 pub mod stream;
 pub mod wallet;
@@ -249,7 +257,8 @@ impl<T: Config> PermissionContract<T> {
         let recipients = match &self.scope {
             PermissionScope::Curator(CuratorScope { recipient, .. })
             | PermissionScope::Namespace(NamespaceScope { recipient, .. })
-            | PermissionScope::Wallet(WalletScope { recipient, .. }) => {
+            | PermissionScope::Wallet(WalletScope { recipient, .. })
+            | PermissionScope::Escrow(EscrowScope { recipient, .. }) => {
                 vec![recipient.clone()]
             }
             PermissionScope::Stream(StreamScope { recipients, .. }) => {
@@ -302,8 +311,22 @@ impl<T: Config> PermissionContract<T> {
                             if let Some(permission) = permission {
                                 #[allow(clippy::single_match)]
                                 match &mut permission.scope {
-                                    PermissionScope::Stream(StreamScope { recipients, .. }) => {
+                                    PermissionScope::Stream(StreamScope {
+                                        recipients,
+                                        recipient_payouts,
+                                        allocation,
+                                        ..
+                                    }) => {
                                         recipients.remove(caller);
+                                        recipient_payouts.remove(caller);
+
+                                        if let StreamAllocation::Streams(streams) = allocation {
+                                            stream::remove_stream_edges::<T>(
+                                                &delegator,
+                                                streams.keys(),
+                                                core::iter::once(caller),
+                                            );
+                                        }
                                     }
                                     _ => {}
                                 }
@@ -351,7 +374,8 @@ impl<T: Config> PermissionContract<T> {
         match &self.scope {
             PermissionScope::Curator(CuratorScope { recipient, .. })
             | PermissionScope::Namespace(NamespaceScope { recipient, .. })
-            | PermissionScope::Wallet(WalletScope { recipient, .. }) => {
+            | PermissionScope::Wallet(WalletScope { recipient, .. })
+            | PermissionScope::Escrow(EscrowScope { recipient, .. }) => {
                 remove_permission_from_indices::<T>(
                     &self.delegator,
                     core::iter::once(recipient),
@@ -369,11 +393,19 @@ impl<T: Config> PermissionContract<T> {
 
         Permissions::<T>::remove(permission_id);
         RevocationTracking::<T>::remove(permission_id);
+        PausedPermissions::<T>::remove(permission_id);
+        TransferablePermissions::<T>::remove(permission_id);
+        PendingRecipientTransfers::<T>::remove(permission_id);
         let _ = EnforcementTracking::<T>::clear_prefix(permission_id, u32::MAX, None);
 
         match self.scope {
             PermissionScope::Stream(stream) => {
-                stream.cleanup(permission_id, &self.last_execution, &self.delegator);
+                stream.cleanup(
+                    permission_id,
+                    &self.last_execution,
+                    self.execution_count,
+                    &self.delegator,
+                );
             }
             PermissionScope::Curator(curator) => {
                 curator.cleanup(permission_id, &self.last_execution, &self.delegator);
@@ -384,6 +416,9 @@ impl<T: Config> PermissionContract<T> {
             PermissionScope::Wallet(wallet) => {
                 wallet.cleanup(permission_id, &self.last_execution, &self.delegator);
             }
+            PermissionScope::Escrow(escrow) => {
+                escrow.cleanup(permission_id, &self.last_execution, &self.delegator);
+            }
         }
 
         Ok(())
@@ -402,6 +437,7 @@ pub enum PermissionScope<T: Config> {
     Curator(CuratorScope<T>),
     Namespace(NamespaceScope<T>),
     Wallet(WalletScope<T>),
+    Escrow(EscrowScope<T>),
 }
 
 #[derive(
@@ -464,16 +500,105 @@ impl<T: Config> RevocationTerms<T> {
     }
 }
 
+/// Whether the recipient of a permission can hand it over to another agent
+#[derive(Encode, Decode, Clone, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum RecipientTransfer {
+    /// The recipient transfers the permission right away
+    Allowed,
+    /// The delegator must approve each transfer
+    RequiresApproval,
+}
+
+/// Operations that can be applied in bulk
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, TypeInfo, MaxEncodedLen, Debug)]
+pub enum BulkOperation {
+    DelegateStream,
+    Revoke,
+    Execute,
+}
+
 /// Types of enforcement actions that can be voted on
 #[derive(
     Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, TypeInfo, MaxEncodedLen, DebugNoBound,
 )]
 #[scale_info(skip_type_params(T))]
-pub enum EnforcementReferendum {
+pub enum EnforcementReferendum<T: Config> {
     /// Toggle emission accumulation state
     EmissionAccumulation(bool),
     /// Execute the permission
     Execution,
+    /// Pause the permission, blocking its execution and accumulation
+    Pause,
+    /// Resume a paused permission
+    Resume,
+    /// Revoke the permission regardless of its revocation terms
+    Revoke,
+    /// Replace the recipients of a stream permission
+    SetRecipients(BoundedBTreeMap<T::AccountId, u16, T::MaxRecipientsPerPermission>),
+}
+
+impl<T: Config> EnforcementReferendum<T> {
+    fn into_api(self) -> ApiEnforcementReferendum<T::AccountId> {
+        match self {
+            Self::EmissionAccumulation(accumulating) => {
+                ApiEnforcementReferendum::EmissionAccumulation(accumulating)
+            }
+            Self::Execution => ApiEnforcementReferendum::Execution,
+            Self::Pause => ApiEnforcementReferendum::Pause,
+            Self::Resume => ApiEnforcementReferendum::Resume,
+            Self::Revoke => ApiEnforcementReferendum::Revoke,
+            Self::SetRecipients(recipients) => {
+                ApiEnforcementReferendum::SetRecipients(recipients.into_iter().collect())
+            }
+        }
+    }
+}
+
+/// Votes cast on an enforcement referendum
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    EqNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+    DebugNoBound,
+    DefaultNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct EnforcementVotes<T: Config> {
+    pub voters: BoundedBTreeSet<T::AccountId, T::MaxControllersPerPermission>,
+    /// Block at which the votes expire, after which the referendum starts over
+    pub expires_at: BlockNumberFor<T>,
+}
+
+impl<T: Config> EnforcementVotes<T> {
+    /// Whether the voting period of the referendum ended.
+    pub fn is_expired(&self, current_block: BlockNumberFor<T>) -> bool {
+        current_block >= self.expires_at
+    }
+}
+
+/// Lists the enforcement referenda of a permission whose votes did not expire.
+pub fn enforcement_referenda<T: Config>(
+    permission_id: PermissionId,
+) -> Vec<EnforcementReferendumInfo<T::AccountId>> {
+    let current_block = <frame_system::Pallet<T>>::block_number();
+    let required_votes = match Permissions::<T>::get(permission_id).map(|c| c.enforcement) {
+        Some(EnforcementAuthority::ControlledBy { required_votes, .. }) => required_votes,
+        _ => return Vec::new(),
+    };
+
+    EnforcementTracking::<T>::iter_prefix(permission_id)
+        .filter(|(_, votes)| !votes.is_expired(current_block))
+        .map(|(referendum, votes)| EnforcementReferendumInfo {
+            referendum: referendum.into_api(),
+            voters: votes.voters.into_iter().collect(),
+            required_votes,
+            expires_at: votes.expires_at.unique_saturated_into(),
+        })
+        .collect()
 }
 
 /// Defines how a permission's enforcement is controlled
@@ -521,8 +646,10 @@ pub(crate) fn do_auto_permission_execution<T: Config>(current_block: BlockNumber
     );
 
     for (permission_id, contract) in Permissions::<T>::iter() {
-        #[allow(clippy::single_match)]
+        let paused = PausedPermissions::<T>::contains_key(permission_id);
+
         match &contract.scope {
+            _ if paused => (),
             PermissionScope::Stream(stream_scope) => {
                 trace!(target: "auto_permission_execution", "executing auto permission execution for permission {permission_id:?}");
                 if let Err(err) = stream::do_auto_distribution(
@@ -536,10 +663,18 @@ pub(crate) fn do_auto_permission_execution<T: Config>(current_block: BlockNumber
                     );
                 }
             }
+            PermissionScope::Escrow(escrow) => {
+                if let Err(err) =
+                    escrow::do_auto_settlement(escrow, permission_id, current_block, &contract)
+                {
+                    error!("failed to settle escrow permission {permission_id:?}: {err:?}");
+                }
+            }
             _ => (),
         }
 
-        if contract.is_expired(current_block) {
+        // Lapsed subscriptions and settled escrows were already removed above
+        if contract.is_expired(current_block) && Permissions::<T>::contains_key(permission_id) {
             expired.push((permission_id, contract));
         }
     }
@@ -603,7 +738,7 @@ pub(crate) fn add_permission_indices<'a, T: Config>(
 }
 
 /// Remove a permission recipient from storage indices
-fn remove_recipient_from_indices<T: Config>(
+pub(crate) fn remove_recipient_from_indices<T: Config>(
     delegator: &T::AccountId,
     recipient: &T::AccountId,
     permission_id: PermissionId,
@@ -3,7 +3,8 @@ use codec::{Decode, Encode, MaxEncodedLen};
 use polkadot_sdk::{
     frame_support::{CloneNoBound, DebugNoBound, EqNoBound, PartialEqNoBound},
     polkadot_sdk_frame::prelude::BlockNumberFor,
-    sp_runtime::{BoundedBTreeMap, BoundedBTreeSet},
+    sp_core::ConstU32,
+    sp_runtime::{BoundedBTreeMap, BoundedBTreeSet, traits::Saturating},
 };
 use scale_info::TypeInfo;
 
@@ -19,6 +20,8 @@ use super::PermissionId;
     Decode,
     EqNoBound,
     PartialEqNoBound,
+    PartialOrd,
+    Ord,
     TypeInfo,
     MaxEncodedLen,
 )]
@@ -42,6 +45,41 @@ bitflags! {
     }
 }
 
+/// Maximum number of quotas a curator permission can define, one for each
+/// delegatable flag.
+pub const MAX_CURATOR_QUOTAS: u32 = 8;
+
+pub type CuratorQuotas<T> =
+    BoundedBTreeMap<CuratorPermissions, CuratorQuota<T>, ConstU32<MAX_CURATOR_QUOTAS>>;
+
+pub type CuratorQuotaUsages<T> =
+    BoundedBTreeMap<CuratorPermissions, CuratorQuotaUsage<T>, ConstU32<MAX_CURATOR_QUOTAS>>;
+
+/// Limits how many times an action can be performed within a period, e.g. 20
+/// whitelist additions every 1000 blocks.
+#[derive(
+    Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, TypeInfo, MaxEncodedLen, DebugNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct CuratorQuota<T: Config> {
+    /// Number of actions allowed in each period.
+    pub max_actions: u32,
+    /// Length of a period, in blocks.
+    pub period: BlockNumberFor<T>,
+}
+
+/// Actions performed in the current period of a quota.
+#[derive(
+    Encode, Decode, CloneNoBound, PartialEqNoBound, EqNoBound, TypeInfo, MaxEncodedLen, DebugNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct CuratorQuotaUsage<T: Config> {
+    /// Block the current period started at.
+    pub period_start: BlockNumberFor<T>,
+    /// Actions performed since the period started.
+    pub used: u32,
+}
+
 #[derive(Encode, Decode, CloneNoBound, PartialEq, TypeInfo, MaxEncodedLen, DebugNoBound)]
 #[scale_info(skip_type_params(T))]
 pub struct CuratorScope<T: Config> {
@@ -56,12 +94,66 @@ pub struct CuratorScope<T: Config> {
     pub max_instances: u32,
     /// Children permissions
     pub children: BoundedBTreeSet<PermissionId, T::MaxChildrenPerPermission>,
+    /// Quotas for each action, keyed by single flags. Actions without a
+    /// quota are only limited by the cooldown.
+    pub quotas: CuratorQuotas<T>,
+    /// Usage of each quota in its current period.
+    pub quota_usage: CuratorQuotaUsages<T>,
 }
 
 impl<T: Config> CuratorScope<T> {
     pub fn has_permission(&self, permission: CuratorPermissions) -> bool {
         self.flags.iter().any(|(_, p)| p.contains(permission))
     }
+
+    /// The curator permission the given flags were delegated from, if any.
+    pub fn parent_of(&self, permission: CuratorPermissions) -> Option<PermissionId> {
+        self.flags
+            .iter()
+            .find(|(_, p)| p.contains(permission))
+            .and_then(|(parent, _)| *parent)
+    }
+
+    /// Number of times the action can still be performed in the current
+    /// period. `None` if the action has no quota.
+    pub fn remaining_quota(
+        &self,
+        action: CuratorPermissions,
+        now: BlockNumberFor<T>,
+    ) -> Option<u32> {
+        let quota = self.quotas.get(&action)?;
+        let used = self
+            .quota_usage
+            .get(&action)
+            .filter(|usage| usage.period_start.saturating_add(quota.period) > now)
+            .map_or(0, |usage| usage.used);
+
+        Some(quota.max_actions.saturating_sub(used))
+    }
+
+    /// Records an action against its quota, starting a new period if the
+    /// previous one is over.
+    pub(crate) fn consume_quota(&mut self, action: CuratorPermissions, now: BlockNumberFor<T>) {
+        let Some(quota) = self.quotas.get(&action) else {
+            return;
+        };
+
+        let usage = match self.quota_usage.get(&action) {
+            Some(usage) if usage.period_start.saturating_add(quota.period) > now => {
+                CuratorQuotaUsage {
+                    period_start: usage.period_start,
+                    used: usage.used.saturating_add(1),
+                }
+            }
+            _ => CuratorQuotaUsage {
+                period_start: now,
+                used: 1,
+            },
+        };
+
+        // Usages are keyed by the quota flags, so the map never exceeds its bound.
+        let _ = self.quota_usage.try_insert(action, usage);
+    }
 }
 
 impl<T: Config> CuratorScope<T> {
@@ -0,0 +1,158 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use polkadot_sdk::{
+    frame_support::{
+        CloneNoBound, DebugNoBound, PartialEqNoBound,
+        dispatch::DispatchResult,
+        traits::{Currency, ExistenceRequirement, ReservableCurrency},
+    },
+    frame_system,
+    polkadot_sdk_frame::prelude::BlockNumberFor,
+    sp_runtime::{
+        BoundedBTreeSet, BoundedVec,
+        traits::{Saturating, Zero},
+    },
+};
+use scale_info::TypeInfo;
+
+use crate::{
+    BalanceOf, Config, EscrowApprovals, Event, Pallet, PermissionContract, PermissionScope,
+    Permissions,
+};
+
+use super::PermissionId;
+
+/// Escrowed funds released to the recipient in milestones
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, TypeInfo, MaxEncodedLen, DebugNoBound)]
+#[scale_info(skip_type_params(T))]
+pub struct EscrowScope<T: Config> {
+    pub recipient: T::AccountId,
+    /// Amounts released by each milestone, in order
+    pub milestones: BoundedVec<BalanceOf<T>, T::MaxEscrowMilestones>,
+    /// Number of milestones already released
+    pub released: u32,
+    /// Accounts that can approve milestones on behalf of the delegator
+    pub arbiters: BoundedBTreeSet<T::AccountId, T::MaxRevokersPerPermission>,
+    /// Arbiter approvals required to release a milestone
+    pub required_votes: u32,
+    /// Blocks without a release after which the unreleased funds
+    /// are returned to the delegator
+    pub dispute_timeout: BlockNumberFor<T>,
+}
+
+impl<T: Config> EscrowScope<T> {
+    /// Returns the next milestone to be released, with its index.
+    pub fn next_milestone(&self) -> Option<(u32, BalanceOf<T>)> {
+        let amount = self.milestones.get(self.released as usize)?;
+        Some((self.released, *amount))
+    }
+
+    /// Returns the sum of the milestones not yet released.
+    pub fn unreleased_amount(&self) -> BalanceOf<T> {
+        self.milestones
+            .iter()
+            .skip(self.released as usize)
+            .fold(BalanceOf::<T>::zero(), |acc, amount| {
+                acc.saturating_add(*amount)
+            })
+    }
+
+    /// Whether the escrow went `dispute_timeout` blocks without a release.
+    pub fn is_timed_out(
+        &self,
+        last_release: BlockNumberFor<T>,
+        current_block: BlockNumberFor<T>,
+    ) -> bool {
+        current_block.saturating_sub(last_release) >= self.dispute_timeout
+    }
+
+    /// Cleanup operations when permission is revoked, expired or settled
+    pub(crate) fn cleanup(
+        &self,
+        permission_id: PermissionId,
+        _last_execution: &Option<BlockNumberFor<T>>,
+        delegator: &T::AccountId,
+    ) {
+        T::Currency::unreserve(delegator, self.unreleased_amount());
+        EscrowApprovals::<T>::remove(permission_id);
+    }
+}
+
+/// Releases the next milestone of the escrow to its recipient, settling the
+/// permission once every milestone was released.
+pub(crate) fn release_milestone<T: Config>(
+    permission_id: PermissionId,
+    mut contract: PermissionContract<T>,
+) -> DispatchResult {
+    let PermissionScope::Escrow(escrow) = &mut contract.scope else {
+        return Ok(());
+    };
+
+    let Some((milestone, amount)) = escrow.next_milestone() else {
+        return Ok(());
+    };
+
+    T::Currency::unreserve(&contract.delegator, amount);
+    T::Currency::transfer(
+        &contract.delegator,
+        &escrow.recipient,
+        amount,
+        ExistenceRequirement::KeepAlive,
+    )?;
+
+    escrow.released = escrow.released.saturating_add(1);
+    let completed = escrow.next_milestone().is_none();
+
+    EscrowApprovals::<T>::remove(permission_id);
+
+    Pallet::<T>::deposit_event(Event::EscrowMilestoneReleased {
+        permission_id,
+        milestone,
+        amount,
+    });
+
+    contract.tick_execution(<frame_system::Pallet<T>>::block_number())?;
+
+    if completed {
+        return settle(permission_id, contract);
+    }
+
+    Permissions::<T>::insert(permission_id, contract);
+
+    Ok(())
+}
+
+/// Returns the unreleased funds of escrows that went `dispute_timeout`
+/// blocks without a release.
+pub(crate) fn do_auto_settlement<T: Config>(
+    escrow: &EscrowScope<T>,
+    permission_id: PermissionId,
+    current_block: BlockNumberFor<T>,
+    contract: &PermissionContract<T>,
+) -> DispatchResult {
+    let last_release = contract.last_execution().unwrap_or(contract.created_at);
+    if !escrow.is_timed_out(last_release, current_block) {
+        return Ok(());
+    }
+
+    settle(permission_id, contract.clone())
+}
+
+fn settle<T: Config>(
+    permission_id: PermissionId,
+    contract: PermissionContract<T>,
+) -> DispatchResult {
+    let (released, refunded) = match &contract.scope {
+        PermissionScope::Escrow(escrow) => (escrow.released, escrow.unreleased_amount()),
+        _ => return Ok(()),
+    };
+
+    contract.cleanup(permission_id)?;
+
+    Pallet::<T>::deposit_event(Event::EscrowSettled {
+        permission_id,
+        released,
+        refunded,
+    });
+
+    Ok(())
+}
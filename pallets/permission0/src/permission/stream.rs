@@ -1,10 +1,16 @@
+use pallet_permission0_api::StreamEdge;
+use pallet_torus0_api::Torus0Api;
 use polkadot_sdk::{
-    frame_support::traits::{
-        Currency, ExistenceRequirement, Imbalance, ReservableCurrency, WithdrawReasons,
+    frame_support::{
+        traits::{
+            Currency, ExistenceRequirement, Get, Imbalance, ReservableCurrency, WithdrawReasons,
+        },
+        weights::Weight,
     },
     frame_system,
     sp_arithmetic::FixedU128,
     sp_runtime::traits::{Saturating, Zero},
+    sp_std::collections::{btree_map::BTreeMap, btree_set::BTreeSet},
 };
 
 use super::*;
@@ -30,6 +36,12 @@ pub struct StreamScope<T: Config> {
     /// An account responsible for updating the weights of existing recipients. Useful
     /// for third-party agents to manage how the streams will be distributed.
     pub weight_setters: BoundedBTreeSet<T::AccountId, T::MaxControllersPerPermission>,
+    /// Caps on how much the streams can accumulate
+    pub limits: StreamLimits<T>,
+    /// How recipients want to receive their share. Recipients without an entry
+    /// are paid as free balance.
+    pub recipient_payouts:
+        BoundedBTreeMap<T::AccountId, StreamPayout<T::AccountId>, T::MaxRecipientsPerPermission>,
 }
 
 impl<T: Config> StreamScope<T> {
@@ -37,17 +49,29 @@ impl<T: Config> StreamScope<T> {
         self,
         permission_id: H256,
         last_executed: &Option<BlockNumberFor<T>>,
+        execution_count: u32,
         delegator: &T::AccountId,
     ) {
         match self.allocation {
             StreamAllocation::Streams(streams) => {
+                remove_stream_edges::<T>(delegator, streams.keys(), self.recipients.keys());
+
                 for stream in streams.keys() {
                     AccumulatedStreamAmounts::<T>::remove((delegator, stream, &permission_id));
                 }
+
+                StreamLimitUsage::<T>::remove(permission_id);
             }
             StreamAllocation::FixedAmount(amount) if last_executed.is_none() => {
                 T::Currency::unreserve(delegator, amount);
             }
+            StreamAllocation::Subscription(terms) => {
+                if terms.funding == SubscriptionFunding::Reserved {
+                    T::Currency::unreserve(delegator, terms.remaining_amount(execution_count));
+                }
+
+                SubscriptionArrears::<T>::remove(permission_id);
+            }
             _ => {}
         }
     }
@@ -61,6 +85,186 @@ pub enum StreamAllocation<T: Config> {
     Streams(BoundedBTreeMap<StreamId, Percent, T::MaxStreamsPerPermission>),
     /// Permission applies to a specific fixed amount
     FixedAmount(BalanceOf<T>),
+    /// Permission pays a fixed amount on every distribution interval
+    Subscription(SubscriptionTerms<T>),
+}
+
+/// Terms of a recurring fixed-amount stream
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, TypeInfo, MaxEncodedLen, DebugNoBound)]
+#[scale_info(skip_type_params(T))]
+pub struct SubscriptionTerms<T: Config> {
+    /// Amount paid on each interval
+    pub amount: BalanceOf<T>,
+    /// Number of payments after which the subscription stops paying
+    pub max_payments: u32,
+    /// Where payments are taken from
+    pub funding: SubscriptionFunding,
+    /// Blocks a missed payment can be retried before it lapses
+    pub grace_period: BlockNumberFor<T>,
+    /// Whether the permission is revoked when a payment lapses
+    pub auto_revoke: bool,
+}
+
+impl<T: Config> SubscriptionTerms<T> {
+    /// Returns the amount still owed after `payments` were made.
+    pub fn remaining_amount(&self, payments: u32) -> BalanceOf<T> {
+        let remaining = self.max_payments.saturating_sub(payments);
+        self.amount.saturating_mul(remaining.into())
+    }
+}
+
+#[derive(
+    Encode, Decode, Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, TypeInfo, MaxEncodedLen,
+)]
+pub enum SubscriptionFunding {
+    /// Each payment is withdrawn from the delegator free balance when due
+    Pull,
+    /// All payments are reserved upfront on permission creation
+    Reserved,
+}
+
+/// Defines how a recipient receives its share of a distribution
+#[derive(
+    Encode, Decode, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, TypeInfo, MaxEncodedLen,
+)]
+pub enum StreamPayout<AccountId> {
+    /// Paid as free balance
+    #[default]
+    FreeBalance,
+    /// Staked to the recipient itself
+    StakeToSelf,
+    /// Staked to the given agent
+    StakeTo(AccountId),
+}
+
+/// Limits the exposure of a percentage stream permission
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+    DebugNoBound,
+    DefaultNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct StreamLimits<T: Config> {
+    /// Maximum amount accumulated over the lifetime of the permission
+    pub lifetime_cap: Option<BalanceOf<T>>,
+    /// Maximum amount accumulated in a single epoch
+    pub epoch_cap: Option<EpochCap<T>>,
+    /// Destination of the amounts above the caps
+    pub overflow: StreamOverflow,
+}
+
+impl<T: Config> StreamLimits<T> {
+    pub fn is_unlimited(&self) -> bool {
+        self.lifetime_cap.is_none() && self.epoch_cap.is_none()
+    }
+
+    /// Records up to `amount` in the usage and returns how much fits in the caps.
+    pub(crate) fn accept(
+        &self,
+        usage: &mut StreamUsage<T>,
+        amount: BalanceOf<T>,
+        current_block: BlockNumberFor<T>,
+    ) -> BalanceOf<T> {
+        let mut accepted = amount;
+
+        if let Some(cap) = self.lifetime_cap {
+            accepted = accepted.min(cap.saturating_sub(usage.total));
+        }
+
+        if let Some(EpochCap {
+            length,
+            amount: cap,
+        }) = self.epoch_cap
+        {
+            if current_block.saturating_sub(usage.epoch_start) >= length {
+                usage.epoch_start = current_block;
+                usage.epoch_amount = Zero::zero();
+            }
+
+            accepted = accepted.min(cap.saturating_sub(usage.epoch_amount));
+        }
+
+        usage.total = usage.total.saturating_add(accepted);
+        usage.epoch_amount = usage.epoch_amount.saturating_add(accepted);
+
+        accepted
+    }
+
+    /// Whether the lifetime cap was used up.
+    pub fn is_exhausted(&self, usage: &StreamUsage<T>) -> bool {
+        self.lifetime_cap.is_some_and(|cap| usage.total >= cap)
+    }
+}
+
+#[derive(Encode, Decode, CloneNoBound, PartialEqNoBound, TypeInfo, MaxEncodedLen, DebugNoBound)]
+#[scale_info(skip_type_params(T))]
+pub struct EpochCap<T: Config> {
+    /// Epoch length in blocks
+    pub length: BlockNumberFor<T>,
+    /// Maximum amount accumulated per epoch
+    pub amount: BalanceOf<T>,
+}
+
+#[derive(
+    Encode,
+    Decode,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    TypeInfo,
+    MaxEncodedLen,
+)]
+pub enum StreamOverflow {
+    /// Overflow stays with the delegator and is staked
+    #[default]
+    ReturnToDelegator,
+    /// Overflow is accumulated by the delegator's permissions over the given stream
+    Forward(StreamId),
+}
+
+/// Amounts accumulated by a permission with stream limits
+#[derive(
+    Encode,
+    Decode,
+    CloneNoBound,
+    PartialEqNoBound,
+    TypeInfo,
+    MaxEncodedLen,
+    DebugNoBound,
+    DefaultNoBound,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct StreamUsage<T: Config> {
+    /// Total accumulated over the lifetime of the permission
+    pub total: BalanceOf<T>,
+    /// Block at which the current epoch started
+    pub epoch_start: BlockNumberFor<T>,
+    /// Accumulated in the current epoch
+    pub epoch_amount: BalanceOf<T>,
+}
+
+/// A single stream delegation of a bulk call
+#[derive(Encode, Decode, CloneNoBound, PartialEq, TypeInfo, MaxEncodedLen, DebugNoBound)]
+#[scale_info(skip_type_params(T))]
+pub struct StreamDelegation<T: Config> {
+    pub recipients: BoundedBTreeMap<T::AccountId, u16, T::MaxRecipientsPerPermission>,
+    pub allocation: StreamAllocation<T>,
+    pub distribution: DistributionControl<T>,
+    pub duration: PermissionDuration<T>,
+    pub revocation: RevocationTerms<T>,
+    pub enforcement: EnforcementAuthority<T>,
+    pub recipient_manager: Option<T::AccountId>,
+    pub weight_setter: Option<T::AccountId>,
 }
 
 #[derive(Encode, Decode, CloneNoBound, PartialEq, TypeInfo, MaxEncodedLen, DebugNoBound)]
@@ -81,6 +285,18 @@ pub(crate) fn do_accumulate_streams<T: Config>(
     agent: &T::AccountId,
     stream_id: &StreamId,
     imbalance: &mut NegativeImbalanceOf<T>,
+) {
+    accumulate_streams::<T>(agent, stream_id, imbalance, true);
+}
+
+/// Overflows are only forwarded once, amounts overflowing the fallback stream
+/// stay with the agent. This keeps fallback streams pointing at each other
+/// from bouncing emissions back and forth.
+fn accumulate_streams<T: Config>(
+    agent: &T::AccountId,
+    stream_id: &StreamId,
+    imbalance: &mut NegativeImbalanceOf<T>,
+    forward_overflow: bool,
 ) {
     let initial_balance = imbalance.peek();
     let total_initial_amount =
@@ -89,6 +305,8 @@ pub(crate) fn do_accumulate_streams<T: Config>(
         return;
     }
 
+    let current_block = <frame_system::Pallet<T>>::block_number();
+
     let streams = AccumulatedStreamAmounts::<T>::iter_prefix((agent, stream_id));
     for (permission_id, accumulated) in streams {
         let Some(contract) = Permissions::<T>::get(permission_id) else {
@@ -100,13 +318,14 @@ pub(crate) fn do_accumulate_streams<T: Config>(
         let PermissionScope::Stream(StreamScope {
             allocation: StreamAllocation::Streams(streams),
             accumulating,
+            limits,
             ..
         }) = contract.scope
         else {
             continue;
         };
 
-        if !accumulating {
+        if !accumulating || PausedPermissions::<T>::contains_key(permission_id) {
             continue;
         }
 
@@ -119,9 +338,35 @@ pub(crate) fn do_accumulate_streams<T: Config>(
             continue;
         }
 
-        let delegated_amount = imbalance
-            .extract(delegated_amount.try_into().unwrap_or_default())
-            .peek();
+        let delegated_amount: BalanceOf<T> = delegated_amount.try_into().unwrap_or_default();
+        let delegated_amount = if limits.is_unlimited() {
+            delegated_amount
+        } else {
+            let mut usage = StreamLimitUsage::<T>::get(permission_id);
+            let accepted = limits.accept(&mut usage, delegated_amount, current_block);
+            StreamLimitUsage::<T>::set(permission_id, usage);
+
+            let overflow = delegated_amount.saturating_sub(accepted);
+            if !overflow.is_zero() {
+                do_overflow_stream::<T>(
+                    agent,
+                    permission_id,
+                    stream_id,
+                    &limits.overflow,
+                    imbalance,
+                    overflow,
+                    forward_overflow,
+                );
+            }
+
+            accepted
+        };
+
+        if delegated_amount.is_zero() {
+            continue;
+        }
+
+        let delegated_amount = imbalance.extract(delegated_amount).peek();
 
         AccumulatedStreamAmounts::<T>::set(
             (agent, stream_id, &permission_id),
@@ -136,6 +381,37 @@ pub(crate) fn do_accumulate_streams<T: Config>(
     }
 }
 
+/// Routes the amount a permission could not accumulate due to its limits.
+/// Overflow returned to the agent is simply left in the imbalance, which is
+/// staked by the emission pallet.
+fn do_overflow_stream<T: Config>(
+    agent: &T::AccountId,
+    permission_id: PermissionId,
+    stream_id: &StreamId,
+    overflow: &StreamOverflow,
+    imbalance: &mut NegativeImbalanceOf<T>,
+    amount: BalanceOf<T>,
+    forward_overflow: bool,
+) {
+    let forwarded_to = match overflow {
+        StreamOverflow::Forward(fallback) if forward_overflow && fallback != stream_id => {
+            let mut forwarded = imbalance.extract(amount);
+            accumulate_streams::<T>(agent, fallback, &mut forwarded, false);
+            imbalance.subsume(forwarded);
+
+            Some(*fallback)
+        }
+        _ => None,
+    };
+
+    Pallet::<T>::deposit_event(Event::StreamOverflowed {
+        permission_id,
+        stream_id: *stream_id,
+        amount,
+        forwarded_to,
+    });
+}
+
 pub(crate) fn do_auto_distribution<T: Config>(
     stream_scope: &StreamScope<T>,
     permission_id: H256,
@@ -152,6 +428,7 @@ pub(crate) fn do_auto_distribution<T: Config>(
                     })
                     .fold(BalanceOf::<T>::zero(), |acc, e| acc.saturating_add(e)), // The Balance AST does not enforce the Sum trait
                 StreamAllocation::FixedAmount(amount) => *amount,
+                StreamAllocation::Subscription(terms) => terms.amount,
             };
 
             if accumulated >= threshold {
@@ -241,7 +518,9 @@ pub(crate) fn do_distribute_stream<T: Config>(
                 }
             });
 
+            let mut distributed = false;
             for (stream, mut imbalance) in streams {
+                distributed = true;
                 do_distribute_to_targets(
                     &mut imbalance,
                     permission_id,
@@ -259,6 +538,14 @@ pub(crate) fn do_distribute_stream<T: Config>(
                     );
                 }
             }
+
+            if distributed
+                && stream_scope
+                    .limits
+                    .is_exhausted(&StreamLimitUsage::<T>::get(permission_id))
+            {
+                Pallet::<T>::deposit_event(Event::StreamLifetimeCapReached { permission_id });
+            }
         }
         StreamAllocation::FixedAmount(amount) => {
             if contract.last_execution().is_some() {
@@ -285,10 +572,116 @@ pub(crate) fn do_distribute_stream<T: Config>(
                 reason,
             );
         }
+        StreamAllocation::Subscription(terms) => {
+            if contract.execution_count() >= terms.max_payments {
+                // All payments of the subscription were already made
+                return Ok(());
+            }
+
+            if terms.funding == SubscriptionFunding::Reserved {
+                let _ = T::Currency::unreserve(&contract.delegator, terms.amount);
+            }
+
+            let Ok(mut imbalance) = T::Currency::withdraw(
+                &contract.delegator,
+                terms.amount,
+                WithdrawReasons::TRANSFER,
+                ExistenceRequirement::KeepAlive,
+            ) else {
+                if terms.funding == SubscriptionFunding::Reserved {
+                    let _ = T::Currency::reserve(&contract.delegator, terms.amount);
+                }
+
+                return do_handle_missed_payment::<T>(permission_id, contract, terms);
+            };
+
+            SubscriptionArrears::<T>::remove(permission_id);
+
+            do_distribute_to_targets(
+                &mut imbalance,
+                permission_id,
+                stream_scope,
+                None,
+                total_weight,
+                reason,
+            );
+        }
+    }
+
+    let Some(mut contract) = Permissions::<T>::get(permission_id) else {
+        return Ok(());
+    };
+
+    contract.tick_execution(<frame_system::Pallet<T>>::block_number())?;
+
+    if let PermissionScope::Stream(StreamScope {
+        allocation: StreamAllocation::Subscription(terms),
+        ..
+    }) = &contract.scope
+        && contract.execution_count() >= terms.max_payments
+    {
+        // The final payment was made, nothing is left for the permission to do
+        let delegator = contract.delegator.clone();
+        contract.cleanup(permission_id)?;
+
+        Pallet::<T>::deposit_event(Event::PermissionExpired {
+            delegator,
+            permission_id,
+        });
+
+        return Ok(());
+    }
+
+    Permissions::<T>::set(permission_id, Some(contract));
+
+    Ok(())
+}
+
+/// Tracks a subscription payment the delegator could not afford. The payment
+/// is retried until the grace period ends, after which it lapses: the permission
+/// is either revoked or the payment is skipped until the next interval.
+fn do_handle_missed_payment<T: Config>(
+    permission_id: PermissionId,
+    contract: &PermissionContract<T>,
+    terms: &SubscriptionTerms<T>,
+) -> DispatchResult {
+    let current_block = <frame_system::Pallet<T>>::block_number();
+
+    let missed_at = match SubscriptionArrears::<T>::get(permission_id) {
+        Some(missed_at) => missed_at,
+        None => {
+            SubscriptionArrears::<T>::insert(permission_id, current_block);
+
+            Pallet::<T>::deposit_event(Event::SubscriptionPaymentMissed {
+                permission_id,
+                amount: terms.amount,
+                grace_period_ends: current_block.saturating_add(terms.grace_period),
+            });
+
+            current_block
+        }
+    };
+
+    if current_block.saturating_sub(missed_at) < terms.grace_period {
+        return Ok(());
+    }
+
+    SubscriptionArrears::<T>::remove(permission_id);
+
+    Pallet::<T>::deposit_event(Event::SubscriptionPaymentLapsed {
+        permission_id,
+        revoked: terms.auto_revoke,
+    });
+
+    if terms.auto_revoke {
+        return contract
+            .clone()
+            .revoke(RawOrigin::Root.into(), permission_id);
     }
 
     if let Some(mut contract) = Permissions::<T>::get(permission_id) {
-        contract.tick_execution(<frame_system::Pallet<T>>::block_number())?;
+        // Skipped payments do not count towards the maximum
+        contract.last_execution = Some(current_block);
         Permissions::<T>::set(permission_id, Some(contract));
     }
 
@@ -331,8 +724,23 @@ fn do_distribute_to_targets<T: Config>(
             do_accumulate_streams::<T>(target, stream, &mut imbalance);
         }
 
+        let payout = imbalance.peek();
         T::Currency::resolve_creating(target, imbalance);
 
+        let staked = match stream_scope.recipient_payouts.get(target) {
+            Some(StreamPayout::StakeToSelf) => Some(target),
+            Some(StreamPayout::StakeTo(agent)) => Some(agent),
+            Some(StreamPayout::FreeBalance) | None => None,
+        };
+
+        if let Some(staked) = staked
+            && !payout.is_zero()
+            && let Err(err) = T::Torus::stake_to(target, staked, payout)
+        {
+            // The payout stays as free balance if the stake cannot be added
+            error!("failed to stake {payout:?} from {target:?} to {staked:?}: {err:?}");
+        }
+
         Pallet::<T>::deposit_event(Event::StreamDistribution {
             permission_id,
             stream_id: stream.cloned(),
@@ -342,3 +750,163 @@ fn do_distribute_to_targets<T: Config>(
         });
     }
 }
+
+/// Records that `delegator` streams to `recipients` over `streams`.
+pub(crate) fn add_stream_edges<'a, T: Config>(
+    delegator: &T::AccountId,
+    streams: impl Iterator<Item = &'a StreamId>,
+    recipients: impl Iterator<Item = &'a T::AccountId> + Clone,
+) {
+    for stream in streams {
+        for recipient in recipients.clone() {
+            StreamGraph::<T>::mutate((stream, delegator, recipient), |count| {
+                if *count == 0 {
+                    ReverseStreamGraph::<T>::insert((stream, recipient, delegator), ());
+                }
+                *count = count.saturating_add(1)
+            });
+        }
+    }
+}
+
+/// Removes the edges added by [`add_stream_edges`].
+pub(crate) fn remove_stream_edges<'a, T: Config>(
+    delegator: &T::AccountId,
+    streams: impl Iterator<Item = &'a StreamId>,
+    recipients: impl Iterator<Item = &'a T::AccountId> + Clone,
+) {
+    for stream in streams {
+        for recipient in recipients.clone() {
+            StreamGraph::<T>::mutate_exists((stream, delegator, recipient), |count| {
+                *count = count
+                    .map(|count| count.saturating_sub(1))
+                    .filter(|count| *count > 0);
+                if count.is_none() {
+                    ReverseStreamGraph::<T>::remove((stream, recipient, delegator));
+                }
+            });
+        }
+    }
+}
+
+/// Ensures that `delegator` streaming to `recipients` over `streams` does not
+/// close a cycle, and that no chain of stream delegations grows deeper than
+/// [`Config::MaxStreamDelegationDepth`]. The rest of the graph is already
+/// valid, so only the chains through the new edges are walked: up from the
+/// delegator and down from the recipients, visiting at most
+/// [`Config::MaxStreamGraphWalk`] accounts.
+pub(crate) fn ensure_valid_stream_graph<'a, T: Config>(
+    delegator: &T::AccountId,
+    streams: impl Iterator<Item = &'a StreamId>,
+    recipients: impl Iterator<Item = &'a T::AccountId> + Clone,
+) -> DispatchResult {
+    let max_depth = T::MaxStreamDelegationDepth::get();
+    let mut budget = T::MaxStreamGraphWalk::get();
+
+    for stream in streams {
+        let mut walk = StreamGraphWalk::<T> {
+            stream,
+            upstream: true,
+            cycle_at: None,
+            lengths: BTreeMap::new(),
+            budget: &mut budget,
+        };
+        let upstream = walk.chain_length(delegator, max_depth)?;
+        let remaining = max_depth
+            .checked_sub(upstream.saturating_add(1))
+            .ok_or(Error::<T>::StreamDepthExceeded)?;
+
+        let mut walk = StreamGraphWalk::<T> {
+            stream,
+            upstream: false,
+            cycle_at: Some(delegator),
+            lengths: BTreeMap::new(),
+            budget: &mut budget,
+        };
+        for recipient in recipients.clone() {
+            ensure!(recipient != delegator, Error::<T>::StreamCycleDetected);
+            walk.chain_length(recipient, remaining)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Weight of the stream graph walk done by [`ensure_valid_stream_graph`]:
+/// reading the edges of up to [`Config::MaxStreamGraphWalk`] accounts.
+pub fn stream_graph_walk_weight<T: Config>() -> Weight {
+    T::DbWeight::get().reads(T::MaxStreamGraphWalk::get().into())
+}
+
+/// Walk over the delegation chains of a single stream, following edges
+/// either up to the delegators or down to the recipients.
+struct StreamGraphWalk<'a, T: Config> {
+    stream: &'a StreamId,
+    upstream: bool,
+    /// Account closing a cycle if reached.
+    cycle_at: Option<&'a T::AccountId>,
+    /// Length of the longest chain from each visited account.
+    lengths: BTreeMap<T::AccountId, u32>,
+    /// Accounts that can still be visited.
+    budget: &'a mut u32,
+}
+
+impl<T: Config> StreamGraphWalk<'_, T> {
+    /// Returns the length of the longest chain from `node`, failing once it
+    /// exceeds `limit`.
+    fn chain_length(&mut self, node: &T::AccountId, limit: u32) -> Result<u32, DispatchError> {
+        if let Some(length) = self.lengths.get(node) {
+            ensure!(*length <= limit, Error::<T>::StreamDepthExceeded);
+            return Ok(*length);
+        }
+
+        *self.budget = self
+            .budget
+            .checked_sub(1)
+            .ok_or(Error::<T>::StreamGraphTooLarge)?;
+
+        let next: Vec<_> = if self.upstream {
+            ReverseStreamGraph::<T>::iter_key_prefix((self.stream, node)).collect()
+        } else {
+            StreamGraph::<T>::iter_key_prefix((self.stream, node)).collect()
+        };
+
+        let mut length = 0u32;
+        for next in next {
+            ensure!(
+                self.cycle_at != Some(&next),
+                Error::<T>::StreamCycleDetected
+            );
+
+            let next_limit = limit
+                .checked_sub(1)
+                .ok_or(Error::<T>::StreamDepthExceeded)?;
+            let next_length = self.chain_length(&next, next_limit)?;
+            length = length.max(next_length.saturating_add(1));
+        }
+
+        self.lengths.insert(node.clone(), length);
+
+        Ok(length)
+    }
+}
+
+/// Lists the edges of the stream graph, optionally limited to a single stream.
+pub fn stream_graph<T: Config>(stream_id: Option<StreamId>) -> Vec<StreamEdge<T::AccountId>> {
+    let into_edge = |((stream_id, delegator, recipient), permissions)| StreamEdge {
+        stream_id,
+        delegator,
+        recipient,
+        permissions,
+    };
+
+    match stream_id {
+        Some(stream_id) => StreamGraph::<T>::iter_prefix((stream_id,))
+            .map(|((delegator, recipient), permissions)| {
+                ((stream_id, delegator, recipient), permissions)
+            })
+            .map(into_edge)
+            .collect(),
+        None => StreamGraph::<T>::iter().map(into_edge).collect(),
+    }
+}
@@ -47,6 +47,14 @@ pub trait WeightInfo {
 	fn delegate_curator_permission() -> Weight;
 	fn update_namespace_permission() -> Weight;
 	fn delegate_namespace_permission() -> Weight;
+	fn bulk_delegate_stream_permission(n: u32, ) -> Weight;
+	fn bulk_revoke_permissions(n: u32, ) -> Weight;
+	fn bulk_execute_permissions(n: u32, ) -> Weight;
+	fn update_stream_limits() -> Weight;
+	fn set_stream_payout() -> Weight;
+	fn set_permission_transferable() -> Weight;
+	fn transfer_permission_recipient(n: u32, ) -> Weight;
+	fn approve_permission_transfer(n: u32, ) -> Weight;
 }
 
 /// Weights for `pallet_permission0` using the Substrate node and recommended hardware.
@@ -199,6 +207,170 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().writes(5_u64))
 	}
+	/// Storage: `Torus0::Agents` (r:21 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::Permissions` (r:20 w:20)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::AccumulatedStreamAmounts` (r:20 w:20)
+	/// Proof: `Permission0::AccumulatedStreamAmounts` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:1 w:1)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 20]`.
+	fn bulk_delegate_stream_permission(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `275 + n * (42 ±0)`
+		//  Estimated: `6699 + n * (70364 ±0)`
+		// Minimum execution time: 29_000_000 picoseconds.
+		Weight::from_parts(3_000_000, 6699)
+			.saturating_add(Weight::from_parts(26_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:20 w:20)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:1 w:1)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::AccumulatedStreamAmounts` (r:0 w:20)
+	/// Proof: `Permission0::AccumulatedStreamAmounts` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::RevocationTracking` (r:0 w:20)
+	/// Proof: `Permission0::RevocationTracking` (`max_values`: None, `max_size`: Some(353), added: 2828, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 20]`.
+	fn bulk_revoke_permissions(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `308 + n * (317 ±0)`
+		//  Estimated: `6699 + n * (70364 ±0)`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(2_000_000, 6699)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+			.saturating_add(T::DbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:20 w:20)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::AccumulatedStreamAmounts` (r:40 w:20)
+	/// Proof: `Permission0::AccumulatedStreamAmounts` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 20]`.
+	fn bulk_execute_permissions(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `263 + n * (317 ±0)`
+		//  Estimated: `3593 + n * (70364 ±0)`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(2_000_000, 3593)
+			.saturating_add(Weight::from_parts(33_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:1 w:1)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	fn update_stream_limits() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `317`
+		//  Estimated: `71354`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 71354)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::Permissions` (r:1 w:1)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	fn set_stream_payout() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `446`
+		//  Estimated: `71354`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 71354)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Permission0::Permissions` (r:1 w:0)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::TransferablePermissions` (r:0 w:1)
+	/// Proof: `Permission0::TransferablePermissions` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PendingRecipientTransfers` (r:0 w:1)
+	/// Proof: `Permission0::PendingRecipientTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	fn set_permission_transferable() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `529`
+		//  Estimated: `71354`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 71354)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Permission0::Permissions` (r:17 w:17)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::TransferablePermissions` (r:1 w:0)
+	/// Proof: `Permission0::TransferablePermissions` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PendingRecipientTransfers` (r:0 w:1)
+	/// Proof: `Permission0::PendingRecipientTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:34 w:34)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:2 w:2)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:3 w:3)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 16]`.
+	fn transfer_permission_recipient(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1017 + n * (352 ±0)`
+		//  Estimated: `77264 + n * (70364 ±0)`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(38_000_000, 77264)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:17 w:17)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PendingRecipientTransfers` (r:1 w:1)
+	/// Proof: `Permission0::PendingRecipientTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:34 w:34)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:2 w:2)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:3 w:3)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 16]`.
+	fn approve_permission_transfer(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1081 + n * (352 ±0)`
+		//  Estimated: `77264 + n * (70364 ±0)`
+		// Minimum execution time: 39_000_000 picoseconds.
+		Weight::from_parts(39_000_000, 77264)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(7_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(5_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -350,4 +522,168 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(12_u64))
 			.saturating_add(RocksDbWeight::get().writes(5_u64))
 	}
+	/// Storage: `Torus0::Agents` (r:21 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::Permissions` (r:20 w:20)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::AccumulatedStreamAmounts` (r:20 w:20)
+	/// Proof: `Permission0::AccumulatedStreamAmounts` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:1 w:1)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 20]`.
+	fn bulk_delegate_stream_permission(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `275 + n * (42 ±0)`
+		//  Estimated: `6699 + n * (70364 ±0)`
+		// Minimum execution time: 29_000_000 picoseconds.
+		Weight::from_parts(3_000_000, 6699)
+			.saturating_add(Weight::from_parts(26_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().reads((5_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((4_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:20 w:20)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:20 w:20)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:1 w:1)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::AccumulatedStreamAmounts` (r:0 w:20)
+	/// Proof: `Permission0::AccumulatedStreamAmounts` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::RevocationTracking` (r:0 w:20)
+	/// Proof: `Permission0::RevocationTracking` (`max_values`: None, `max_size`: Some(353), added: 2828, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 20]`.
+	fn bulk_revoke_permissions(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `308 + n * (317 ±0)`
+		//  Estimated: `6699 + n * (70364 ±0)`
+		// Minimum execution time: 27_000_000 picoseconds.
+		Weight::from_parts(2_000_000, 6699)
+			.saturating_add(Weight::from_parts(24_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+			.saturating_add(RocksDbWeight::get().writes((5_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:20 w:20)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::AccumulatedStreamAmounts` (r:40 w:20)
+	/// Proof: `Permission0::AccumulatedStreamAmounts` (`max_values`: None, `max_size`: Some(112), added: 2587, mode: `MaxEncodedLen`)
+	/// Storage: `System::Account` (r:1 w:0)
+	/// Proof: `System::Account` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[1, 20]`.
+	fn bulk_execute_permissions(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `263 + n * (317 ±0)`
+		//  Estimated: `3593 + n * (70364 ±0)`
+		// Minimum execution time: 36_000_000 picoseconds.
+		Weight::from_parts(2_000_000, 3593)
+			.saturating_add(Weight::from_parts(33_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:1 w:1)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	fn update_stream_limits() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `317`
+		//  Estimated: `71354`
+		// Minimum execution time: 12_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 71354)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::Permissions` (r:1 w:1)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	fn set_stream_payout() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `446`
+		//  Estimated: `71354`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(14_000_000, 71354)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Permission0::Permissions` (r:1 w:0)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::TransferablePermissions` (r:0 w:1)
+	/// Proof: `Permission0::TransferablePermissions` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PendingRecipientTransfers` (r:0 w:1)
+	/// Proof: `Permission0::PendingRecipientTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	fn set_permission_transferable() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `529`
+		//  Estimated: `71354`
+		// Minimum execution time: 13_000_000 picoseconds.
+		Weight::from_parts(13_000_000, 71354)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Permission0::Permissions` (r:17 w:17)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::TransferablePermissions` (r:1 w:0)
+	/// Proof: `Permission0::TransferablePermissions` (`max_values`: None, `max_size`: Some(33), added: 2508, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PendingRecipientTransfers` (r:0 w:1)
+	/// Proof: `Permission0::PendingRecipientTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:34 w:34)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:2 w:2)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:3 w:3)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 16]`.
+	fn transfer_permission_recipient(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1017 + n * (352 ±0)`
+		//  Estimated: `77264 + n * (70364 ±0)`
+		// Minimum execution time: 38_000_000 picoseconds.
+		Weight::from_parts(38_000_000, 77264)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
+	/// Storage: `Permission0::Permissions` (r:17 w:17)
+	/// Proof: `Permission0::Permissions` (`max_values`: None, `max_size`: Some(67889), added: 70364, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PendingRecipientTransfers` (r:1 w:1)
+	/// Proof: `Permission0::PendingRecipientTransfers` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:1 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByParticipants` (r:34 w:34)
+	/// Proof: `Permission0::PermissionsByParticipants` (`max_values`: None, `max_size`: Some(3266), added: 5741, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByRecipient` (r:2 w:2)
+	/// Proof: `Permission0::PermissionsByRecipient` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// Storage: `Permission0::PermissionsByDelegator` (r:3 w:3)
+	/// Proof: `Permission0::PermissionsByDelegator` (`max_values`: None, `max_size`: Some(3234), added: 5709, mode: `MaxEncodedLen`)
+	/// The range of component `n` is `[0, 16]`.
+	fn approve_permission_transfer(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1081 + n * (352 ±0)`
+		//  Estimated: `77264 + n * (70364 ±0)`
+		// Minimum execution time: 39_000_000 picoseconds.
+		Weight::from_parts(39_000_000, 77264)
+			.saturating_add(Weight::from_parts(21_000_000, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(7_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(5_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(n.into())))
+			.saturating_add(Weight::from_parts(0, 70364).saturating_mul(n.into()))
+	}
 }
@@ -1,10 +1,14 @@
-use pallet_permission0::{Config, CuratorPermissions, Error, Pallet, PermissionId};
+use pallet_permission0::{
+    Config, CuratorPermissions, CuratorQuota, Error, Pallet, PermissionId, PermissionScope,
+    Permissions, permission::curator::MAX_CURATOR_QUOTAS,
+};
 use pallet_permission0_api::{CuratorPermissions as ApiCuratorPermissions, Permission0CuratorApi};
 use polkadot_sdk::{
     frame_support::{assert_err, dispatch::DispatchResult},
     frame_system::RawOrigin,
     polkadot_sdk_frame::prelude::OriginFor,
-    sp_runtime::BoundedBTreeMap,
+    sp_core::{ConstU32, Get},
+    sp_runtime::{BoundedBTreeMap, traits::AccountIdConversion},
 };
 use test_utils::*;
 
@@ -39,7 +43,8 @@ fn delegate_curator_permission_correctly() {
                 None,
                 pallet_permission0::PermissionDuration::Indefinite,
                 pallet_permission0::RevocationTerms::Irrevocable,
-                1
+                1,
+                Default::default(),
             ),
             Error::<Test>::NotPermissionRecipient
         );
@@ -55,7 +60,8 @@ fn delegate_curator_permission_correctly() {
                 None,
                 pallet_permission0::PermissionDuration::Indefinite,
                 pallet_permission0::RevocationTerms::Irrevocable,
-                1
+                1,
+                Default::default(),
             ),
             Error::<Test>::DuplicatePermissionInBlock
         );
@@ -69,7 +75,8 @@ fn delegate_curator_permission_correctly() {
             None,
             pallet_permission0::PermissionDuration::Indefinite,
             pallet_permission0::RevocationTerms::Irrevocable,
-            1
+            1,
+            Default::default(),
         ));
 
         let key = 0;
@@ -84,7 +91,8 @@ fn delegate_curator_permission_correctly() {
                 None,
                 pallet_permission0::PermissionDuration::Indefinite,
                 pallet_permission0::RevocationTerms::Irrevocable,
-                1
+                1,
+                Default::default(),
             ),
             Error::<Test>::InvalidCuratorPermissions
         );
@@ -156,3 +164,248 @@ fn ensure_curator_handles_cooldown_correctly() {
         ));
     });
 }
+
+fn quotas(
+    quotas: &[(CuratorPermissions, u32, u64)],
+) -> BoundedBTreeMap<u32, CuratorQuota<Test>, ConstU32<MAX_CURATOR_QUOTAS>> {
+    let mut map = BoundedBTreeMap::new();
+    for (flag, max_actions, period) in quotas {
+        map.try_insert(
+            flag.bits(),
+            CuratorQuota {
+                max_actions: *max_actions,
+                period: *period,
+            },
+        )
+        .unwrap();
+    }
+    map
+}
+
+#[test]
+fn curator_quota_limits_actions_per_period() {
+    new_test_ext().execute_with(|| {
+        let key = 0;
+
+        assert_err!(
+            Permission0::delegate_curator_permission(
+                RawOrigin::Root.into(),
+                key,
+                root_permissions(CuratorPermissions::WHITELIST_MANAGE),
+                None,
+                pallet_permission0::PermissionDuration::Indefinite,
+                pallet_permission0::RevocationTerms::Irrevocable,
+                1,
+                quotas(&[(CuratorPermissions::PENALTY_CONTROL, 2, 10)]),
+            ),
+            Error::<Test>::InvalidCuratorQuota
+        );
+
+        assert_err!(
+            Permission0::delegate_curator_permission(
+                RawOrigin::Root.into(),
+                key,
+                root_permissions(CuratorPermissions::WHITELIST_MANAGE),
+                None,
+                pallet_permission0::PermissionDuration::Indefinite,
+                pallet_permission0::RevocationTerms::Irrevocable,
+                1,
+                quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 0, 10)]),
+            ),
+            Error::<Test>::InvalidCuratorQuota
+        );
+
+        assert_ok!(Permission0::delegate_curator_permission(
+            RawOrigin::Root.into(),
+            key,
+            root_permissions(CuratorPermissions::WHITELIST_MANAGE),
+            None,
+            pallet_permission0::PermissionDuration::Indefinite,
+            pallet_permission0::RevocationTerms::Irrevocable,
+            1,
+            quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 2, 10)]),
+        ));
+
+        for _ in 0..2 {
+            assert_ok!(ensure_curator(
+                RawOrigin::Signed(key).into(),
+                CuratorPermissions::WHITELIST_MANAGE
+            ));
+        }
+
+        assert_err!(
+            ensure_curator(
+                RawOrigin::Signed(key).into(),
+                CuratorPermissions::WHITELIST_MANAGE
+            ),
+            Error::<Test>::CuratorQuotaExceeded
+        );
+
+        step_block(10);
+
+        assert_ok!(ensure_curator(
+            RawOrigin::Signed(key).into(),
+            CuratorPermissions::WHITELIST_MANAGE
+        ));
+    });
+}
+
+#[test]
+fn sub_curator_quota_is_bounded_by_parent() {
+    new_test_ext().execute_with(|| {
+        let curator = 0;
+        let sub_curator = 1;
+
+        assert_ok!(Permission0::delegate_curator_permission(
+            RawOrigin::Root.into(),
+            curator,
+            root_permissions(CuratorPermissions::WHITELIST_MANAGE),
+            None,
+            pallet_permission0::PermissionDuration::Indefinite,
+            pallet_permission0::RevocationTerms::RevocableByDelegator,
+            2,
+            quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 3, 10)]),
+        ));
+        let parent_id = Pallet::<Test>::get_curator_permission(&curator).unwrap();
+
+        assert_ok!(ensure_curator(
+            RawOrigin::Signed(curator).into(),
+            CuratorPermissions::WHITELIST_MANAGE
+        ));
+
+        let mut flags = BoundedBTreeMap::new();
+        flags
+            .try_insert(Some(parent_id), CuratorPermissions::WHITELIST_MANAGE.bits())
+            .unwrap();
+
+        for invalid in [
+            quotas(&[]),
+            quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 3, 10)]),
+            quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 2, 5)]),
+        ] {
+            assert_err!(
+                Permission0::delegate_curator_permission(
+                    RawOrigin::Signed(curator).into(),
+                    sub_curator,
+                    flags.clone(),
+                    None,
+                    pallet_permission0::PermissionDuration::Indefinite,
+                    pallet_permission0::RevocationTerms::RevocableByDelegator,
+                    1,
+                    invalid,
+                ),
+                Error::<Test>::CuratorQuotaExceedsParent
+            );
+        }
+
+        assert_ok!(Permission0::delegate_curator_permission(
+            RawOrigin::Signed(curator).into(),
+            sub_curator,
+            flags,
+            None,
+            pallet_permission0::PermissionDuration::Indefinite,
+            pallet_permission0::RevocationTerms::RevocableByDelegator,
+            1,
+            quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 2, 10)]),
+        ));
+
+        // Sub-curator actions count against the parent quota as well.
+        for _ in 0..2 {
+            assert_ok!(ensure_curator(
+                RawOrigin::Signed(sub_curator).into(),
+                CuratorPermissions::WHITELIST_MANAGE
+            ));
+        }
+
+        let Some(PermissionScope::Curator(parent)) =
+            Permissions::<Test>::get(parent_id).map(|contract| contract.scope)
+        else {
+            panic!("parent curator permission not found");
+        };
+        assert_eq!(
+            parent.remaining_quota(CuratorPermissions::WHITELIST_MANAGE, System::block_number()),
+            Some(0)
+        );
+    });
+}
+
+#[test]
+fn sub_curators_cannot_share_more_than_the_parent_quota() {
+    new_test_ext().execute_with(|| {
+        let curator = 0;
+        let (sub_curator_1, sub_curator_2) = (1, 2);
+
+        assert_ok!(Permission0::delegate_curator_permission(
+            RawOrigin::Root.into(),
+            curator,
+            root_permissions(CuratorPermissions::WHITELIST_MANAGE),
+            None,
+            pallet_permission0::PermissionDuration::Indefinite,
+            pallet_permission0::RevocationTerms::RevocableByDelegator,
+            2,
+            quotas(&[(CuratorPermissions::WHITELIST_MANAGE, 4, 10)]),
+        ));
+        let parent_id = Pallet::<Test>::get_curator_permission(&curator).unwrap();
+
+        let mut flags = BoundedBTreeMap::new();
+        flags
+            .try_insert(Some(parent_id), CuratorPermissions::WHITELIST_MANAGE.bits())
+            .unwrap();
+        let delegate = |sub_curator, max_actions| {
+            Permission0::delegate_curator_permission(
+                RawOrigin::Signed(curator).into(),
+                sub_curator,
+                flags.clone(),
+                None,
+                pallet_permission0::PermissionDuration::Indefinite,
+                pallet_permission0::RevocationTerms::RevocableByDelegator,
+                1,
+                quotas(&[(CuratorPermissions::WHITELIST_MANAGE, max_actions, 10)]),
+            )
+        };
+
+        assert_ok!(delegate(sub_curator_1, 3));
+
+        // Only one action is left once the first sub-curator's is reserved.
+        assert_err!(
+            delegate(sub_curator_2, 2),
+            Error::<Test>::CuratorQuotaExceedsParent
+        );
+        assert_ok!(delegate(sub_curator_2, 1));
+
+        for sub_curator in [sub_curator_1, sub_curator_1, sub_curator_1, sub_curator_2] {
+            assert_ok!(ensure_curator(
+                RawOrigin::Signed(sub_curator).into(),
+                CuratorPermissions::WHITELIST_MANAGE
+            ));
+        }
+    });
+}
+
+#[test]
+fn ensure_curator_returns_authorizing_permission() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            Pallet::<Test>::ensure_curator_permission(
+                RawOrigin::Root.into(),
+                ApiCuratorPermissions::WHITELIST_MANAGE,
+            ),
+            Ok((
+                <Test as Config>::PalletId::get().into_account_truncating(),
+                None
+            ))
+        );
+
+        let key = 0;
+        delegate_curator_permission(key, CuratorPermissions::WHITELIST_MANAGE, None);
+        let permission_id = Pallet::<Test>::get_curator_permission(&key).unwrap();
+
+        assert_eq!(
+            Pallet::<Test>::ensure_curator_permission(
+                RawOrigin::Signed(key).into(),
+                ApiCuratorPermissions::WHITELIST_MANAGE,
+            ),
+            Ok((key, Some(permission_id)))
+        );
+    });
+}
@@ -365,9 +365,9 @@ fn multi_controller_voting() {
 
         let votes = pallet_permission0::EnforcementTracking::<Test>::get(
             permission_id,
-            EnforcementReferendum::Execution,
+            EnforcementReferendum::<Test>::Execution,
         );
-        assert_eq!(votes.len(), 1);
+        assert_eq!(votes.voters.len(), 1);
 
         let balance_before = get_balance(recipient);
 
@@ -448,3 +448,235 @@ fn enforcement_cannot_execute_non_manual_distribution() {
         );
     });
 }
+
+fn setup_controlled_stream(
+    delegator: AccountId,
+    recipient: AccountId,
+    controllers: Vec<AccountId>,
+    required_votes: u32,
+) -> pallet_permission0::PermissionId {
+    zero_min_burn();
+
+    register_empty_agent(delegator);
+    register_empty_agent(recipient);
+    for controller in &controllers {
+        register_empty_agent(*controller);
+    }
+
+    add_balance(delegator, as_tors(100) + 1);
+
+    assert_ok!(delegate_stream_permission(
+        delegator,
+        vec![(recipient, u16::MAX)],
+        pallet_permission0_api::StreamAllocation::Streams(stream_percentages(delegator, 100)),
+        pallet_permission0_api::DistributionControl::Manual,
+        pallet_permission0_api::PermissionDuration::Indefinite,
+        pallet_permission0_api::RevocationTerms::Irrevocable,
+        pallet_permission0_api::EnforcementAuthority::ControlledBy {
+            controllers,
+            required_votes
+        },
+    ))
+}
+
+#[test]
+fn enforcement_votes_expire_after_voting_period() {
+    new_test_ext().execute_with(|| {
+        let delegator = 0;
+        let recipient = 1;
+        let controller1 = 2;
+        let controller2 = 3;
+        let controller3 = 4;
+
+        let permission_id = setup_controlled_stream(
+            delegator,
+            recipient,
+            vec![controller1, controller2, controller3],
+            2,
+        );
+
+        assert_ok!(
+            pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                get_origin(controller1),
+                permission_id,
+                EnforcementReferendum::Pause,
+            )
+        );
+
+        let open = pallet_permission0::permission::enforcement_referenda::<Test>(permission_id);
+        assert_eq!(open.len(), 1);
+        assert_eq!(
+            open[0].referendum,
+            pallet_permission0_api::EnforcementReferendum::Pause
+        );
+        assert_eq!(open[0].voters, vec![controller1]);
+        assert_eq!(open[0].required_votes, 2);
+
+        step_block(100);
+
+        assert!(
+            pallet_permission0::permission::enforcement_referenda::<Test>(permission_id).is_empty()
+        );
+
+        // The first vote expired, so this one starts the referendum over
+        assert_ok!(
+            pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                get_origin(controller2),
+                permission_id,
+                EnforcementReferendum::Pause,
+            )
+        );
+        assert!(!pallet_permission0::PausedPermissions::<Test>::contains_key(permission_id));
+
+        assert_ok!(
+            pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                get_origin(controller3),
+                permission_id,
+                EnforcementReferendum::Pause,
+            )
+        );
+        assert!(pallet_permission0::PausedPermissions::<Test>::contains_key(
+            permission_id
+        ));
+        assert!(
+            pallet_permission0::permission::enforcement_referenda::<Test>(permission_id).is_empty()
+        );
+    });
+}
+
+#[test]
+fn paused_permission_does_not_accumulate_or_execute() {
+    new_test_ext().execute_with(|| {
+        let delegator = 0;
+        let recipient = 1;
+        let controller = 2;
+
+        let permission_id = setup_controlled_stream(delegator, recipient, vec![controller], 1);
+        let stream_id = generate_root_stream_id(&delegator);
+        let accumulated = || {
+            pallet_permission0::AccumulatedStreamAmounts::<Test>::get((
+                delegator,
+                stream_id,
+                permission_id,
+            ))
+            .unwrap_or_default()
+        };
+
+        assert_err!(
+            pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                get_origin(controller),
+                permission_id,
+                EnforcementReferendum::Resume,
+            ),
+            pallet_permission0::Error::<Test>::PermissionNotPaused
+        );
+
+        assert_ok!(
+            pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                get_origin(controller),
+                permission_id,
+                EnforcementReferendum::Pause,
+            )
+        );
+
+        distribute_emission(delegator, as_tors(10));
+        assert_eq!(accumulated(), 0);
+
+        assert_err!(
+            pallet_permission0::Pallet::<Test>::execute_permission(
+                get_origin(delegator),
+                permission_id,
+            ),
+            pallet_permission0::Error::<Test>::PermissionPaused
+        );
+        assert_err!(
+            pallet_permission0::Pallet::<Test>::enforcement_execute_permission(
+                get_origin(controller),
+                permission_id,
+            ),
+            pallet_permission0::Error::<Test>::PermissionPaused
+        );
+
+        assert_ok!(
+            pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                RuntimeOrigin::root(),
+                permission_id,
+                EnforcementReferendum::Resume,
+            )
+        );
+
+        distribute_emission(delegator, as_tors(10));
+        assert_eq!(accumulated(), as_tors(10));
+
+        let balance_before = get_balance(recipient);
+        assert_ok!(pallet_permission0::Pallet::<Test>::execute_permission(
+            get_origin(delegator),
+            permission_id,
+        ));
+        assert_eq!(get_balance(recipient), balance_before + as_tors(10));
+    });
+}
+
+#[test]
+fn controllers_edit_recipients_and_revoke_irrevocable_permission() {
+    new_test_ext().execute_with(|| {
+        let delegator = 0;
+        let recipient = 1;
+        let controller1 = 2;
+        let controller2 = 3;
+        let new_recipient = 4;
+
+        let permission_id =
+            setup_controlled_stream(delegator, recipient, vec![controller1, controller2], 2);
+        register_empty_agent(new_recipient);
+
+        let mut recipients = polkadot_sdk::sp_runtime::BoundedBTreeMap::new();
+        recipients.try_insert(new_recipient, u16::MAX).unwrap();
+
+        for controller in [controller1, controller2] {
+            assert_ok!(
+                pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                    get_origin(controller),
+                    permission_id,
+                    EnforcementReferendum::SetRecipients(recipients.clone()),
+                )
+            );
+        }
+
+        let contract = pallet_permission0::Permissions::<Test>::get(permission_id).unwrap();
+        let pallet_permission0::PermissionScope::Stream(scope) = contract.scope else {
+            panic!("expected stream permission");
+        };
+        assert_eq!(scope.recipients, recipients);
+        assert!(
+            pallet_permission0::PermissionsByRecipient::<Test>::get(new_recipient)
+                .contains(&permission_id)
+        );
+        assert!(
+            !pallet_permission0::PermissionsByRecipient::<Test>::get(recipient)
+                .contains(&permission_id)
+        );
+
+        assert_err!(
+            pallet_permission0::Pallet::<Test>::revoke_permission(
+                get_origin(delegator),
+                permission_id,
+            ),
+            pallet_permission0::Error::<Test>::NotAuthorizedToRevoke
+        );
+
+        for controller in [controller1, controller2] {
+            assert_ok!(
+                pallet_permission0::Pallet::<Test>::vote_enforcement_referendum(
+                    get_origin(controller),
+                    permission_id,
+                    EnforcementReferendum::Revoke,
+                )
+            );
+        }
+
+        assert!(!pallet_permission0::Permissions::<Test>::contains_key(
+            permission_id
+        ));
+    });
+}
@@ -0,0 +1,254 @@
+use pallet_permission0::{
+    Error, PermissionDuration, PermissionId, PermissionScope, Permissions, RevocationTerms,
+};
+use polkadot_sdk::{
+    frame_support::{assert_err, assert_ok},
+    sp_runtime::{BoundedBTreeSet, BoundedVec},
+};
+use test_utils::*;
+
+pub fn new_test_ext() -> polkadot_sdk::sp_io::TestExternalities {
+    new_test_ext_with_block(1)
+}
+
+fn setup_agents() -> (AccountId, AccountId, AccountId, AccountId) {
+    zero_min_burn();
+
+    let delegator = 1;
+    let recipient = 2;
+    let arbiter_a = 3;
+    let arbiter_b = 4;
+
+    for agent in [delegator, recipient, arbiter_a, arbiter_b] {
+        register_empty_agent(agent);
+    }
+
+    add_balance(delegator, as_tors(1000));
+
+    (delegator, recipient, arbiter_a, arbiter_b)
+}
+
+fn delegate_escrow(
+    delegator: AccountId,
+    recipient: AccountId,
+    milestones: Vec<Balance>,
+    arbiters: Vec<AccountId>,
+    required_votes: u32,
+    dispute_timeout: BlockNumber,
+) -> PermissionId {
+    let mut arbiter_set = BoundedBTreeSet::new();
+    for arbiter in arbiters {
+        arbiter_set.try_insert(arbiter).unwrap();
+    }
+
+    assert_ok!(Permission0::delegate_escrow_permission(
+        RuntimeOrigin::signed(delegator),
+        recipient,
+        BoundedVec::try_from(milestones).unwrap(),
+        arbiter_set,
+        required_votes,
+        dispute_timeout,
+        PermissionDuration::Indefinite,
+        RevocationTerms::Irrevocable,
+    ));
+
+    get_last_delegated_permission_id(delegator)
+}
+
+fn get_last_delegated_permission_id(delegator: AccountId) -> PermissionId {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            RuntimeEvent::Permission0(pallet_permission0::Event::PermissionDelegated {
+                delegator: event_delegator,
+                permission_id,
+            }) if event_delegator == delegator => Some(permission_id),
+            _ => None,
+        })
+        .next_back()
+        .expect("No PermissionDelegated event found")
+}
+
+fn released_milestones(permission_id: PermissionId) -> u32 {
+    match Permissions::<Test>::get(permission_id).map(|contract| contract.scope) {
+        Some(PermissionScope::Escrow(escrow)) => escrow.released,
+        _ => panic!("escrow permission not found"),
+    }
+}
+
+#[test]
+fn escrow_rejects_invalid_terms() {
+    new_test_ext().execute_with(|| {
+        let (delegator, recipient, arbiter_a, _) = setup_agents();
+
+        let mut arbiters = BoundedBTreeSet::new();
+        arbiters.try_insert(arbiter_a).unwrap();
+
+        let cases = [
+            (vec![], BoundedBTreeSet::new(), 0, 10),
+            (vec![as_tors(10), 0], BoundedBTreeSet::new(), 0, 10),
+            (vec![as_tors(10)], BoundedBTreeSet::new(), 0, 0),
+            (vec![as_tors(10)], BoundedBTreeSet::new(), 1, 10),
+            (vec![as_tors(10)], arbiters.clone(), 0, 10),
+            (vec![as_tors(10)], arbiters, 2, 10),
+        ];
+
+        for (milestones, arbiters, required_votes, dispute_timeout) in cases {
+            assert_err!(
+                Permission0::delegate_escrow_permission(
+                    RuntimeOrigin::signed(delegator),
+                    recipient,
+                    BoundedVec::try_from(milestones).unwrap(),
+                    arbiters,
+                    required_votes,
+                    dispute_timeout,
+                    PermissionDuration::Indefinite,
+                    RevocationTerms::Irrevocable,
+                ),
+                Error::<Test>::InvalidEscrowTerms
+            );
+        }
+
+        assert_err!(
+            Permission0::delegate_escrow_permission(
+                RuntimeOrigin::signed(delegator),
+                recipient,
+                BoundedVec::try_from(vec![as_tors(2000)]).unwrap(),
+                BoundedBTreeSet::new(),
+                0,
+                10,
+                PermissionDuration::Indefinite,
+                RevocationTerms::Irrevocable,
+            ),
+            Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn delegator_releases_milestones_in_order() {
+    new_test_ext().execute_with(|| {
+        let (delegator, recipient, _, _) = setup_agents();
+
+        let permission_id = delegate_escrow(
+            delegator,
+            recipient,
+            vec![as_tors(100), as_tors(50)],
+            vec![],
+            0,
+            100,
+        );
+
+        assert_eq!(Balances::reserved_balance(delegator), as_tors(150));
+
+        assert_ok!(Permission0::approve_escrow_milestone(
+            RuntimeOrigin::signed(delegator),
+            permission_id
+        ));
+
+        assert_eq!(released_milestones(permission_id), 1);
+        assert_eq!(Balances::reserved_balance(delegator), as_tors(50));
+        assert_eq!(get_balance(recipient), as_tors(100));
+
+        assert_ok!(Permission0::approve_escrow_milestone(
+            RuntimeOrigin::signed(delegator),
+            permission_id
+        ));
+
+        assert_eq!(Balances::reserved_balance(delegator), 0);
+        assert_eq!(get_balance(recipient), as_tors(150));
+        assert!(!Permissions::<Test>::contains_key(permission_id));
+
+        System::assert_has_event(RuntimeEvent::Permission0(
+            pallet_permission0::Event::EscrowSettled {
+                permission_id,
+                released: 2,
+                refunded: 0,
+            },
+        ));
+    });
+}
+
+#[test]
+fn arbiters_release_milestone_once_votes_are_met() {
+    new_test_ext().execute_with(|| {
+        let (delegator, recipient, arbiter_a, arbiter_b) = setup_agents();
+
+        let permission_id = delegate_escrow(
+            delegator,
+            recipient,
+            vec![as_tors(100), as_tors(50)],
+            vec![arbiter_a, arbiter_b],
+            2,
+            100,
+        );
+
+        assert_err!(
+            Permission0::approve_escrow_milestone(RuntimeOrigin::signed(recipient), permission_id),
+            Error::<Test>::NotAuthorizedToApprove
+        );
+
+        assert_ok!(Permission0::approve_escrow_milestone(
+            RuntimeOrigin::signed(arbiter_a),
+            permission_id
+        ));
+        assert_eq!(released_milestones(permission_id), 0);
+
+        // Approving twice does not count as a second vote
+        assert_ok!(Permission0::approve_escrow_milestone(
+            RuntimeOrigin::signed(arbiter_a),
+            permission_id
+        ));
+        assert_eq!(released_milestones(permission_id), 0);
+
+        assert_ok!(Permission0::approve_escrow_milestone(
+            RuntimeOrigin::signed(arbiter_b),
+            permission_id
+        ));
+
+        assert_eq!(released_milestones(permission_id), 1);
+        assert_eq!(get_balance(recipient), as_tors(100));
+        assert_eq!(Balances::reserved_balance(delegator), as_tors(50));
+        assert!(pallet_permission0::EscrowApprovals::<Test>::get(permission_id).is_empty());
+    });
+}
+
+#[test]
+fn dispute_timeout_refunds_unreleased_funds() {
+    new_test_ext().execute_with(|| {
+        let (delegator, recipient, _, _) = setup_agents();
+        let initial_balance = get_balance(delegator);
+
+        let permission_id = delegate_escrow(
+            delegator,
+            recipient,
+            vec![as_tors(100), as_tors(50)],
+            vec![],
+            0,
+            20,
+        );
+
+        assert_ok!(Permission0::approve_escrow_milestone(
+            RuntimeOrigin::signed(delegator),
+            permission_id
+        ));
+
+        step_block(10);
+        assert!(Permissions::<Test>::contains_key(permission_id));
+
+        step_block(20);
+
+        assert!(!Permissions::<Test>::contains_key(permission_id));
+        assert_eq!(Balances::reserved_balance(delegator), 0);
+        assert_eq!(get_balance(delegator), initial_balance - as_tors(100));
+        assert_eq!(get_balance(recipient), as_tors(100));
+
+        System::assert_has_event(RuntimeEvent::Permission0(
+            pallet_permission0::Event::EscrowSettled {
+                permission_id,
+                released: 1,
+                refunded: as_tors(50),
+            },
+        ));
+    });
+}
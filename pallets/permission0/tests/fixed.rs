@@ -1,3 +1,6 @@
+use std::collections::BTreeMap;
+
+use pallet_permission0::PermissionId;
 use polkadot_sdk::frame_support::assert_err;
 use test_utils::*;
 
@@ -210,3 +213,303 @@ fn fixed_at_block_executes() {
         assert_eq!(get_balance(agent_0), 1);
     });
 }
+
+fn delegate_subscription(
+    delegator: AccountId,
+    recipients: Vec<(AccountId, u16)>,
+    terms: pallet_permission0::SubscriptionTerms<Test>,
+    interval: BlockNumber,
+) -> Result<PermissionId, polkadot_sdk::sp_runtime::DispatchError> {
+    Permission0::delegate_stream_permission(
+        get_origin(delegator),
+        BTreeMap::from_iter(recipients).try_into().unwrap(),
+        pallet_permission0::StreamAllocation::Subscription(terms),
+        pallet_permission0::DistributionControl::Interval(interval),
+        pallet_permission0::PermissionDuration::Indefinite,
+        pallet_permission0::RevocationTerms::RevocableByDelegator,
+        pallet_permission0::EnforcementAuthority::None,
+        None,
+        None,
+    )?;
+
+    Ok(
+        pallet_permission0::PermissionsByDelegator::<Test>::get(delegator)
+            .into_iter()
+            .next()
+            .unwrap(),
+    )
+}
+
+fn subscription_terms(
+    max_payments: u32,
+    funding: pallet_permission0::SubscriptionFunding,
+    auto_revoke: bool,
+) -> pallet_permission0::SubscriptionTerms<Test> {
+    pallet_permission0::SubscriptionTerms {
+        amount: as_tors(10),
+        max_payments,
+        funding,
+        grace_period: 20,
+        auto_revoke,
+    }
+}
+
+#[test]
+fn subscription_validates_terms() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        add_balance(agent_0, as_tors(10) + 1);
+
+        assert_err!(
+            delegate_stream_permission(
+                agent_0,
+                vec![(agent_1, u16::MAX)],
+                pallet_permission0_api::StreamAllocation::FixedAmount(as_tors(10)),
+                pallet_permission0_api::DistributionControl::Interval(20),
+                pallet_permission0_api::PermissionDuration::Indefinite,
+                pallet_permission0_api::RevocationTerms::Irrevocable,
+                pallet_permission0_api::EnforcementAuthority::None,
+            ),
+            pallet_permission0::Error::<Test>::FixedAmountCanOnlyBeTriggeredOnce
+        );
+
+        assert_err!(
+            delegate_subscription(
+                agent_0,
+                vec![(agent_1, u16::MAX)],
+                subscription_terms(0, pallet_permission0::SubscriptionFunding::Pull, false),
+                20,
+            ),
+            pallet_permission0::Error::<Test>::InvalidSubscriptionTerms
+        );
+
+        assert_err!(
+            Permission0::delegate_stream_permission(
+                get_origin(agent_0),
+                BTreeMap::from([(agent_1, u16::MAX)]).try_into().unwrap(),
+                pallet_permission0::StreamAllocation::Subscription(subscription_terms(
+                    2,
+                    pallet_permission0::SubscriptionFunding::Pull,
+                    false
+                )),
+                pallet_permission0::DistributionControl::Manual,
+                pallet_permission0::PermissionDuration::Indefinite,
+                pallet_permission0::RevocationTerms::RevocableByDelegator,
+                pallet_permission0::EnforcementAuthority::None,
+                None,
+                None,
+            ),
+            pallet_permission0::Error::<Test>::InvalidSubscriptionTerms
+        );
+
+        assert_err!(
+            delegate_subscription(
+                agent_0,
+                vec![(agent_1, u16::MAX)],
+                subscription_terms(2, pallet_permission0::SubscriptionFunding::Reserved, false),
+                20,
+            ),
+            pallet_permission0::Error::<Test>::InsufficientBalance
+        );
+    });
+}
+
+#[test]
+fn subscription_pays_every_interval_until_max_payments() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        add_balance(agent_0, as_tors(30) + 1);
+
+        let permission_id = assert_ok!(delegate_subscription(
+            agent_0,
+            vec![(agent_1, u16::MAX)],
+            subscription_terms(2, pallet_permission0::SubscriptionFunding::Pull, false),
+            20,
+        ));
+
+        assert_eq!(Balances::reserved_balance(agent_0), 0);
+
+        step_block(21);
+        assert_eq!(get_balance(agent_1), as_tors(10));
+        assert_eq!(get_balance(agent_0), as_tors(20) + 1);
+
+        step_block(20);
+        assert_eq!(get_balance(agent_1), as_tors(20));
+        assert_eq!(get_balance(agent_0), as_tors(10) + 1);
+
+        // The permission is removed right after its final payment
+        assert!(!pallet_permission0::Permissions::<Test>::contains_key(
+            permission_id
+        ));
+        assert!(pallet_permission0::PermissionsByDelegator::<Test>::get(agent_0).is_empty());
+        assert!(pallet_permission0::PermissionsByRecipient::<Test>::get(agent_1).is_empty());
+
+        step_block(40);
+        assert_eq!(get_balance(agent_1), as_tors(20));
+        assert_eq!(get_balance(agent_0), as_tors(10) + 1);
+    });
+}
+
+#[test]
+fn subscription_expires_after_final_reserved_payment() {
+    new_test_ext_with_block(1).execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        add_balance(agent_0, as_tors(20) + 1);
+
+        let permission_id = assert_ok!(delegate_subscription(
+            agent_0,
+            vec![(agent_1, u16::MAX)],
+            subscription_terms(2, pallet_permission0::SubscriptionFunding::Reserved, false),
+            20,
+        ));
+
+        step_block(30);
+        assert!(pallet_permission0::Permissions::<Test>::contains_key(
+            permission_id
+        ));
+        assert_eq!(Balances::reserved_balance(agent_0), as_tors(10));
+
+        step_block(20);
+        assert_eq!(get_balance(agent_1), as_tors(20));
+        assert_eq!(Balances::reserved_balance(agent_0), 0);
+        assert_eq!(get_balance(agent_0), 1);
+
+        assert!(!pallet_permission0::Permissions::<Test>::contains_key(
+            permission_id
+        ));
+        System::assert_has_event(RuntimeEvent::Permission0(
+            pallet_permission0::Event::PermissionExpired {
+                delegator: agent_0,
+                permission_id,
+            },
+        ));
+    });
+}
+
+#[test]
+fn subscription_reserves_all_payments() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        add_balance(agent_0, as_tors(30) + 1);
+
+        let permission_id = assert_ok!(delegate_subscription(
+            agent_0,
+            vec![(agent_1, u16::MAX)],
+            subscription_terms(3, pallet_permission0::SubscriptionFunding::Reserved, false),
+            20,
+        ));
+
+        assert_eq!(Balances::reserved_balance(agent_0), as_tors(30));
+
+        step_block(21);
+        assert_eq!(get_balance(agent_1), as_tors(10));
+        assert_eq!(Balances::reserved_balance(agent_0), as_tors(20));
+
+        assert_ok!(Permission0::revoke_permission(
+            get_origin(agent_0),
+            permission_id
+        ));
+
+        assert_eq!(Balances::reserved_balance(agent_0), 0);
+        assert_eq!(get_balance(agent_0), as_tors(20) + 1);
+    });
+}
+
+#[test]
+fn subscription_revokes_after_grace_period() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        add_balance(agent_0, as_tors(5));
+
+        let permission_id = assert_ok!(delegate_subscription(
+            agent_0,
+            vec![(agent_1, u16::MAX)],
+            subscription_terms(2, pallet_permission0::SubscriptionFunding::Pull, true),
+            20,
+        ));
+
+        step_block(21);
+        assert_eq!(get_balance(agent_1), 0);
+        assert_eq!(
+            pallet_permission0::SubscriptionArrears::<Test>::get(permission_id),
+            Some(20)
+        );
+
+        step_block(10);
+        assert!(pallet_permission0::Permissions::<Test>::contains_key(
+            permission_id
+        ));
+
+        step_block(10);
+        assert!(!pallet_permission0::Permissions::<Test>::contains_key(
+            permission_id
+        ));
+        assert!(!pallet_permission0::SubscriptionArrears::<Test>::contains_key(permission_id));
+        assert_eq!(get_balance(agent_0), as_tors(5));
+    });
+}
+
+#[test]
+fn subscription_skips_lapsed_payments() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        let permission_id = assert_ok!(delegate_subscription(
+            agent_0,
+            vec![(agent_1, u16::MAX)],
+            subscription_terms(2, pallet_permission0::SubscriptionFunding::Pull, false),
+            20,
+        ));
+
+        step_block(41);
+        assert_eq!(get_balance(agent_1), 0);
+        assert!(!pallet_permission0::SubscriptionArrears::<Test>::contains_key(permission_id));
+
+        let contract = pallet_permission0::Permissions::<Test>::get(permission_id).unwrap();
+        assert_eq!(contract.execution_count(), 0);
+        assert_eq!(contract.last_execution(), Some(40));
+
+        add_balance(agent_0, as_tors(10) + 1);
+
+        step_block(20);
+        assert_eq!(get_balance(agent_1), as_tors(10));
+
+        let contract = pallet_permission0::Permissions::<Test>::get(permission_id).unwrap();
+        assert_eq!(contract.execution_count(), 1);
+    });
+}
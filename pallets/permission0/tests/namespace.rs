@@ -2084,3 +2084,212 @@ fn bulk_delegate_namespace_permission_succeeds_within_parent_instance_limit() {
         );
     });
 }
+
+#[test]
+fn transfer_permission_recipient_keeps_children_and_moves_indices() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+        let eve = 4;
+
+        for agent in [alice, bob, charlie, dave, eve] {
+            register_agent(agent);
+        }
+
+        let namespace = register_namespace(alice, b"agent.alice.compute");
+
+        assert_ok!(Permission0::delegate_namespace_permission(
+            get_origin(alice),
+            bob,
+            paths_map!(None => [namespace.clone()]),
+            PermissionDuration::Indefinite,
+            RevocationTerms::Irrevocable,
+            2
+        ));
+        let bob_permission_id = get_last_delegated_permission_id(alice);
+
+        assert_ok!(Permission0::delegate_namespace_permission(
+            get_origin(bob),
+            charlie,
+            paths_map!(Some(bob_permission_id) => [namespace]),
+            PermissionDuration::Indefinite,
+            RevocationTerms::Irrevocable,
+            1
+        ));
+        let charlie_permission_id = get_last_delegated_permission_id(bob);
+
+        assert_err!(
+            Permission0::transfer_permission_recipient(get_origin(bob), bob_permission_id, dave),
+            Error::<Test>::NotTransferable
+        );
+
+        assert_err!(
+            Permission0::set_permission_transferable(
+                get_origin(bob),
+                bob_permission_id,
+                Some(pallet_permission0::RecipientTransfer::Allowed)
+            ),
+            Error::<Test>::NotPermissionDelegator
+        );
+        assert_ok!(Permission0::set_permission_transferable(
+            get_origin(alice),
+            bob_permission_id,
+            Some(pallet_permission0::RecipientTransfer::Allowed)
+        ));
+
+        assert_err!(
+            Permission0::transfer_permission_recipient(get_origin(dave), bob_permission_id, dave),
+            Error::<Test>::NotPermissionRecipient
+        );
+        assert_ok!(Permission0::transfer_permission_recipient(
+            get_origin(bob),
+            bob_permission_id,
+            dave
+        ));
+
+        let contract = Permissions::<Test>::get(bob_permission_id).unwrap();
+        let PermissionScope::Namespace(scope) = &contract.scope else {
+            panic!("expected namespace permission");
+        };
+        assert_eq!(scope.recipient, dave);
+        assert!(scope.children.contains(&charlie_permission_id));
+
+        assert!(!PermissionsByRecipient::<Test>::get(bob).contains(&bob_permission_id));
+        assert!(PermissionsByRecipient::<Test>::get(dave).contains(&bob_permission_id));
+        assert!(
+            pallet_permission0::PermissionsByParticipants::<Test>::get((alice, bob)).is_empty()
+        );
+        assert!(
+            pallet_permission0::PermissionsByParticipants::<Test>::get((alice, dave))
+                .contains(&bob_permission_id)
+        );
+
+        // Once approval is required, the transfer waits for the delegator
+        assert_ok!(Permission0::set_permission_transferable(
+            get_origin(alice),
+            bob_permission_id,
+            Some(pallet_permission0::RecipientTransfer::RequiresApproval)
+        ));
+        assert_ok!(Permission0::transfer_permission_recipient(
+            get_origin(dave),
+            bob_permission_id,
+            eve
+        ));
+        assert!(PermissionsByRecipient::<Test>::get(dave).contains(&bob_permission_id));
+
+        assert_err!(
+            Permission0::approve_permission_transfer(get_origin(alice), bob_permission_id, bob),
+            Error::<Test>::TransferNotRequested
+        );
+        assert_ok!(Permission0::approve_permission_transfer(
+            get_origin(alice),
+            bob_permission_id,
+            eve
+        ));
+
+        assert!(PermissionsByRecipient::<Test>::get(eve).contains(&bob_permission_id));
+        assert!(
+            !pallet_permission0::PendingRecipientTransfers::<Test>::contains_key(bob_permission_id)
+        );
+    });
+}
+
+#[test]
+fn transfer_permission_recipient_hands_children_to_the_new_recipient() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let alice = 0;
+        let bob = 1;
+        let charlie = 2;
+        let dave = 3;
+
+        for agent in [alice, bob, charlie, dave] {
+            register_agent(agent);
+        }
+
+        let namespace = register_namespace(alice, b"agent.alice.compute");
+
+        assert_ok!(Permission0::delegate_namespace_permission(
+            get_origin(alice),
+            bob,
+            paths_map!(None => [namespace.clone()]),
+            PermissionDuration::Indefinite,
+            RevocationTerms::Irrevocable,
+            2
+        ));
+        let bob_permission_id = get_last_delegated_permission_id(alice);
+
+        assert_ok!(Permission0::delegate_namespace_permission(
+            get_origin(bob),
+            charlie,
+            paths_map!(Some(bob_permission_id) => [namespace.clone()]),
+            PermissionDuration::Indefinite,
+            RevocationTerms::RevocableByDelegator,
+            1
+        ));
+        let charlie_permission_id = get_last_delegated_permission_id(bob);
+
+        assert_ok!(Permission0::set_permission_transferable(
+            get_origin(alice),
+            bob_permission_id,
+            Some(pallet_permission0::RecipientTransfer::Allowed)
+        ));
+
+        // The child recipient cannot become the delegator of its own permission
+        assert_err!(
+            Permission0::transfer_permission_recipient(get_origin(bob), bob_permission_id, charlie),
+            Error::<Test>::SelfPermissionNotAllowed
+        );
+
+        assert_ok!(Permission0::transfer_permission_recipient(
+            get_origin(bob),
+            bob_permission_id,
+            dave
+        ));
+
+        let child = Permissions::<Test>::get(charlie_permission_id).unwrap();
+        assert_eq!(child.delegator, dave);
+        assert!(!PermissionsByDelegator::<Test>::get(bob).contains(&charlie_permission_id));
+        assert!(PermissionsByDelegator::<Test>::get(dave).contains(&charlie_permission_id));
+        assert!(
+            pallet_permission0::PermissionsByParticipants::<Test>::get((bob, charlie)).is_empty()
+        );
+        assert!(
+            pallet_permission0::PermissionsByParticipants::<Test>::get((dave, charlie))
+                .contains(&charlie_permission_id)
+        );
+
+        // The old recipient lost control over the children
+        assert_err!(
+            Permission0::revoke_permission(get_origin(bob), charlie_permission_id),
+            Error::<Test>::NotAuthorizedToRevoke
+        );
+        assert_err!(
+            Permission0::delegate_namespace_permission(
+                get_origin(bob),
+                charlie,
+                paths_map!(Some(bob_permission_id) => [namespace.clone()]),
+                PermissionDuration::Indefinite,
+                RevocationTerms::Irrevocable,
+                1
+            ),
+            Error::<Test>::NotPermissionRecipient
+        );
+
+        assert_ok!(Permission0::revoke_permission(
+            get_origin(dave),
+            charlie_permission_id
+        ));
+        assert_ok!(Permission0::delegate_namespace_permission(
+            get_origin(dave),
+            charlie,
+            paths_map!(Some(bob_permission_id) => [namespace]),
+            PermissionDuration::Indefinite,
+            RevocationTerms::Irrevocable,
+            1
+        ));
+    });
+}
@@ -2,9 +2,13 @@ use std::collections::BTreeMap;
 
 use pallet_emission0::distribute::get_total_emission_per_block;
 use pallet_permission0::{AccumulatedStreamAmounts, PermissionScope};
-use pallet_permission0_api::generate_root_stream_id;
+use pallet_permission0_api::{Permission0StreamApi, generate_root_stream_id};
 use polkadot_sdk::{
-    frame_support::assert_err,
+    frame_support::{
+        assert_err,
+        traits::{Currency, Imbalance},
+    },
+    sp_core::H256,
     sp_runtime::{BoundedBTreeMap, BoundedVec, Percent},
 };
 use test_utils::{
@@ -1456,3 +1460,498 @@ fn cannot_create_empty_recipients_for_irrevocable_permissions() {
         );
     });
 }
+
+fn try_delegate_full_stream(
+    delegator: AccountId,
+    recipient: AccountId,
+    stream_id: H256,
+) -> polkadot_sdk::frame_support::dispatch::DispatchResult {
+    pallet_permission0::Pallet::<Test>::delegate_stream_permission(
+        get_origin(delegator),
+        BTreeMap::from([(recipient, u16::MAX)]).try_into().unwrap(),
+        pallet_permission0::StreamAllocation::Streams(
+            BTreeMap::from([(stream_id, Percent::from_percent(100))])
+                .try_into()
+                .unwrap(),
+        ),
+        pallet_permission0::DistributionControl::Manual,
+        pallet_permission0::PermissionDuration::Indefinite,
+        pallet_permission0::RevocationTerms::RevocableByDelegator,
+        pallet_permission0::EnforcementAuthority::None,
+        None,
+        None,
+    )
+}
+
+fn delegate_full_stream(
+    delegator: AccountId,
+    recipient: AccountId,
+    stream_id: H256,
+) -> pallet_permission0::PermissionId {
+    assert_ok!(try_delegate_full_stream(delegator, recipient, stream_id));
+    get_last_delegated_permission_id(delegator)
+}
+
+fn accumulate(agent: AccountId, stream_id: H256, amount: Balance) -> Balance {
+    let mut imbalance = Balances::issue(amount);
+    Permission0::accumulate_streams(&agent, &stream_id, &mut imbalance);
+    imbalance.peek()
+}
+
+#[test]
+fn stream_limits_cap_accumulation() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        let stream_id = generate_root_stream_id(&agent_0);
+        let permission_id = delegate_full_stream(agent_0, agent_1, stream_id);
+
+        assert_err!(
+            Permission0::update_stream_limits(
+                get_origin(agent_0),
+                permission_id,
+                pallet_permission0::StreamLimits {
+                    lifetime_cap: Some(0),
+                    epoch_cap: None,
+                    overflow: Default::default(),
+                },
+            ),
+            pallet_permission0::Error::<Test>::InvalidStreamLimits
+        );
+
+        assert_err!(
+            Permission0::update_stream_limits(
+                get_origin(agent_1),
+                permission_id,
+                Default::default(),
+            ),
+            pallet_permission0::Error::<Test>::NotPermissionDelegator
+        );
+
+        assert_ok!(Permission0::update_stream_limits(
+            get_origin(agent_0),
+            permission_id,
+            pallet_permission0::StreamLimits {
+                lifetime_cap: Some(as_tors(15)),
+                epoch_cap: Some(pallet_permission0::EpochCap {
+                    length: 100,
+                    amount: as_tors(10),
+                }),
+                overflow: pallet_permission0::StreamOverflow::ReturnToDelegator,
+            },
+        ));
+
+        assert_eq!(accumulate(agent_0, stream_id, as_tors(8)), 0);
+        assert_eq!(accumulate(agent_0, stream_id, as_tors(8)), as_tors(6));
+        assert_eq!(
+            AccumulatedStreamAmounts::<Test>::get((agent_0, stream_id, permission_id)),
+            Some(as_tors(10))
+        );
+
+        step_block(100);
+
+        assert_eq!(accumulate(agent_0, stream_id, as_tors(8)), as_tors(3));
+        assert_eq!(accumulate(agent_0, stream_id, as_tors(8)), as_tors(8));
+        assert_eq!(
+            AccumulatedStreamAmounts::<Test>::get((agent_0, stream_id, permission_id)),
+            Some(as_tors(15))
+        );
+
+        assert_ok!(Permission0::execute_permission(
+            get_origin(agent_0),
+            permission_id
+        ));
+        assert_eq!(get_balance(agent_1), as_tors(15));
+
+        assert!(System::events().into_iter().any(|record| {
+            record.event
+                == RuntimeEvent::Permission0(pallet_permission0::Event::StreamLifetimeCapReached {
+                    permission_id,
+                })
+        }));
+    });
+}
+
+#[test]
+fn stream_limits_forward_overflow() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        let agent_2 = 2;
+        register_empty_agent(agent_2);
+
+        let stream_id = generate_root_stream_id(&agent_0);
+        let fallback_id = H256::repeat_byte(1);
+
+        let permission_id = delegate_full_stream(agent_0, agent_1, stream_id);
+        let fallback_permission_id = delegate_full_stream(agent_0, agent_2, fallback_id);
+
+        assert_err!(
+            Permission0::update_stream_limits(
+                get_origin(agent_0),
+                permission_id,
+                pallet_permission0::StreamLimits {
+                    lifetime_cap: Some(as_tors(10)),
+                    epoch_cap: None,
+                    overflow: pallet_permission0::StreamOverflow::Forward(stream_id),
+                },
+            ),
+            pallet_permission0::Error::<Test>::InvalidStreamLimits
+        );
+
+        assert_ok!(Permission0::update_stream_limits(
+            get_origin(agent_0),
+            permission_id,
+            pallet_permission0::StreamLimits {
+                lifetime_cap: Some(as_tors(10)),
+                epoch_cap: None,
+                overflow: pallet_permission0::StreamOverflow::Forward(fallback_id),
+            },
+        ));
+
+        assert_eq!(accumulate(agent_0, stream_id, as_tors(15)), 0);
+        assert_eq!(
+            AccumulatedStreamAmounts::<Test>::get((agent_0, stream_id, permission_id)),
+            Some(as_tors(10))
+        );
+        assert_eq!(
+            AccumulatedStreamAmounts::<Test>::get((agent_0, fallback_id, fallback_permission_id)),
+            Some(as_tors(5))
+        );
+
+        assert!(System::events().into_iter().any(|record| {
+            record.event
+                == RuntimeEvent::Permission0(pallet_permission0::Event::StreamOverflowed {
+                    permission_id,
+                    stream_id,
+                    amount: as_tors(5),
+                    forwarded_to: Some(fallback_id),
+                })
+        }));
+    });
+}
+
+#[test]
+fn stream_pays_recipients_by_payout_preference() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        let agent_0 = 0;
+        register_empty_agent(agent_0);
+
+        let agent_1 = 1;
+        register_empty_agent(agent_1);
+
+        let agent_2 = 2;
+        register_empty_agent(agent_2);
+
+        let agent_3 = 3;
+        register_empty_agent(agent_3);
+
+        let stream_id = generate_root_stream_id(&agent_0);
+
+        assert_ok!(
+            pallet_permission0::Pallet::<Test>::delegate_stream_permission(
+                get_origin(agent_0),
+                BTreeMap::from([(agent_1, u16::MAX / 2), (agent_2, u16::MAX / 2)])
+                    .try_into()
+                    .unwrap(),
+                pallet_permission0::StreamAllocation::Streams(
+                    BTreeMap::from([(stream_id, Percent::from_percent(100))])
+                        .try_into()
+                        .unwrap()
+                ),
+                pallet_permission0::DistributionControl::Manual,
+                pallet_permission0::PermissionDuration::Indefinite,
+                pallet_permission0::RevocationTerms::RevocableByDelegator,
+                pallet_permission0::EnforcementAuthority::None,
+                None,
+                None,
+            )
+        );
+
+        let permission_id = get_last_delegated_permission_id(agent_0);
+
+        assert_err!(
+            Permission0::set_stream_payout(
+                get_origin(agent_3),
+                permission_id,
+                pallet_permission0::StreamPayout::StakeToSelf,
+            ),
+            pallet_permission0::Error::<Test>::NotPermissionRecipient
+        );
+
+        assert_err!(
+            Permission0::set_stream_payout(
+                get_origin(agent_2),
+                permission_id,
+                pallet_permission0::StreamPayout::StakeTo(4),
+            ),
+            pallet_permission0::Error::<Test>::NotRegisteredAgent
+        );
+
+        assert_ok!(Permission0::set_stream_payout(
+            get_origin(agent_1),
+            permission_id,
+            pallet_permission0::StreamPayout::StakeToSelf,
+        ));
+
+        assert_ok!(Permission0::set_stream_payout(
+            get_origin(agent_2),
+            permission_id,
+            pallet_permission0::StreamPayout::StakeTo(agent_3),
+        ));
+
+        assert_eq!(accumulate(agent_0, stream_id, as_tors(20)), 0);
+
+        assert_ok!(Permission0::execute_permission(
+            get_origin(agent_0),
+            permission_id
+        ));
+
+        assert_eq!(
+            pallet_torus0::StakingTo::<Test>::get(agent_1, agent_1),
+            Some(as_tors(10))
+        );
+        assert_eq!(
+            pallet_torus0::StakingTo::<Test>::get(agent_2, agent_3),
+            Some(as_tors(10))
+        );
+        assert_eq!(get_balance(agent_1), 0);
+        assert_eq!(get_balance(agent_2), 0);
+
+        // Removed recipients lose their payout preference
+        assert_ok!(Permission0::update_stream_permission(
+            get_origin(agent_0),
+            permission_id,
+            Some(BTreeMap::from([(agent_2, u16::MAX)]).try_into().unwrap()),
+            None,
+            None,
+            None,
+            None,
+        ));
+
+        let Some(pallet_permission0::PermissionContract {
+            scope: PermissionScope::Stream(scope),
+            ..
+        }) = pallet_permission0::Permissions::<Test>::get(permission_id)
+        else {
+            panic!("stream permission not found");
+        };
+
+        assert_eq!(
+            scope.recipient_payouts.into_inner(),
+            BTreeMap::from([(agent_2, pallet_permission0::StreamPayout::StakeTo(agent_3))])
+        );
+    });
+}
+
+#[test]
+fn stream_graph_rejects_cycles_and_deep_chains() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+        for agent in 0..5 {
+            register_empty_agent(agent);
+        }
+
+        let stream_id = generate_root_stream_id(&0);
+
+        let first = delegate_full_stream(0, 1, stream_id);
+        let second = delegate_full_stream(1, 2, stream_id);
+
+        assert_err!(
+            try_delegate_full_stream(2, 0, stream_id),
+            pallet_permission0::Error::<Test>::StreamCycleDetected
+        );
+
+        // The same delegators can form a chain over a different stream
+        assert_ok!(try_delegate_full_stream(2, 0, H256::repeat_byte(1)));
+
+        delegate_full_stream(2, 3, stream_id);
+
+        assert_err!(
+            try_delegate_full_stream(3, 4, stream_id),
+            pallet_permission0::Error::<Test>::StreamDepthExceeded
+        );
+
+        assert_err!(
+            Permission0::update_stream_permission(
+                get_origin(1),
+                second,
+                Some(
+                    BTreeMap::from([(0, u16::MAX), (2, u16::MAX)])
+                        .try_into()
+                        .unwrap()
+                ),
+                None,
+                None,
+                None,
+                None,
+            ),
+            pallet_permission0::Error::<Test>::StreamCycleDetected
+        );
+
+        assert_eq!(
+            pallet_permission0::permission::stream::stream_graph::<Test>(Some(stream_id))
+                .into_iter()
+                .map(|edge| (edge.delegator, edge.recipient))
+                .collect::<Vec<_>>(),
+            vec![(0, 1), (1, 2), (2, 3)]
+        );
+
+        // Skipping the first hop makes room for a longer tail
+        assert_ok!(Permission0::update_stream_permission(
+            get_origin(0),
+            first,
+            Some(BTreeMap::from([(2, u16::MAX)]).try_into().unwrap()),
+            None,
+            None,
+            None,
+            None,
+        ));
+
+        assert_ok!(try_delegate_full_stream(3, 4, stream_id));
+        assert!(pallet_permission0::ReverseStreamGraph::<Test>::contains_key((stream_id, 2, 0)));
+
+        assert_ok!(Permission0::revoke_permission(get_origin(0), first));
+        assert!(!pallet_permission0::StreamGraph::<Test>::contains_key((
+            stream_id, 0, 2
+        )));
+        assert!(!pallet_permission0::ReverseStreamGraph::<Test>::contains_key((stream_id, 2, 0)));
+    });
+}
+
+fn full_stream_delegation(
+    recipient: AccountId,
+    stream_id: H256,
+) -> pallet_permission0::StreamDelegation<Test> {
+    pallet_permission0::StreamDelegation {
+        recipients: BTreeMap::from([(recipient, u16::MAX)]).try_into().unwrap(),
+        allocation: pallet_permission0::StreamAllocation::Streams(
+            BTreeMap::from([(stream_id, Percent::from_percent(100))])
+                .try_into()
+                .unwrap(),
+        ),
+        distribution: pallet_permission0::DistributionControl::Manual,
+        duration: pallet_permission0::PermissionDuration::Indefinite,
+        revocation: pallet_permission0::RevocationTerms::RevocableByDelegator,
+        enforcement: pallet_permission0::EnforcementAuthority::None,
+        recipient_manager: None,
+        weight_setter: None,
+    }
+}
+
+fn bulk_events() -> Vec<pallet_permission0::Event<Test>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            RuntimeEvent::Permission0(
+                event @ (pallet_permission0::Event::BulkItemApplied { .. }
+                | pallet_permission0::Event::BulkOperationCompleted { .. }),
+            ) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn bulk_stream_operations_are_all_or_nothing() {
+    new_test_ext().execute_with(|| {
+        zero_min_burn();
+
+        for agent in 0..3 {
+            register_empty_agent(agent);
+        }
+
+        let stream_a = H256::repeat_byte(1);
+        let stream_b = H256::repeat_byte(2);
+
+        // Agent 5 is not registered, so the first delegation is reverted too
+        assert_err!(
+            Permission0::bulk_delegate_stream_permission(
+                get_origin(0),
+                vec![
+                    full_stream_delegation(1, stream_a),
+                    full_stream_delegation(5, stream_b),
+                ]
+                .try_into()
+                .unwrap(),
+            ),
+            pallet_permission0::Error::<Test>::NotRegisteredAgent
+        );
+        assert!(pallet_permission0::PermissionsByDelegator::<Test>::get(0).is_empty());
+        assert!(bulk_events().is_empty());
+
+        assert_ok!(Permission0::bulk_delegate_stream_permission(
+            get_origin(0),
+            vec![
+                full_stream_delegation(1, stream_a),
+                full_stream_delegation(2, stream_b),
+            ]
+            .try_into()
+            .unwrap(),
+        ));
+
+        let permissions = pallet_permission0::PermissionsByDelegator::<Test>::get(0);
+        assert_eq!(permissions.len(), 2);
+
+        let events = bulk_events();
+        assert_eq!(events.len(), 3);
+        assert_eq!(
+            events.last(),
+            Some(&pallet_permission0::Event::BulkOperationCompleted {
+                operation: pallet_permission0::BulkOperation::DelegateStream,
+                count: 2,
+            })
+        );
+
+        let permission_ids: Vec<_> = events
+            .iter()
+            .filter_map(|event| match event {
+                pallet_permission0::Event::BulkItemApplied { permission_id, .. } => {
+                    Some(*permission_id)
+                }
+                _ => None,
+            })
+            .collect();
+
+        accumulate(0, stream_a, as_tors(10));
+        accumulate(0, stream_b, as_tors(20));
+
+        assert_ok!(Permission0::bulk_execute_permissions(
+            get_origin(0),
+            permission_ids.clone().try_into().unwrap(),
+        ));
+        assert_eq!(get_balance(1), as_tors(10));
+        assert_eq!(get_balance(2), as_tors(20));
+
+        // Agent 1 can revoke the permission it receives but not the other one,
+        // so neither is revoked
+        assert_err!(
+            Permission0::bulk_revoke_permissions(
+                get_origin(1),
+                permission_ids.clone().try_into().unwrap(),
+            ),
+            pallet_permission0::Error::<Test>::NotAuthorizedToRevoke
+        );
+        assert_eq!(
+            pallet_permission0::PermissionsByDelegator::<Test>::get(0).len(),
+            2
+        );
+
+        assert_ok!(Permission0::bulk_revoke_permissions(
+            get_origin(0),
+            permission_ids.try_into().unwrap(),
+        ));
+        assert!(pallet_permission0::PermissionsByDelegator::<Test>::get(0).is_empty());
+    });
+}
@@ -49,6 +49,11 @@ pub trait Torus0Api<AccountId, Balance> {
 
     fn namespace_exists(agent: &AccountId, path: &NamespacePath) -> bool;
 
+    /// Resolves the account `who` acts for when performing `operation`: the
+    /// agent it is a proxy of, if it is allowed to perform the operation, or
+    /// `who` itself otherwise.
+    fn resolve_proxy(who: &AccountId, operation: ProxyOperation) -> AccountId;
+
     #[doc(hidden)]
     #[cfg(feature = "runtime-benchmarks")]
     fn force_register_agent(
@@ -71,6 +76,22 @@ pub trait Torus0Api<AccountId, Balance> {
     ) -> polkadot_sdk::frame_support::dispatch::DispatchResult;
 }
 
+/// Agent operations a proxy key can be allowed to perform on behalf of an
+/// agent, so the agent key holding the stake can be kept off hot machines.
+#[derive(
+    Encode, Decode, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, TypeInfo, MaxEncodedLen,
+)]
+pub enum ProxyOperation {
+    /// Setting, committing and revealing weights.
+    SetWeights,
+    /// Updating the agent metadata and fees.
+    UpdateAgent,
+    /// Creating and deleting namespaces.
+    ManageNamespaces,
+    /// Executing permissions delegated by the agent.
+    ExecutePermissions,
+}
+
 /// Number of total bytes a namespace path can contain.
 /// This might have to increase in the future, but is a good enough default value.
 /// If it ends up being formalized, the length can be described as a u8.
@@ -66,6 +66,11 @@ pub fn register<T: crate::Config>(
         crate::Error::<T>::AgentAlreadyRegistered
     );
 
+    ensure!(
+        !crate::ProxyAgents::<T>::contains_key(&agent_key),
+        crate::Error::<T>::KeyIsAgentProxy
+    );
+
     ensure!(
         crate::RegistrationsThisBlock::<T>::get() < crate::MaxRegistrationsPerBlock::<T>::get(),
         crate::Error::<T>::TooManyAgentRegistrationsThisBlock
@@ -121,7 +126,7 @@ pub fn register<T: crate::Config>(
 
     crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentRegistered(agent_key.clone()));
 
-    if let Some(allocator) = <T::Governance>::get_allocators().next() {
+    if let Some(allocator) = <T::Governance>::assign_allocator(&agent_key) {
         let _ = <T::Emission>::delegate_weight_control(&agent_key, &allocator);
     } else {
         polkadot_sdk::sp_tracing::warn!("no allocators available to delegate to for {agent_key:?}");
@@ -148,6 +153,8 @@ pub fn deregister<T: crate::Config>(agent_key: AccountIdOf<T>) -> DispatchResult
     crate::stake::clear_key::<T>(&agent_key)?;
 
     crate::Agents::<T>::remove(&agent_key);
+    crate::PendingKeyRotations::<T>::remove(&agent_key);
+    crate::proxy::clear::<T>(&agent_key);
 
     crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentUnregistered(agent_key));
 
@@ -1,11 +1,13 @@
 #![cfg(feature = "runtime-benchmarks")]
 
+use pallet_emission0_api::Emission0Api;
 use pallet_governance_api::GovernanceApi;
-use pallet_torus0_api::Torus0Api;
+use pallet_torus0_api::{ProxyOperation, Torus0Api};
 use polkadot_sdk::{
     frame_benchmarking::{account, v2::*},
     frame_system::RawOrigin,
     sp_runtime::Percent,
+    sp_std::{collections::btree_set::BTreeSet, vec::Vec},
 };
 
 use crate::*;
@@ -16,6 +18,16 @@ fn register_test_agent<T: Config>(id: &T::AccountId, name: &str) {
         .expect("failed to register agent");
 }
 
+/// Agent name unique to `index`, spelling its bytes with lowercase letters.
+fn indexed_name(prefix: &str, index: u32) -> Vec<u8> {
+    let suffix = index
+        .to_le_bytes()
+        .into_iter()
+        .flat_map(|byte| [byte / 16, byte % 16])
+        .map(|nibble| b'a'.saturating_add(nibble));
+    prefix.bytes().chain(suffix).collect()
+}
+
 #[benchmarks]
 mod benchmarks {
 
@@ -121,4 +133,96 @@ mod benchmarks {
         #[extrinsic_call]
         set_agent_update_cooldown(RawOrigin::Root, new_cooldown)
     }
+
+    #[benchmark]
+    fn rotate_agent_key() {
+        let agent: T::AccountId = account("agent", 0, 1);
+        let new_key: T::AccountId = account("new-key", 1, 1);
+        register_test_agent::<T>(&agent, "agent");
+
+        #[extrinsic_call]
+        rotate_agent_key(RawOrigin::Signed(agent), new_key)
+    }
+
+    #[benchmark]
+    fn enact_agent_key_rotation(r: Linear<0, 1_000>) {
+        let agent: T::AccountId = account("agent", 0, 1);
+        let new_key: T::AccountId = account("new-key", 1, 1);
+        register_test_agent::<T>(&agent, "agent");
+
+        <T::Governance>::force_set_whitelisted(&agent);
+        <T::Governance>::set_allocator(&agent);
+        for index in 0..r {
+            let delegator: T::AccountId = account("delegator", index, 2);
+            let name = indexed_name("delegator-", index);
+            Pallet::<T>::force_register_agent(&delegator, name.clone(), name.clone(), name)
+                .expect("failed to register delegator");
+            <T::Governance>::force_set_whitelisted(&delegator);
+            <T::Emission>::delegate_weight_control(&delegator, &agent)
+                .expect("failed to delegate weight control");
+        }
+
+        let amount = MinAllowedStake::<T>::get();
+        let _ = <T::Currency>::deposit_creating(&agent, amount.saturating_mul(2));
+        let _ = <T::Currency>::deposit_creating(&new_key, amount);
+        Pallet::<T>::force_set_stake(&agent, &agent, amount).expect("failed to add stake");
+
+        rotation::request::<T>(agent.clone(), new_key.clone())
+            .expect("failed to request key rotation");
+
+        #[extrinsic_call]
+        enact_agent_key_rotation(RawOrigin::Signed(new_key), agent, r)
+    }
+
+    #[benchmark]
+    fn cancel_agent_key_rotation() {
+        let agent: T::AccountId = account("agent", 0, 1);
+        let new_key: T::AccountId = account("new-key", 1, 1);
+        register_test_agent::<T>(&agent, "agent");
+
+        rotation::request::<T>(agent.clone(), new_key).expect("failed to request key rotation");
+
+        #[extrinsic_call]
+        cancel_agent_key_rotation(RawOrigin::Signed(agent))
+    }
+
+    #[benchmark]
+    fn add_agent_proxy() {
+        let agent: T::AccountId = account("agent", 0, 1);
+        let proxy: T::AccountId = account("proxy", 1, 1);
+        register_test_agent::<T>(&agent, "agent");
+
+        let operations = proxy::ProxyOperations::try_from(
+            [
+                ProxyOperation::SetWeights,
+                ProxyOperation::UpdateAgent,
+                ProxyOperation::ManageNamespaces,
+                ProxyOperation::ExecutePermissions,
+            ]
+            .into_iter()
+            .collect::<BTreeSet<_>>(),
+        )
+        .expect("failed to build proxy operations");
+
+        #[extrinsic_call]
+        add_agent_proxy(RawOrigin::Signed(agent), proxy, operations)
+    }
+
+    #[benchmark]
+    fn remove_agent_proxy() {
+        let agent: T::AccountId = account("agent", 0, 1);
+        let proxy: T::AccountId = account("proxy", 1, 1);
+        register_test_agent::<T>(&agent, "agent");
+
+        let operations = proxy::ProxyOperations::try_from(
+            [ProxyOperation::SetWeights]
+                .into_iter()
+                .collect::<BTreeSet<_>>(),
+        )
+        .expect("failed to build proxy operations");
+        proxy::add::<T>(agent.clone(), proxy.clone(), operations).expect("failed to add proxy");
+
+        #[extrinsic_call]
+        remove_agent_proxy(RawOrigin::Signed(agent), proxy)
+    }
 }
@@ -6,6 +6,8 @@ mod ext;
 pub mod fee;
 pub mod migrations;
 pub mod namespace;
+pub mod proxy;
+pub mod rotation;
 pub mod stake;
 
 pub mod benchmarking;
@@ -39,8 +41,10 @@ pub mod pallet {
     use frame::prelude::BlockNumberFor;
     use pallet_emission0_api::Emission0Api;
     use pallet_governance_api::GovernanceApi;
-    use pallet_permission0_api::{Permission0NamespacesApi, Permission0WalletApi, WalletScopeType};
-    use pallet_torus0_api::NamespacePathInner;
+    use pallet_permission0_api::{
+        Permission0KeyRotationApi, Permission0NamespacesApi, Permission0WalletApi, WalletScopeType,
+    };
+    use pallet_torus0_api::{NamespacePathInner, ProxyOperation};
     use polkadot_sdk::frame_support::traits::{NamedReservableCurrency, ReservableCurrency};
     use weights::WeightInfo;
 
@@ -162,6 +166,42 @@ pub mod pallet {
         T::DefaultNamespacePricingConfig,
     >;
 
+    /// Agent key rotations waiting to be enacted, indexed by the current
+    /// agent key.
+    #[pallet::storage]
+    pub type PendingKeyRotations<T: Config> =
+        StorageMap<_, Identity, AccountIdOf<T>, rotation::KeyRotation<T>>;
+
+    /// Proxy keys of each agent, mapping to the operations they are allowed
+    /// to perform on behalf of the agent.
+    #[pallet::storage]
+    pub type AgentProxies<T: Config> = StorageDoubleMap<
+        _,
+        Identity,
+        AccountIdOf<T>,
+        Identity,
+        AccountIdOf<T>,
+        proxy::ProxyOperations,
+    >;
+
+    /// The agent each proxy key acts for, indexed by the proxy key.
+    #[pallet::storage]
+    pub type ProxyAgents<T: Config> = StorageMap<_, Identity, AccountIdOf<T>, AccountIdOf<T>>;
+
+    /// Amounts a spender may transfer on behalf of an owner through the
+    /// native token EVM precompile, indexed by the owner and the spender.
+    /// `u128::MAX` is an unlimited allowance.
+    #[pallet::storage]
+    pub type NativeTokenAllowances<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        Blake2_128Concat,
+        AccountIdOf<T>,
+        BalanceOf<T>,
+        ValueQuery,
+    >;
+
     #[pallet::hooks]
     impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
         fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
@@ -252,6 +292,16 @@ pub mod pallet {
         #[pallet::constant]
         type DefaultNamespacePricingConfig: Get<namespace::NamespacePricingConfig<Self>>;
 
+        /// Number of blocks the previous agent key has to cancel a key
+        /// rotation before anyone can enact it. Rotations enacted by the new
+        /// key skip this delay.
+        #[pallet::constant]
+        type KeyRotationDelay: Get<BlockNumberFor<Self>>;
+
+        /// Maximum number of proxy keys an agent can have.
+        #[pallet::constant]
+        type MaxAgentProxies: Get<u32>;
+
         type RuntimeEvent: From<Event<Self>>
             + IsType<<Self as polkadot_sdk::frame_system::Config>::RuntimeEvent>;
 
@@ -266,7 +316,8 @@ pub mod pallet {
 
         type Emission: Emission0Api<Self::AccountId>;
         type Permission0: Permission0NamespacesApi<Self::AccountId, NamespacePath>
-            + Permission0WalletApi<Self::AccountId>;
+            + Permission0WalletApi<Self::AccountId>
+            + Permission0KeyRotationApi<Self::AccountId>;
 
         type WeightInfo: WeightInfo;
     }
@@ -377,7 +428,8 @@ pub mod pallet {
             staking_fee: Option<Percent>,
             weight_control_fee: Option<Percent>,
         ) -> DispatchResult {
-            let agent_key = ensure_signed(origin)?;
+            let agent_key =
+                proxy::resolve::<T>(ensure_signed(origin)?, ProxyOperation::UpdateAgent);
             agent::update::<T>(agent_key, url, metadata, staking_fee, weight_control_fee)
         }
 
@@ -397,7 +449,8 @@ pub mod pallet {
         #[pallet::call_index(7)]
         #[pallet::weight(Weight::default())]
         pub fn create_namespace(origin: OriginFor<T>, path: NamespacePathInner) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
+            let owner =
+                proxy::resolve::<T>(ensure_signed(origin)?, ProxyOperation::ManageNamespaces);
 
             ensure!(
                 <T as pallet::Config>::Governance::can_create_namespace(&owner),
@@ -414,7 +467,8 @@ pub mod pallet {
         #[pallet::call_index(8)]
         #[pallet::weight(Weight::default())]
         pub fn delete_namespace(origin: OriginFor<T>, path: NamespacePathInner) -> DispatchResult {
-            let owner = ensure_signed(origin)?;
+            let owner =
+                proxy::resolve::<T>(ensure_signed(origin)?, ProxyOperation::ManageNamespaces);
 
             let namespace_path =
                 NamespacePath::new_agent(&path).map_err(|_| Error::<T>::InvalidNamespacePath)?;
@@ -426,6 +480,64 @@ pub mod pallet {
 
             namespace::delete_namespace::<T>(NamespaceOwnership::Account(owner), namespace_path)
         }
+
+        /// Requests the rotation of origin's agent key to `new_key`. The
+        /// rotation can be enacted right away by the new key, or by anyone
+        /// after [`Config::KeyRotationDelay`] blocks, unless cancelled by the
+        /// current key in between. Requesting again replaces the pending
+        /// rotation.
+        #[pallet::call_index(9)]
+        #[pallet::weight((T::WeightInfo::rotate_agent_key(), DispatchClass::Normal, Pays::Yes))]
+        pub fn rotate_agent_key(origin: OriginFor<T>, new_key: AccountIdOf<T>) -> DispatchResult {
+            let agent_key = ensure_signed(origin)?;
+            rotation::request::<T>(agent_key, new_key)
+        }
+
+        /// Enacts the key rotation requested by `agent_key`, moving its agent,
+        /// stake, namespaces, weight control and permissions to the new key.
+        /// `references` must be at least the number of records of other
+        /// accounts referencing the agent key, see
+        /// [`rotation::key_references`].
+        #[pallet::call_index(10)]
+        #[pallet::weight((T::WeightInfo::enact_agent_key_rotation(*references), DispatchClass::Normal, Pays::Yes))]
+        pub fn enact_agent_key_rotation(
+            origin: OriginFor<T>,
+            agent_key: AccountIdOf<T>,
+            references: u32,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            rotation::enact::<T>(who, agent_key, references)
+        }
+
+        /// Cancels the key rotation requested by origin's agent key.
+        #[pallet::call_index(11)]
+        #[pallet::weight((T::WeightInfo::cancel_agent_key_rotation(), DispatchClass::Normal, Pays::Yes))]
+        pub fn cancel_agent_key_rotation(origin: OriginFor<T>) -> DispatchResult {
+            let agent_key = ensure_signed(origin)?;
+            rotation::cancel::<T>(agent_key)
+        }
+
+        /// Allows `proxy` to perform `operations` on behalf of origin's agent,
+        /// resolving calls it signs to the agent key. Adding an existing proxy
+        /// replaces its operations.
+        #[pallet::call_index(12)]
+        #[pallet::weight((T::WeightInfo::add_agent_proxy(), DispatchClass::Normal, Pays::Yes))]
+        pub fn add_agent_proxy(
+            origin: OriginFor<T>,
+            proxy: AccountIdOf<T>,
+            operations: proxy::ProxyOperations,
+        ) -> DispatchResult {
+            let agent_key = ensure_signed(origin)?;
+            proxy::add::<T>(agent_key, proxy, operations)
+        }
+
+        /// Removes a proxy of origin's agent. A proxy can also remove itself.
+        #[pallet::call_index(13)]
+        #[pallet::weight((T::WeightInfo::remove_agent_proxy(), DispatchClass::Normal, Pays::Yes))]
+        pub fn remove_agent_proxy(origin: OriginFor<T>, proxy: AccountIdOf<T>) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            proxy::remove::<T>(who, proxy)
+        }
     }
 
     #[pallet::event]
@@ -456,6 +568,34 @@ pub mod pallet {
             owner: NamespaceOwnership<T>,
             path: NamespacePath,
         },
+        /// An agent requested the rotation of its key
+        AgentKeyRotationRequested {
+            old_key: AccountIdOf<T>,
+            new_key: AccountIdOf<T>,
+            enactable_at: BlockNumberFor<T>,
+        },
+        /// A pending agent key rotation was cancelled
+        AgentKeyRotationCancelled {
+            old_key: AccountIdOf<T>,
+            new_key: AccountIdOf<T>,
+        },
+        /// An agent key was rotated, moving the agent and its records to the
+        /// new key
+        AgentKeyRotated {
+            old_key: AccountIdOf<T>,
+            new_key: AccountIdOf<T>,
+        },
+        /// A proxy key was allowed to perform operations on behalf of an agent
+        AgentProxyAdded {
+            agent: AccountIdOf<T>,
+            proxy: AccountIdOf<T>,
+            operations: proxy::ProxyOperations,
+        },
+        /// A proxy key was removed from an agent
+        AgentProxyRemoved {
+            agent: AccountIdOf<T>,
+            proxy: AccountIdOf<T>,
+        },
     }
 
     #[pallet::error]
@@ -556,6 +696,28 @@ pub mod pallet {
         NamespacesFrozen,
         /// The stake is being delegated exclusively.
         StakeIsDelegated,
+        /// The new agent key is the current key, is already registered as an
+        /// agent or is a proxy of an agent.
+        InvalidNewAgentKey,
+        /// The agent has no pending key rotation.
+        KeyRotationNotFound,
+        /// The key rotation can only be enacted by the new key until the
+        /// delay has passed.
+        KeyRotationDelayNotElapsed,
+        /// The proxy key is the agent itself, an agent or a proxy of another
+        /// agent.
+        InvalidAgentProxy,
+        /// The agent has reached the maximum number of proxies.
+        TooManyAgentProxies,
+        /// A proxy must be allowed to perform at least one operation.
+        NoProxyOperations,
+        /// The proxy key is not a proxy of the agent.
+        AgentProxyNotFound,
+        /// The key is a proxy of an agent.
+        KeyIsAgentProxy,
+        /// The agent key is referenced by more records than declared when
+        /// enacting its key rotation.
+        TooManyKeyReferences,
     }
 }
 
@@ -649,6 +811,13 @@ impl<T: Config>
         Namespaces::<T>::contains_key(NamespaceOwnership::Account(agent.clone()), path)
     }
 
+    fn resolve_proxy(
+        who: &T::AccountId,
+        operation: pallet_torus0_api::ProxyOperation,
+    ) -> T::AccountId {
+        proxy::resolve::<T>(who.clone(), operation)
+    }
+
     #[cfg(feature = "runtime-benchmarks")]
     fn force_register_agent(
         id: &T::AccountId,
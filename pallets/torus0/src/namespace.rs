@@ -311,3 +311,28 @@ pub fn delete_namespace<T: Config>(
 
     Ok(())
 }
+
+/// Moves the namespaces owned by `old_key` to `new_key`, returning the total
+/// deposit held for them. Paths derive from the agent name, so they are kept
+/// as they are.
+pub(crate) fn rotate_owner<T: Config>(
+    old_key: &T::AccountId,
+    new_key: &T::AccountId,
+) -> BalanceOf<T> {
+    let old_owner = NamespaceOwnership::<T>::Account(old_key.clone());
+    let new_owner = NamespaceOwnership::<T>::Account(new_key.clone());
+
+    let mut total_deposit = BalanceOf::<T>::zero();
+    let namespaces: Vec<_> = Namespaces::<T>::drain_prefix(&old_owner).collect();
+    for (path, metadata) in namespaces {
+        total_deposit = total_deposit.saturating_add(metadata.deposit);
+        Namespaces::<T>::insert(&new_owner, path, metadata);
+    }
+
+    let count = NamespaceCount::<T>::take(&old_owner);
+    NamespaceCount::<T>::mutate(&new_owner, |new_count| {
+        *new_count = new_count.saturating_add(count)
+    });
+
+    total_deposit
+}
@@ -0,0 +1,111 @@
+use pallet_torus0_api::ProxyOperation;
+use polkadot_sdk::{
+    frame_support::{dispatch::DispatchResult, ensure, traits::Get},
+    sp_core::ConstU32,
+    sp_runtime::BoundedBTreeSet,
+    sp_std::vec::Vec,
+    sp_tracing::debug_span,
+};
+
+use crate::{AccountIdOf, AgentProxies, ProxyAgents, agent};
+
+/// Operations a proxy key is allowed to perform on behalf of its agent.
+pub type ProxyOperations = BoundedBTreeSet<ProxyOperation, ConstU32<MAX_PROXY_OPERATIONS>>;
+
+/// Number of [`ProxyOperation`] variants.
+pub const MAX_PROXY_OPERATIONS: u32 = 4;
+
+/// Allows `proxy` to perform `operations` on behalf of `agent_key`. Adding an
+/// existing proxy of the agent replaces its operations.
+pub fn add<T: crate::Config>(
+    agent_key: AccountIdOf<T>,
+    proxy: AccountIdOf<T>,
+    operations: ProxyOperations,
+) -> DispatchResult {
+    let span = debug_span!("add_proxy", agent.key = ?agent_key);
+    let _guard = span.enter();
+
+    ensure!(
+        agent::exists::<T>(&agent_key),
+        crate::Error::<T>::AgentDoesNotExist
+    );
+    ensure!(!operations.is_empty(), crate::Error::<T>::NoProxyOperations);
+    ensure!(
+        proxy != agent_key && !agent::exists::<T>(&proxy),
+        crate::Error::<T>::InvalidAgentProxy
+    );
+
+    match ProxyAgents::<T>::get(&proxy) {
+        Some(current) => ensure!(current == agent_key, crate::Error::<T>::InvalidAgentProxy),
+        None => ensure!(
+            (AgentProxies::<T>::iter_key_prefix(&agent_key).count() as u32)
+                < T::MaxAgentProxies::get(),
+            crate::Error::<T>::TooManyAgentProxies
+        ),
+    }
+
+    ProxyAgents::<T>::insert(&proxy, &agent_key);
+    AgentProxies::<T>::insert(&agent_key, &proxy, &operations);
+
+    crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentProxyAdded {
+        agent: agent_key,
+        proxy,
+        operations,
+    });
+
+    Ok(())
+}
+
+/// Removes `proxy` from the proxies of an agent. Called either by the agent
+/// or by the proxy itself, giving up its access.
+pub fn remove<T: crate::Config>(who: AccountIdOf<T>, proxy: AccountIdOf<T>) -> DispatchResult {
+    let agent_key = if who == proxy {
+        ProxyAgents::<T>::get(&proxy).ok_or(crate::Error::<T>::AgentProxyNotFound)?
+    } else {
+        who
+    };
+
+    ensure!(
+        AgentProxies::<T>::take(&agent_key, &proxy).is_some(),
+        crate::Error::<T>::AgentProxyNotFound
+    );
+    ProxyAgents::<T>::remove(&proxy);
+
+    crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentProxyRemoved {
+        agent: agent_key,
+        proxy,
+    });
+
+    Ok(())
+}
+
+/// Returns the agent `who` acts for when performing `operation`. Keys that
+/// are not proxies, or are not allowed to perform the operation, act for
+/// themselves.
+pub fn resolve<T: crate::Config>(who: AccountIdOf<T>, operation: ProxyOperation) -> AccountIdOf<T> {
+    let Some(agent_key) = ProxyAgents::<T>::get(&who) else {
+        return who;
+    };
+
+    match AgentProxies::<T>::get(&agent_key, &who) {
+        Some(operations) if operations.contains(&operation) => agent_key,
+        _ => who,
+    }
+}
+
+/// Removes every proxy of `agent_key`.
+pub(crate) fn clear<T: crate::Config>(agent_key: &AccountIdOf<T>) {
+    for proxy in AgentProxies::<T>::drain_prefix(agent_key).map(|(proxy, _)| proxy) {
+        ProxyAgents::<T>::remove(&proxy);
+    }
+}
+
+/// Moves every proxy of `old_key` over to `new_key`.
+pub(crate) fn rotate_key<T: crate::Config>(old_key: &AccountIdOf<T>, new_key: &AccountIdOf<T>) {
+    let proxies: Vec<_> = AgentProxies::<T>::drain_prefix(old_key).collect();
+
+    for (proxy, operations) in proxies {
+        ProxyAgents::<T>::insert(&proxy, new_key);
+        AgentProxies::<T>::insert(new_key, &proxy, operations);
+    }
+}
@@ -0,0 +1,167 @@
+use codec::{Decode, Encode, MaxEncodedLen};
+use pallet_emission0_api::Emission0Api;
+use pallet_governance_api::GovernanceApi;
+use pallet_permission0_api::Permission0KeyRotationApi;
+use polkadot_sdk::{
+    frame_support::{
+        CloneNoBound, DebugNoBound, EqNoBound, PartialEqNoBound,
+        dispatch::DispatchResult,
+        ensure,
+        storage::with_storage_layer,
+        traits::{BalanceStatus, Get, ReservableCurrency},
+    },
+    polkadot_sdk_frame::prelude::BlockNumberFor,
+    sp_runtime::traits::Saturating,
+    sp_tracing::debug_span,
+};
+use scale_info::TypeInfo;
+
+use crate::{AccountIdOf, PendingKeyRotations, agent};
+
+/// A key rotation requested by an agent. See [`crate::PendingKeyRotations`].
+#[derive(
+    CloneNoBound, DebugNoBound, PartialEqNoBound, EqNoBound, Encode, Decode, MaxEncodedLen, TypeInfo,
+)]
+#[scale_info(skip_type_params(T))]
+pub struct KeyRotation<T: crate::Config> {
+    /// Key that will replace the agent key.
+    pub new_key: AccountIdOf<T>,
+    /// Block from which anyone can enact the rotation.
+    pub enactable_at: BlockNumberFor<T>,
+}
+
+/// Requests the rotation of `agent_key` to `new_key`, replacing any rotation
+/// already pending for the agent.
+pub fn request<T: crate::Config>(
+    agent_key: AccountIdOf<T>,
+    new_key: AccountIdOf<T>,
+) -> DispatchResult {
+    ensure!(
+        agent::exists::<T>(&agent_key),
+        crate::Error::<T>::AgentDoesNotExist
+    );
+    ensure_valid_new_key::<T>(&agent_key, &new_key)?;
+
+    let enactable_at = <polkadot_sdk::frame_system::Pallet<T>>::block_number()
+        .saturating_add(T::KeyRotationDelay::get());
+
+    PendingKeyRotations::<T>::insert(
+        &agent_key,
+        KeyRotation {
+            new_key: new_key.clone(),
+            enactable_at,
+        },
+    );
+
+    crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentKeyRotationRequested {
+        old_key: agent_key,
+        new_key,
+        enactable_at,
+    });
+
+    Ok(())
+}
+
+/// Cancels the rotation pending for `agent_key`.
+pub fn cancel<T: crate::Config>(agent_key: AccountIdOf<T>) -> DispatchResult {
+    let rotation =
+        PendingKeyRotations::<T>::take(&agent_key).ok_or(crate::Error::<T>::KeyRotationNotFound)?;
+
+    crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentKeyRotationCancelled {
+        old_key: agent_key,
+        new_key: rotation.new_key,
+    });
+
+    Ok(())
+}
+
+/// Enacts the rotation pending for `agent_key`. The new key signing the
+/// enactment proves both keys agree on the rotation, so it is enacted right
+/// away. Anyone else has to wait for the cancellation window to pass.
+/// `references` bounds the records of other accounts the rotation rewrites.
+pub fn enact<T: crate::Config>(
+    who: AccountIdOf<T>,
+    agent_key: AccountIdOf<T>,
+    references: u32,
+) -> DispatchResult {
+    let span = debug_span!("enact_key_rotation", agent.key = ?agent_key);
+    let _guard = span.enter();
+
+    let rotation =
+        PendingKeyRotations::<T>::get(&agent_key).ok_or(crate::Error::<T>::KeyRotationNotFound)?;
+
+    let current_block = <polkadot_sdk::frame_system::Pallet<T>>::block_number();
+    ensure!(
+        who == rotation.new_key || current_block >= rotation.enactable_at,
+        crate::Error::<T>::KeyRotationDelayNotElapsed
+    );
+
+    ensure!(
+        agent::exists::<T>(&agent_key),
+        crate::Error::<T>::AgentDoesNotExist
+    );
+    ensure_valid_new_key::<T>(&agent_key, &rotation.new_key)?;
+    ensure!(
+        key_references::<T>(&agent_key) <= references,
+        crate::Error::<T>::TooManyKeyReferences
+    );
+
+    with_storage_layer(|| rotate_key::<T>(&agent_key, &rotation.new_key))?;
+
+    PendingKeyRotations::<T>::remove(&agent_key);
+
+    crate::Pallet::<T>::deposit_event(crate::Event::<T>::AgentKeyRotated {
+        old_key: agent_key,
+        new_key: rotation.new_key,
+    });
+
+    Ok(())
+}
+
+/// Number of records of other accounts rewritten when rotating the agent key:
+/// weights set on it, weight control and votes delegated to it, and validator
+/// bonds. The key rotation weight grows with it.
+pub fn key_references<T: crate::Config>(agent_key: &AccountIdOf<T>) -> u32 {
+    <T::Emission>::key_references(agent_key)
+        .saturating_add(<T::Governance>::key_references(agent_key))
+}
+
+fn ensure_valid_new_key<T: crate::Config>(
+    agent_key: &AccountIdOf<T>,
+    new_key: &AccountIdOf<T>,
+) -> DispatchResult {
+    ensure!(
+        new_key != agent_key
+            && !agent::exists::<T>(new_key)
+            && !crate::ProxyAgents::<T>::contains_key(new_key),
+        crate::Error::<T>::InvalidNewAgentKey
+    );
+
+    Ok(())
+}
+
+/// Moves the agent and every record keyed by its account, in this pallet and
+/// in the emission, governance and permission pallets, to the new key.
+fn rotate_key<T: crate::Config>(
+    old_key: &AccountIdOf<T>,
+    new_key: &AccountIdOf<T>,
+) -> DispatchResult {
+    let mut agent =
+        crate::Agents::<T>::take(old_key).ok_or(crate::Error::<T>::AgentDoesNotExist)?;
+    agent.key = new_key.clone();
+    crate::Agents::<T>::insert(new_key, agent);
+
+    crate::stake::rotate_key::<T>(old_key, new_key)?;
+    crate::proxy::rotate_key::<T>(old_key, new_key);
+
+    let deposits = crate::namespace::rotate_owner::<T>(old_key, new_key);
+    let missing =
+        T::Currency::repatriate_reserved(old_key, new_key, deposits, BalanceStatus::Reserved)?;
+    ensure!(missing == 0, crate::Error::<T>::BalanceNotRemoved);
+
+    <T::Governance>::rotate_key(old_key, new_key);
+    <T::Emission>::rotate_key(old_key, new_key);
+    <T::Permission0>::rotate_key(old_key, new_key)?;
+
+    Ok(())
+}
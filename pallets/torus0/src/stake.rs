@@ -1,5 +1,9 @@
 use polkadot_sdk::{
-    frame_support::{dispatch::DispatchResult, ensure, traits::NamedReservableCurrency},
+    frame_support::{
+        dispatch::DispatchResult,
+        ensure,
+        traits::{BalanceStatus, NamedReservableCurrency},
+    },
     sp_std::{collections::btree_map::BTreeMap, vec::Vec},
 };
 
@@ -23,12 +27,7 @@ pub fn add_stake<T: crate::Config>(
     T::Currency::reserve_named(STAKE_IDENTIFIER, &staker, amount)
         .map_err(|_| crate::Error::<T>::NotEnoughBalanceToStake)?;
 
-    StakedBy::<T>::mutate(&staked, &staker, |stake| {
-        *stake = Some(stake.unwrap_or(0).saturating_add(amount))
-    });
-    StakingTo::<T>::mutate(&staker, &staked, |stake| {
-        *stake = Some(stake.unwrap_or(0).saturating_add(amount))
-    });
+    increase_stake::<T>(&staker, &staked, amount);
 
     TotalStake::<T>::mutate(|total_stake| *total_stake = total_stake.saturating_add(amount));
 
@@ -111,6 +110,68 @@ pub(crate) fn clear_key<T: crate::Config>(key: &AccountIdOf<T>) -> DispatchResul
     Ok(())
 }
 
+/// Moves the stake held by and staked on `old_key` to `new_key`, along with
+/// the tokens reserved for the stake it holds. Used when rotating agent keys.
+pub(crate) fn rotate_key<T: crate::Config>(
+    old_key: &AccountIdOf<T>,
+    new_key: &AccountIdOf<T>,
+) -> DispatchResult {
+    let staking_to: Vec<_> = StakingTo::<T>::iter_prefix(old_key).collect();
+    let staked_by: Vec<_> = StakedBy::<T>::iter_prefix(old_key)
+        .filter(|(staker, _)| staker != old_key)
+        .collect();
+
+    for (staked, _) in &staking_to {
+        StakingTo::<T>::remove(old_key, staked);
+        StakedBy::<T>::remove(staked, old_key);
+    }
+
+    for (staker, _) in &staked_by {
+        StakedBy::<T>::remove(old_key, staker);
+        StakingTo::<T>::remove(staker, old_key);
+    }
+
+    let mut held_stake: BalanceOf<T> = 0;
+    for (staked, amount) in staking_to {
+        let staked = if &staked == old_key {
+            new_key.clone()
+        } else {
+            staked
+        };
+
+        held_stake = held_stake.saturating_add(amount);
+        increase_stake::<T>(new_key, &staked, amount);
+    }
+
+    for (staker, amount) in staked_by {
+        increase_stake::<T>(&staker, new_key, amount);
+    }
+
+    let missing = T::Currency::repatriate_reserved_named(
+        STAKE_IDENTIFIER,
+        old_key,
+        new_key,
+        held_stake,
+        BalanceStatus::Reserved,
+    )?;
+    ensure!(missing == 0, crate::Error::<T>::StakeNotRemoved);
+
+    Ok(())
+}
+
+fn increase_stake<T: crate::Config>(
+    staker: &AccountIdOf<T>,
+    staked: &AccountIdOf<T>,
+    amount: BalanceOf<T>,
+) {
+    StakedBy::<T>::mutate(staked, staker, |stake| {
+        *stake = Some(stake.unwrap_or(0).saturating_add(amount))
+    });
+    StakingTo::<T>::mutate(staker, staked, |stake| {
+        *stake = Some(stake.unwrap_or(0).saturating_add(amount))
+    });
+}
+
 #[inline]
 pub fn sum_staking_to<T: crate::Config>(staker: &AccountIdOf<T>) -> BalanceOf<T> {
     StakingTo::<T>::iter_prefix_values(staker).sum()
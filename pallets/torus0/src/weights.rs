@@ -45,6 +45,11 @@ pub trait WeightInfo {
 	fn deregister_agent() -> Weight;
 	fn update_agent() -> Weight;
 	fn set_agent_update_cooldown() -> Weight;
+	fn rotate_agent_key() -> Weight;
+	fn enact_agent_key_rotation(r: u32, ) -> Weight;
+	fn cancel_agent_key_rotation() -> Weight;
+	fn add_agent_proxy() -> Weight;
+	fn remove_agent_proxy() -> Weight;
 }
 
 /// Weights for `pallet_torus0` using the Substrate node and recommended hardware.
@@ -198,6 +203,86 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(2_000_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Torus0::Agents` (r:2 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::PendingKeyRotations` (r:0 w:1)
+	/// Proof: `Torus0::PendingKeyRotations` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	fn rotate_agent_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `270`
+		//  Estimated: `7654`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 7654)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Torus0::PendingKeyRotations` (r:1 w:1)
+	/// Proof: `Torus0::PendingKeyRotations` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:2 w:2)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakingTo` (r:2 w:2)
+	/// Proof: `Torus0::StakingTo` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakedBy` (r:2 w:2)
+	/// Proof: `Torus0::StakedBy` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegators` (r:1001 w:1000)
+	/// Proof: `Emission0::WeightControlDelegators` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegation` (r:1000 w:1000)
+	/// Proof: `Emission0::WeightControlDelegation` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegatorCount` (r:1 w:1)
+	/// Proof: `Emission0::WeightControlDelegatorCount` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[0, 1000]`.
+	fn enact_agent_key_rotation(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `712 + r * (96 ±0)`
+		//  Estimated: `7654 + r * (2603 ±0)`
+		// Minimum execution time: 95_000_000 picoseconds.
+		Weight::from_parts(98_000_000, 7654)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(24_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
+			.saturating_add(T::DbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
+	/// Storage: `Torus0::PendingKeyRotations` (r:1 w:1)
+	/// Proof: `Torus0::PendingKeyRotations` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	fn cancel_agent_key_rotation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `180`
+		//  Estimated: `3537`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 3537)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Torus0::Agents` (r:2 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::ProxyAgents` (r:1 w:1)
+	/// Proof: `Torus0::ProxyAgents` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::AgentProxies` (r:1 w:1)
+	/// Proof: `Torus0::AgentProxies` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	fn add_agent_proxy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `270`
+		//  Estimated: `4322`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 4322)
+			.saturating_add(T::DbWeight::get().reads(4_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Torus0::AgentProxies` (r:1 w:1)
+	/// Proof: `Torus0::AgentProxies` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::ProxyAgents` (r:0 w:1)
+	/// Proof: `Torus0::ProxyAgents` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	fn remove_agent_proxy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `220`
+		//  Estimated: `3534`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3534)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests.
@@ -350,4 +435,84 @@ impl WeightInfo for () {
 		Weight::from_parts(2_000_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: `Torus0::Agents` (r:2 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::PendingKeyRotations` (r:0 w:1)
+	/// Proof: `Torus0::PendingKeyRotations` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	fn rotate_agent_key() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `270`
+		//  Estimated: `7654`
+		// Minimum execution time: 14_000_000 picoseconds.
+		Weight::from_parts(15_000_000, 7654)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Torus0::PendingKeyRotations` (r:1 w:1)
+	/// Proof: `Torus0::PendingKeyRotations` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::Agents` (r:2 w:2)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakingTo` (r:2 w:2)
+	/// Proof: `Torus0::StakingTo` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::StakedBy` (r:2 w:2)
+	/// Proof: `Torus0::StakedBy` (`max_values`: None, `max_size`: Some(80), added: 2555, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegators` (r:1001 w:1000)
+	/// Proof: `Emission0::WeightControlDelegators` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegation` (r:1000 w:1000)
+	/// Proof: `Emission0::WeightControlDelegation` (`max_values`: None, `max_size`: Some(128), added: 2603, mode: `MaxEncodedLen`)
+	/// Storage: `Emission0::WeightControlDelegatorCount` (r:1 w:1)
+	/// Proof: `Emission0::WeightControlDelegatorCount` (`max_values`: None, `max_size`: Some(36), added: 2511, mode: `MaxEncodedLen`)
+	/// The range of component `r` is `[0, 1000]`.
+	fn enact_agent_key_rotation(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `712 + r * (96 ±0)`
+		//  Estimated: `7654 + r * (2603 ±0)`
+		// Minimum execution time: 95_000_000 picoseconds.
+		Weight::from_parts(98_000_000, 7654)
+			.saturating_add(Weight::from_parts(9_000_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(24_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
+			.saturating_add(RocksDbWeight::get().writes((3_u64).saturating_mul(r.into())))
+			.saturating_add(Weight::from_parts(0, 2603).saturating_mul(r.into()))
+	}
+	/// Storage: `Torus0::PendingKeyRotations` (r:1 w:1)
+	/// Proof: `Torus0::PendingKeyRotations` (`max_values`: None, `max_size`: Some(72), added: 2547, mode: `MaxEncodedLen`)
+	fn cancel_agent_key_rotation() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `180`
+		//  Estimated: `3537`
+		// Minimum execution time: 9_000_000 picoseconds.
+		Weight::from_parts(10_000_000, 3537)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: `Torus0::Agents` (r:2 w:0)
+	/// Proof: `Torus0::Agents` (`max_values`: None, `max_size`: Some(857), added: 3332, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::ProxyAgents` (r:1 w:1)
+	/// Proof: `Torus0::ProxyAgents` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::AgentProxies` (r:1 w:1)
+	/// Proof: `Torus0::AgentProxies` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	fn add_agent_proxy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `270`
+		//  Estimated: `4322`
+		// Minimum execution time: 15_000_000 picoseconds.
+		Weight::from_parts(16_000_000, 4322)
+			.saturating_add(RocksDbWeight::get().reads(4_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: `Torus0::AgentProxies` (r:1 w:1)
+	/// Proof: `Torus0::AgentProxies` (`max_values`: None, `max_size`: Some(69), added: 2544, mode: `MaxEncodedLen`)
+	/// Storage: `Torus0::ProxyAgents` (r:0 w:1)
+	/// Proof: `Torus0::ProxyAgents` (`max_values`: None, `max_size`: Some(64), added: 2539, mode: `MaxEncodedLen`)
+	fn remove_agent_proxy() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `220`
+		//  Estimated: `3534`
+		// Minimum execution time: 11_000_000 picoseconds.
+		Weight::from_parts(12_000_000, 3534)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -1,12 +1,27 @@
+use std::collections::{BTreeMap, BTreeSet};
+
 use pallet_governance_api::GovernanceApi;
-use pallet_torus0::{AgentUpdateCooldown, Agents, Burn, Error, agent::Agent};
-use pallet_torus0_api::NamespacePath;
-use polkadot_sdk::{frame_support::assert_err, sp_core::Get, sp_runtime::Percent};
+use pallet_permission0_api::generate_root_stream_id;
+use pallet_torus0::{
+    AgentProxies, AgentUpdateCooldown, Agents, Burn, Error, Namespaces, PendingKeyRotations,
+    ProxyAgents, StakedBy, StakingTo, agent::Agent, namespace::NamespaceOwnership,
+    proxy::ProxyOperations,
+};
+use pallet_torus0_api::{NamespacePath, ProxyOperation, Torus0Api};
+use polkadot_sdk::{
+    frame_support::{assert_err, traits::ReservableCurrency},
+    sp_core::Get,
+    sp_runtime::Percent,
+};
 use test_utils::{
-    Governance, Test, assert_ok, clear_cooldown, get_balance, get_origin,
-    pallet_emission0::{PendingEmission, WeightControlDelegation},
+    Balances, Governance, RuntimeEvent, System, Test, add_balance, add_stake, as_tors, assert_ok,
+    clear_cooldown, delegate_stream_permission, get_balance, get_origin,
+    pallet_emission0::{
+        PendingEmission, WeightControlDelegation, WeightControlDelegators,
+        weight_control::{set_delegation, whole_weight_control},
+    },
     pallet_governance::{self, Allocators, DaoTreasuryAddress, TreasuryEmissionFee},
-    step_block,
+    pallet_permission0, register_empty_agent, step_block,
 };
 
 #[test]
@@ -61,7 +76,7 @@ fn register_correctly() {
 
         assert_eq!(
             WeightControlDelegation::<Test>::get(agent.key),
-            Some(allocator_id)
+            Some(whole_weight_control::<Test>(allocator_id))
         );
 
         assert!(pallet_torus0::Namespaces::<Test>::contains_key(
@@ -622,3 +637,310 @@ fn agent_freezing() {
         );
     });
 }
+
+#[test]
+fn rotate_agent_key_moves_agent_records() {
+    test_utils::new_test_ext().execute_with(|| {
+        let agent = 0;
+        let new_key = 1;
+        let staker = 2;
+        let staked = 3;
+        let delegator = 4;
+
+        assert_ok!(pallet_governance::whitelist::add_to_whitelist::<Test>(
+            agent
+        ));
+        assert_ok!(pallet_torus0::Pallet::<Test>::register_agent(
+            get_origin(agent),
+            b"alice".to_vec(),
+            b"idk://agent".to_vec(),
+            b"idk://agent".to_vec(),
+        ));
+
+        let stake = as_tors(10);
+        add_stake(staker, agent, stake);
+        add_stake(agent, staked, stake);
+        add_balance(new_key, as_tors(1));
+
+        set_delegation::<Test>(&delegator, Some(whole_weight_control::<Test>(agent)));
+
+        let mut streams = BTreeMap::new();
+        streams.insert(generate_root_stream_id(&agent), Percent::from_percent(10));
+        let permission_id = assert_ok!(delegate_stream_permission(
+            agent,
+            vec![(staked, u16::MAX)],
+            pallet_permission0_api::StreamAllocation::Streams(streams),
+            pallet_permission0_api::DistributionControl::Manual,
+            pallet_permission0_api::PermissionDuration::Indefinite,
+            pallet_permission0_api::RevocationTerms::Irrevocable,
+            pallet_permission0_api::EnforcementAuthority::None,
+        ));
+
+        assert_ok!(pallet_torus0::Pallet::<Test>::rotate_agent_key(
+            get_origin(agent),
+            new_key
+        ));
+
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::enact_agent_key_rotation(get_origin(staker), agent, 1),
+            Error::<Test>::KeyRotationDelayNotElapsed
+        );
+
+        assert_eq!(pallet_torus0::rotation::key_references::<Test>(&agent), 1);
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::enact_agent_key_rotation(get_origin(new_key), agent, 0),
+            Error::<Test>::TooManyKeyReferences
+        );
+
+        // The new key signing the enactment skips the cancellation window
+        assert_ok!(pallet_torus0::Pallet::<Test>::enact_agent_key_rotation(
+            get_origin(new_key),
+            agent,
+            1
+        ));
+
+        assert!(Agents::<Test>::get(agent).is_none());
+        assert_eq!(
+            Agents::<Test>::get(new_key).map(|agent| agent.key),
+            Some(new_key)
+        );
+        assert!(PendingKeyRotations::<Test>::get(agent).is_none());
+
+        assert_eq!(StakedBy::<Test>::get(new_key, staker), Some(stake));
+        assert_eq!(StakingTo::<Test>::get(staker, new_key), Some(stake));
+        assert_eq!(StakingTo::<Test>::get(new_key, staked), Some(stake));
+        assert_eq!(StakedBy::<Test>::get(staked, new_key), Some(stake));
+        assert!(StakingTo::<Test>::iter_prefix(agent).next().is_none());
+        assert!(StakedBy::<Test>::iter_prefix(agent).next().is_none());
+        assert_eq!(Balances::reserved_balance(agent), 0);
+        assert_eq!(Balances::reserved_balance(new_key), stake);
+
+        let path = "agent.alice".parse::<NamespacePath>().unwrap();
+        assert!(Namespaces::<Test>::contains_key(
+            NamespaceOwnership::Account(new_key),
+            &path
+        ));
+        assert!(!Namespaces::<Test>::contains_key(
+            NamespaceOwnership::Account(agent),
+            &path
+        ));
+
+        assert!(Governance::is_whitelisted(&new_key));
+        assert!(!Governance::is_whitelisted(&agent));
+
+        assert!(
+            WeightControlDelegation::<Test>::get(delegator)
+                .is_some_and(|shares| shares.contains_key(&new_key))
+        );
+        assert!(WeightControlDelegators::<Test>::contains_key(
+            new_key, delegator
+        ));
+        assert!(!WeightControlDelegators::<Test>::contains_key(
+            agent, delegator
+        ));
+
+        let contract = pallet_permission0::Permissions::<Test>::get(permission_id).unwrap();
+        assert_eq!(contract.delegator, new_key);
+        let pallet_permission0::PermissionScope::Stream(scope) = contract.scope else {
+            panic!("expected a stream permission");
+        };
+        let pallet_permission0::StreamAllocation::Streams(streams) = scope.allocation else {
+            panic!("expected a streams allocation");
+        };
+        assert!(streams.contains_key(&generate_root_stream_id(&new_key)));
+        assert!(
+            pallet_permission0::PermissionsByDelegator::<Test>::get(new_key)
+                .contains(&permission_id)
+        );
+        assert!(pallet_permission0::PermissionsByDelegator::<Test>::get(agent).is_empty());
+    });
+}
+
+#[test]
+fn key_rotation_can_be_cancelled_until_the_delay_passes() {
+    test_utils::new_test_ext_with_block(1).execute_with(|| {
+        let agent = 0;
+        let new_key = 1;
+        let other = 2;
+
+        register_empty_agent(agent);
+        register_empty_agent(other);
+
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::rotate_agent_key(get_origin(agent), other),
+            Error::<Test>::InvalidNewAgentKey
+        );
+
+        assert_ok!(pallet_torus0::Pallet::<Test>::rotate_agent_key(
+            get_origin(agent),
+            new_key
+        ));
+        assert_ok!(pallet_torus0::Pallet::<Test>::cancel_agent_key_rotation(
+            get_origin(agent)
+        ));
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::enact_agent_key_rotation(get_origin(new_key), agent, 0),
+            Error::<Test>::KeyRotationNotFound
+        );
+
+        assert_ok!(pallet_torus0::Pallet::<Test>::rotate_agent_key(
+            get_origin(agent),
+            new_key
+        ));
+
+        let delay: u64 = <Test as pallet_torus0::Config>::KeyRotationDelay::get();
+        step_block(delay - 1);
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::enact_agent_key_rotation(get_origin(other), agent, 0),
+            Error::<Test>::KeyRotationDelayNotElapsed
+        );
+
+        step_block(1);
+        assert_ok!(pallet_torus0::Pallet::<Test>::enact_agent_key_rotation(
+            get_origin(other),
+            agent,
+            0
+        ));
+
+        assert!(Agents::<Test>::contains_key(new_key));
+        System::assert_last_event(RuntimeEvent::Torus0(
+            pallet_torus0::Event::AgentKeyRotated {
+                old_key: agent,
+                new_key,
+            },
+        ));
+    });
+}
+
+#[test]
+fn proxy_acts_for_agent_in_allowed_operations() {
+    test_utils::new_test_ext_with_block(1).execute_with(|| {
+        clear_cooldown();
+
+        let agent = 0;
+        let proxy = 1;
+        register_empty_agent(agent);
+
+        let operations =
+            ProxyOperations::try_from(BTreeSet::from([ProxyOperation::UpdateAgent])).unwrap();
+        assert_ok!(pallet_torus0::Pallet::<Test>::add_agent_proxy(
+            get_origin(agent),
+            proxy,
+            operations.clone()
+        ));
+        assert_eq!(ProxyAgents::<Test>::get(proxy), Some(agent));
+        System::assert_last_event(RuntimeEvent::Torus0(
+            pallet_torus0::Event::AgentProxyAdded {
+                agent,
+                proxy,
+                operations,
+            },
+        ));
+
+        assert_eq!(
+            pallet_torus0::Pallet::<Test>::resolve_proxy(&proxy, ProxyOperation::UpdateAgent),
+            agent
+        );
+        assert_eq!(
+            pallet_torus0::Pallet::<Test>::resolve_proxy(&proxy, ProxyOperation::SetWeights),
+            proxy
+        );
+
+        let new_url = b"proxied-url".to_vec();
+        assert_ok!(pallet_torus0::Pallet::<Test>::update_agent(
+            get_origin(proxy),
+            new_url.clone(),
+            None,
+            None,
+            None,
+        ));
+        assert_eq!(Agents::<Test>::get(agent).unwrap().url.to_vec(), new_url);
+
+        assert_ok!(pallet_torus0::Pallet::<Test>::remove_agent_proxy(
+            get_origin(proxy),
+            proxy
+        ));
+        assert!(!AgentProxies::<Test>::contains_key(agent, proxy));
+        assert!(!ProxyAgents::<Test>::contains_key(proxy));
+        assert_eq!(
+            pallet_torus0::Pallet::<Test>::resolve_proxy(&proxy, ProxyOperation::UpdateAgent),
+            proxy
+        );
+    });
+}
+
+#[test]
+fn agent_proxies_are_validated() {
+    test_utils::new_test_ext().execute_with(|| {
+        let agent = 0;
+        let other = 1;
+        let proxy = 2;
+        register_empty_agent(agent);
+        register_empty_agent(other);
+
+        let operations =
+            ProxyOperations::try_from(BTreeSet::from([ProxyOperation::SetWeights])).unwrap();
+
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::add_agent_proxy(
+                get_origin(agent),
+                proxy,
+                Default::default()
+            ),
+            Error::<Test>::NoProxyOperations
+        );
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::add_agent_proxy(
+                get_origin(agent),
+                other,
+                operations.clone()
+            ),
+            Error::<Test>::InvalidAgentProxy
+        );
+
+        assert_ok!(pallet_torus0::Pallet::<Test>::add_agent_proxy(
+            get_origin(agent),
+            proxy,
+            operations.clone()
+        ));
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::add_agent_proxy(
+                get_origin(other),
+                proxy,
+                operations.clone()
+            ),
+            Error::<Test>::InvalidAgentProxy
+        );
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::register_agent(
+                get_origin(proxy),
+                b"proxy".to_vec(),
+                b"proxy".to_vec(),
+                b"proxy".to_vec(),
+            ),
+            Error::<Test>::KeyIsAgentProxy
+        );
+
+        let max_proxies: u32 = <Test as pallet_torus0::Config>::MaxAgentProxies::get();
+        for proxy in 100..(100 + max_proxies - 1) {
+            assert_ok!(pallet_torus0::Pallet::<Test>::add_agent_proxy(
+                get_origin(agent),
+                proxy,
+                operations.clone()
+            ));
+        }
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::add_agent_proxy(
+                get_origin(agent),
+                200,
+                operations.clone()
+            ),
+            Error::<Test>::TooManyAgentProxies
+        );
+
+        assert_err!(
+            pallet_torus0::Pallet::<Test>::remove_agent_proxy(get_origin(other), proxy),
+            Error::<Test>::AgentProxyNotFound
+        );
+    });
+}
@@ -470,6 +470,36 @@ impl_runtime_apis! {
         fn root_stream_id_for_account(account_id: AccountId) -> StreamId {
             generate_root_stream_id(&account_id)
         }
+
+        fn stream_graph(stream_id: Option<StreamId>) -> Vec<pallet_permission0_api::StreamEdge<AccountId>> {
+            pallet_permission0::permission::stream::stream_graph::<Runtime>(stream_id)
+        }
+
+        fn enforcement_referenda(
+            permission_id: pallet_permission0_api::PermissionId,
+        ) -> Vec<pallet_permission0_api::EnforcementReferendumInfo<AccountId>> {
+            pallet_permission0::permission::enforcement_referenda::<Runtime>(permission_id)
+        }
+    }
+
+    impl pallet_emission0_api::api::Emission0RuntimeApi<Block, AccountId, Balance, BlockNumber> for Runtime {
+        fn epoch_reports(count: u32) -> Vec<(u64, pallet_emission0_api::EpochReport<AccountId, Balance, BlockNumber>)> {
+            Emission0::epoch_reports(count)
+        }
+
+        fn simulate_epoch(weight_overrides: Vec<(AccountId, Vec<(AccountId, u16)>)>) -> pallet_emission0_api::EpochSimulation<AccountId, Balance> {
+            pallet_emission0::distribute::simulate_epoch::<Runtime>(weight_overrides)
+        }
+    }
+
+    impl pallet_governance_api::api::GovernanceRuntimeApi<Block, AccountId, BlockNumber, Balance> for Runtime {
+        fn curator_actions(before: Option<u64>, count: u32) -> Vec<(u64, pallet_governance_api::CuratorActionRecord<AccountId, BlockNumber>)> {
+            pallet_governance::audit::curator_actions::<Runtime>(before, count)
+        }
+
+        fn voting_power(account: AccountId) -> Vec<(pallet_governance_api::ProposalCategory, Balance)> {
+            pallet_governance::voting::voting_power::<Runtime>(&account)
+        }
     }
 
     impl pallet_torus0_api::api::Torus0RuntimeApi<Block, AccountId, Balance> for Runtime {
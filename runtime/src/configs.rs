@@ -308,7 +308,7 @@ impl pallet_authority_admin::Config for Runtime {
 
 // --- Torus ---
 
-const fn as_tors(val: u128) -> u128 {
+pub(crate) const fn as_tors(val: u128) -> u128 {
     val.saturating_mul(10u128.pow(TOKEN_DECIMALS))
 }
 
@@ -360,6 +360,9 @@ impl pallet_torus0::Config for Runtime {
 
     type DefaultAgentUpdateCooldown = ConstU64<32_400>; // 3 days
 
+    type KeyRotationDelay = ConstU64<32_400>; // 3 days
+    type MaxAgentProxies = ConstU32<8>;
+
     #[doc = " The storage MaxNameLength should be constrained to be no more than the value of this."]
     #[doc = " This is needed on agent::Agent to set the `name` field BoundedVec max length."]
     type MaxAgentNameLengthConstraint = ConstU32<256>;
@@ -413,6 +416,11 @@ impl pallet_governance::Config for Runtime {
     type DefaultMaxProposalRewardTreasuryAllocation = ConstU128<{ as_tors(10_000) }>;
     type DefaultProposalRewardInterval = ConstU64<75_600>;
 
+    type MaxCuratorActions = ConstU32<1024>;
+
+    type MaxRebalancedDelegations = ConstU32<64>;
+    type MaxVoteDelegators = ConstU32<256>;
+
     type RuntimeEvent = RuntimeEvent;
 
     type Currency = Balances;
@@ -424,6 +432,7 @@ impl pallet_governance::Config for Runtime {
 parameter_types! {
     pub const DefaultEmissionRecyclingPercentage: Percent = Percent::one();
     pub const DefaultIncentivesRatio: Percent = Percent::from_parts(50);
+    pub const DefaultBondsMovingAverage: Percent = Percent::from_parts(10);
 }
 
 #[cfg(not(feature = "testnet"))]
@@ -455,6 +464,15 @@ impl pallet_emission0::Config for Runtime {
 
     type DefaultIncentivesRatio = DefaultIncentivesRatio;
 
+    type DefaultBondsMovingAverage = DefaultBondsMovingAverage;
+
+    type MaxEpochReports = ConstU32<64>;
+    type MaxConsensusAgents = ConstU32<1_024>;
+
+    type MaxWeightDelegationDepth = ConstU32<3>;
+
+    type MaxWeightDelegatees = ConstU32<8>;
+
     type Currency = Balances;
 
     type Torus = Torus0;
@@ -480,6 +498,10 @@ parameter_types! {
     pub const MaxChildrenPerPermission: u32 = 16;
     pub const MaxCuratorSubpermissionsPerPermission: u32 = 16;
     pub const MaxBulkOperationsPerCall: u32 = 20;
+    pub const MaxStreamDelegationDepth: u32 = 5;
+    pub const MaxStreamGraphWalk: u32 = 256;
+    pub const MaxEscrowMilestones: u32 = 16;
+    pub const EnforcementVotingPeriod: BlockNumber = 32_400;
 }
 
 impl pallet_permission0::Config for Runtime {
@@ -504,6 +526,10 @@ impl pallet_permission0::Config for Runtime {
     type MaxChildrenPerPermission = MaxChildrenPerPermission;
     type MaxCuratorSubpermissionsPerPermission = MaxCuratorSubpermissionsPerPermission;
     type MaxBulkOperationsPerCall = MaxBulkOperationsPerCall;
+    type MaxStreamDelegationDepth = MaxStreamDelegationDepth;
+    type MaxStreamGraphWalk = MaxStreamGraphWalk;
+    type MaxEscrowMilestones = MaxEscrowMilestones;
+    type EnforcementVotingPeriod = EnforcementVotingPeriod;
 }
 
 impl pallet_faucet::Config for Runtime {
@@ -38,10 +38,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("torus-runtime"),
     impl_name: create_runtime_str!("torus-runtime"),
     authoring_version: 1,
-    spec_version: 29,
+    spec_version: 30,
     impl_version: 1,
     apis: apis::RUNTIME_API_VERSIONS,
-    transaction_version: 1,
+    transaction_version: 2,
     state_version: 1,
 };
 
@@ -83,7 +83,29 @@ pub type SignedPayload = sp_runtime::generic::SignedPayload<RuntimeCall, SignedE
 /// All migrations of the runtime, aside from the ones declared in the pallets.
 ///
 /// This can be a tuple of types, each implementing `OnRuntimeUpgrade`.
-type Migrations = (migrations::MigrateAllocator,);
+type Migrations = (
+    migrations::MigrateAllocator,
+    pallet_emission0::migrations::v3::Migration<
+        Runtime,
+        frame_support::weights::constants::RocksDbWeight,
+    >,
+    pallet_emission0::migrations::v4::Migration<
+        Runtime,
+        frame_support::weights::constants::RocksDbWeight,
+    >,
+    pallet_permission0::migrations::v8::Migration<
+        Runtime,
+        frame_support::weights::constants::RocksDbWeight,
+    >,
+    pallet_permission0::migrations::v9::Migration<
+        Runtime,
+        frame_support::weights::constants::RocksDbWeight,
+    >,
+    pallet_permission0::migrations::v10::Migration<
+        Runtime,
+        frame_support::weights::constants::RocksDbWeight,
+    >,
+);
 
 /// Executive: handles dispatch to the various modules.
 pub type RuntimeExecutive = frame_executive::Executive<
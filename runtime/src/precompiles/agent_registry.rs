@@ -0,0 +1,114 @@
+use alloc::vec::Vec;
+
+use pallet_evm::{
+    ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+
+use crate::{
+    Runtime,
+    precompiles::{
+        AbiValue, encode_abi, encode_bool, find_agent_by_name, get_method, get_method_id,
+        parse_account_id_arg, parse_bytes_arg, record_reads, revert,
+    },
+};
+
+pub const AGENT_REGISTRY_INDEX: u64 = 2050;
+
+/// Read-only access to the torus0 agent registry. See
+/// `solidity/agentRegistry.sol`.
+pub struct AgentRegistryPrecompile;
+
+impl AgentRegistryPrecompile {
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        let method = get_method(&input)?;
+
+        let output = match method {
+            m if m == get_method_id("isAgent(bytes32)") => Self::is_agent(handle, &input)?,
+            m if m == get_method_id("getAgent(bytes32)") => Self::get_agent(handle, &input)?,
+            m if m == get_method_id("getAgentByName(string)") => {
+                Self::get_agent_by_name(handle, &input)?
+            }
+            m if m == get_method_id("findAgentByName(string)") => {
+                Self::find_agent_by_name(handle, &input)?
+            }
+            _ => return Err(revert("unknown selector")),
+        };
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+
+    fn is_agent(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let key = parse_account_id_arg(input, 0)?;
+
+        record_reads(handle, 1)?;
+        let exists = pallet_torus0::Agents::<Runtime>::contains_key(key);
+
+        Ok(encode_bool(exists))
+    }
+
+    /// Returns `(exists, name, url, metadata, registrationBlock)`.
+    fn get_agent(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let key = parse_account_id_arg(input, 0)?;
+
+        record_reads(handle, 1)?;
+        let agent = pallet_torus0::Agents::<Runtime>::get(key);
+
+        Ok(Self::encode_agent(agent.as_ref()))
+    }
+
+    /// Same as `getAgent`, looking the agent up by its name.
+    fn get_agent_by_name(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let name = parse_bytes_arg(input, 0)?;
+        let agent = find_agent_by_name(handle, &name)?.map(|(_, agent)| agent);
+
+        Ok(Self::encode_agent(agent.as_ref()))
+    }
+
+    /// Returns `(found, key)`.
+    fn find_agent_by_name(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let name = parse_bytes_arg(input, 0)?;
+
+        let output = match find_agent_by_name(handle, &name)? {
+            Some((key, _)) => encode_abi(&[AbiValue::bool(true), AbiValue::bytes32(key)]),
+            None => encode_abi(&[AbiValue::bool(false), AbiValue::Word([0; 32])]),
+        };
+
+        Ok(output)
+    }
+
+    fn encode_agent(agent: Option<&pallet_torus0::agent::Agent<Runtime>>) -> Vec<u8> {
+        let Some(agent) = agent else {
+            return encode_abi(&[
+                AbiValue::bool(false),
+                AbiValue::Bytes(Vec::new()),
+                AbiValue::Bytes(Vec::new()),
+                AbiValue::Bytes(Vec::new()),
+                AbiValue::uint(0),
+            ]);
+        };
+
+        encode_abi(&[
+            AbiValue::bool(true),
+            AbiValue::Bytes(agent.name.to_vec()),
+            AbiValue::Bytes(agent.url.to_vec()),
+            AbiValue::Bytes(agent.metadata.to_vec()),
+            AbiValue::uint(agent.registration_block.into()),
+        ])
+    }
+}
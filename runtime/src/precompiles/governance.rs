@@ -0,0 +1,137 @@
+use alloc::vec::Vec;
+
+use pallet_evm::{
+    ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+use pallet_governance::proposal::ProposalStatus;
+
+use crate::{
+    Runtime, RuntimeCall,
+    precompiles::{
+        AbiValue, caller_account_id, dispatch_as, encode_abi, ensure_not_payable,
+        ensure_not_static, get_method, get_method_id, parse_bool_arg, parse_u64_arg, record_reads,
+        revert,
+    },
+};
+
+pub const GOVERNANCE_INDEX: u64 = 2052;
+
+/// Proposal status codes returned by `getProposal`.
+const STATUS_OPEN: u128 = 0;
+const STATUS_ACCEPTED: u128 = 1;
+const STATUS_REFUSED: u128 = 2;
+const STATUS_EXPIRED: u128 = 3;
+
+/// Exposes governance proposals to EVM contracts. Votes are cast as the
+/// substrate account mapped to the caller. See `solidity/governance.sol`.
+pub struct GovernancePrecompile;
+
+impl GovernancePrecompile {
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        let method = get_method(&input)?;
+
+        let output = match method {
+            m if m == get_method_id("getProposal(uint256)") => Self::get_proposal(handle, &input)?,
+            m if m == get_method_id("voteProposal(uint256,bool)") => {
+                Self::vote_proposal(handle, &input)?
+            }
+            m if m == get_method_id("removeVoteProposal(uint256)") => {
+                Self::remove_vote_proposal(handle, &input)?
+            }
+            _ => return Err(revert("unknown selector")),
+        };
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+
+    /// Returns `(exists, proposer, status, expirationBlock, stakeFor,
+    /// stakeAgainst)`. Stakes are the estimated tally for open proposals and the
+    /// final one for closed proposals.
+    fn get_proposal(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let proposal_id = parse_u64_arg(input, 0)?;
+
+        record_reads(handle, 1)?;
+        let Some(proposal) = pallet_governance::Proposals::<Runtime>::get(proposal_id) else {
+            return Ok(encode_abi(&[
+                AbiValue::bool(false),
+                AbiValue::Word([0; 32]),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+            ]));
+        };
+
+        let (status, stake_for, stake_against) = match &proposal.status {
+            ProposalStatus::Open {
+                stake_for,
+                stake_against,
+                ..
+            } => (STATUS_OPEN, *stake_for, *stake_against),
+            ProposalStatus::Accepted {
+                stake_for,
+                stake_against,
+                ..
+            } => (STATUS_ACCEPTED, *stake_for, *stake_against),
+            ProposalStatus::Refused {
+                stake_for,
+                stake_against,
+                ..
+            } => (STATUS_REFUSED, *stake_for, *stake_against),
+            ProposalStatus::Expired => (STATUS_EXPIRED, 0, 0),
+        };
+
+        Ok(encode_abi(&[
+            AbiValue::bool(true),
+            AbiValue::bytes32(proposal.proposer),
+            AbiValue::uint(status),
+            AbiValue::uint(proposal.expiration_block.into()),
+            AbiValue::uint(stake_for),
+            AbiValue::uint(stake_against),
+        ]))
+    }
+
+    fn vote_proposal(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let proposal_id = parse_u64_arg(input, 0)?;
+        let agree = parse_bool_arg(input, 1)?;
+
+        let call = RuntimeCall::Governance(pallet_governance::Call::<Runtime>::vote_proposal {
+            proposal_id,
+            agree,
+        });
+        dispatch_as(handle, caller_account_id(handle), call)?;
+
+        Ok(Vec::new())
+    }
+
+    fn remove_vote_proposal(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let proposal_id = parse_u64_arg(input, 0)?;
+
+        let call =
+            RuntimeCall::Governance(pallet_governance::Call::<Runtime>::remove_vote_proposal {
+                proposal_id,
+            });
+        dispatch_as(handle, caller_account_id(handle), call)?;
+
+        Ok(Vec::new())
+    }
+}
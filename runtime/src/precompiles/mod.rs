@@ -1,22 +1,42 @@
+use alloc::vec::Vec;
+
 use pallet_evm::{
-    ExitError, IsPrecompileResult, Precompile, PrecompileFailure, PrecompileHandle,
-    PrecompileResult, PrecompileSet,
+    AddressMapping, ExitError, ExitRevert, GasWeightMapping, IsPrecompileResult, Precompile,
+    PrecompileFailure, PrecompileHandle, PrecompileResult, PrecompileSet,
 };
 use pallet_evm_precompile_modexp::Modexp;
 use pallet_evm_precompile_sha3fips::Sha3FIPS256;
 use pallet_evm_precompile_simple::{ECRecover, ECRecoverPublicKey, Identity, Ripemd160, Sha256};
 use polkadot_sdk::{
-    sp_core::{H160, hashing::keccak_256},
-    sp_runtime::AccountId32,
+    frame_support::{dispatch::GetDispatchInfo, storage::with_storage_layer, weights::Weight},
+    frame_system::{self, RawOrigin},
+    sp_core::{Get, H160, H256, hashing::keccak_256},
+    sp_runtime::{AccountId32, DispatchError, traits::Dispatchable},
 };
-mod balance_transfer;
 
-use balance_transfer::*;
+use crate::{Runtime, RuntimeCall};
+
+mod agent_registry;
+mod governance;
+mod namespace;
+mod native_token;
+mod permission;
+mod staking;
+
+#[cfg(test)]
+mod tests;
+
+use agent_registry::*;
+use governance::*;
+use namespace::*;
+use native_token::*;
+use permission::*;
+use staking::*;
 
 pub struct FrontierPrecompiles;
 
 impl FrontierPrecompiles {
-    pub fn used_addresses() -> [H160; 8] {
+    pub fn used_addresses() -> [H160; 13] {
         [
             hash(1),
             hash(2),
@@ -25,7 +45,12 @@ impl FrontierPrecompiles {
             hash(5),
             hash(1024),
             hash(1025),
-            hash(BALANCE_TRANSFER_INDEX),
+            hash(NATIVE_TOKEN_INDEX),
+            hash(STAKING_INDEX),
+            hash(AGENT_REGISTRY_INDEX),
+            hash(NAMESPACE_INDEX),
+            hash(GOVERNANCE_INDEX),
+            hash(PERMISSION_INDEX),
         ]
     }
 }
@@ -41,8 +66,24 @@ impl PrecompileSet for FrontierPrecompiles {
             // Non-Frontier specific nor Ethereum precompiles :
             a if a == hash(1024) => Some(Sha3FIPS256::execute(handle)),
             a if a == hash(1025) => Some(ECRecoverPublicKey::execute(handle)),
-            a if a == hash(BALANCE_TRANSFER_INDEX) => {
-                Some(BalanceTransferPrecompile::execute(handle))
+            // Torus precompiles :
+            a if a == hash(NATIVE_TOKEN_INDEX) => {
+                Some(execute_direct(handle, NativeTokenPrecompile::execute))
+            }
+            a if a == hash(STAKING_INDEX) => {
+                Some(execute_direct(handle, StakingPrecompile::execute))
+            }
+            a if a == hash(AGENT_REGISTRY_INDEX) => {
+                Some(execute_direct(handle, AgentRegistryPrecompile::execute))
+            }
+            a if a == hash(NAMESPACE_INDEX) => {
+                Some(execute_direct(handle, NamespacePrecompile::execute))
+            }
+            a if a == hash(GOVERNANCE_INDEX) => {
+                Some(execute_direct(handle, GovernancePrecompile::execute))
+            }
+            a if a == hash(PERMISSION_INDEX) => {
+                Some(execute_direct(handle, PermissionPrecompile::execute))
             }
             _ => None,
         }
@@ -60,6 +101,20 @@ fn hash(a: u64) -> H160 {
     H160::from_low_u64_be(a)
 }
 
+/// Runs a Torus precompile, rejecting delegate calls and call codes. Those
+/// run the precompile in the context of the calling contract, so it would
+/// act as that contract's caller, which never authorized the call.
+fn execute_direct<H: PrecompileHandle>(
+    handle: &mut H,
+    execute: impl FnOnce(&mut H) -> PrecompileResult,
+) -> PrecompileResult {
+    if handle.context().address != handle.code_address() {
+        return Err(revert("cannot be called through delegatecall or callcode"));
+    }
+
+    execute(handle)
+}
+
 /// Returns Ethereum method ID from an str method signature
 pub fn get_method_id(method_signature: &str) -> [u8; 4] {
     // Calculate the full Keccak-256 hash of the method signature
@@ -91,3 +146,385 @@ pub fn get_slice(data: &[u8], from: usize, to: usize) -> Result<&[u8], Precompil
         })
     }
 }
+
+/// Reads the method selector of the call.
+pub fn get_method(input: &[u8]) -> Result<[u8; 4], PrecompileFailure> {
+    let method = get_slice(input, 0, 4)?;
+    method.try_into().map_err(|_| PrecompileFailure::Error {
+        exit_status: ExitError::InvalidRange,
+    })
+}
+
+/// Takes the ABI word of the argument at `index`, right after the method
+/// selector.
+pub fn get_arg(input: &[u8], index: usize) -> Result<&[u8], PrecompileFailure> {
+    let from = index.saturating_mul(32).saturating_add(4);
+    get_slice(input, from, from.saturating_add(32))
+}
+
+/// Parses a `bytes32` argument as an AccountId32.
+pub fn parse_account_id_arg(input: &[u8], index: usize) -> Result<AccountId32, PrecompileFailure> {
+    bytes_to_account_id(get_arg(input, index)?)
+}
+
+/// Parses a `uint256` argument, reverting if it does not fit a u128.
+pub fn parse_u128_arg(input: &[u8], index: usize) -> Result<u128, PrecompileFailure> {
+    let word = get_arg(input, index)?;
+
+    let (Some(high), Some(low)) = (word.get(..16), word.get(16..)) else {
+        return Err(revert("invalid argument"));
+    };
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(revert("value does not fit 128 bits"));
+    }
+
+    let low: [u8; 16] = low.try_into().map_err(|_| revert("invalid argument"))?;
+    Ok(u128::from_be_bytes(low))
+}
+
+/// Parses a `uint256` argument, saturating at `u128::MAX`.
+pub fn parse_saturating_u128_arg(input: &[u8], index: usize) -> Result<u128, PrecompileFailure> {
+    let word = get_arg(input, index)?;
+
+    let (Some(high), Some(low)) = (word.get(..16), word.get(16..)) else {
+        return Err(revert("invalid argument"));
+    };
+    if high.iter().any(|byte| *byte != 0) {
+        return Ok(u128::MAX);
+    }
+
+    let low: [u8; 16] = low.try_into().map_err(|_| revert("invalid argument"))?;
+    Ok(u128::from_be_bytes(low))
+}
+
+/// Parses an `address` argument, reverting if the upper 12 bytes are set.
+pub fn parse_address_arg(input: &[u8], index: usize) -> Result<H160, PrecompileFailure> {
+    let word = get_arg(input, index)?;
+
+    let (Some(high), Some(low)) = (word.get(..12), word.get(12..)) else {
+        return Err(revert("invalid argument"));
+    };
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(revert("invalid address"));
+    }
+
+    Ok(H160::from_slice(low))
+}
+
+/// Encodes an address as an ABI word.
+pub fn address_word(address: H160) -> [u8; 32] {
+    let mut word = [0; 32];
+    if let Some(low) = word.get_mut(12..) {
+        low.copy_from_slice(address.as_bytes());
+    }
+    word
+}
+
+/// Parses a `uint256` argument, reverting if it does not fit a u64.
+pub fn parse_u64_arg(input: &[u8], index: usize) -> Result<u64, PrecompileFailure> {
+    parse_u128_arg(input, index)?
+        .try_into()
+        .map_err(|_| revert("value does not fit 64 bits"))
+}
+
+/// Parses a `bool` argument, reverting on anything other than 0 or 1.
+pub fn parse_bool_arg(input: &[u8], index: usize) -> Result<bool, PrecompileFailure> {
+    match parse_u128_arg(input, index)? {
+        0 => Ok(false),
+        1 => Ok(true),
+        _ => Err(revert("invalid bool")),
+    }
+}
+
+/// Parses a dynamic `bytes` or `string` argument. The head word at `index`
+/// holds the offset of the length-prefixed data, relative to the start of
+/// the arguments.
+pub fn parse_bytes_arg(input: &[u8], index: usize) -> Result<Vec<u8>, PrecompileFailure> {
+    let (data, len) = dynamic_arg(input, index)?;
+    Ok(get_slice(data, 0, len)?.to_vec())
+}
+
+/// Parses a dynamic array of one-word elements, like `bytes32[]` or
+/// `uint256[]`.
+pub fn parse_words_arg(input: &[u8], index: usize) -> Result<Vec<[u8; 32]>, PrecompileFailure> {
+    let (data, len) = dynamic_arg(input, index)?;
+    let data = get_slice(data, 0, len.saturating_mul(32))?;
+
+    Ok(data
+        .chunks_exact(32)
+        .filter_map(|word| word.try_into().ok())
+        .collect())
+}
+
+/// Resolves the dynamic argument at `index`, returning the data following
+/// its length prefix and the length itself.
+fn dynamic_arg(input: &[u8], index: usize) -> Result<(&[u8], usize), PrecompileFailure> {
+    let args = get_slice(input, 4, input.len())?;
+
+    let offset = parse_u128_arg(input, index)?;
+    let offset = usize::try_from(offset).map_err(|_| revert("invalid offset"))?;
+
+    let len_word = get_slice(args, offset, offset.saturating_add(32))?;
+    let (Some(high), Some(low)) = (len_word.get(..24), len_word.get(24..)) else {
+        return Err(revert("invalid length"));
+    };
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(revert("invalid length"));
+    }
+
+    let low: [u8; 8] = low.try_into().map_err(|_| revert("invalid length"))?;
+    let len = usize::try_from(u64::from_be_bytes(low)).map_err(|_| revert("invalid length"))?;
+
+    let data = get_slice(args, offset.saturating_add(32), args.len())?;
+    Ok((data, len))
+}
+
+/// Reads the last 16 bytes of a word as a u128, reverting if the value does
+/// not fit.
+pub fn word_to_u128(word: &[u8; 32]) -> Result<u128, PrecompileFailure> {
+    let (high, low) = word.split_at(16);
+    if high.iter().any(|byte| *byte != 0) {
+        return Err(revert("value does not fit 128 bits"));
+    }
+
+    let low: [u8; 16] = low.try_into().map_err(|_| revert("invalid argument"))?;
+    Ok(u128::from_be_bytes(low))
+}
+
+/// Encodes a value as an ABI `uint256` word.
+pub fn encode_u128(value: u128) -> Vec<u8> {
+    [0; 16].into_iter().chain(value.to_be_bytes()).collect()
+}
+
+/// Encodes a value as a single ABI `uint256` word.
+pub fn u128_word(value: u128) -> [u8; 32] {
+    let mut word = [0; 32];
+    if let Some(low) = word.get_mut(16..) {
+        low.copy_from_slice(&value.to_be_bytes());
+    }
+    word
+}
+
+/// Encodes a value as an ABI `bool` word.
+pub fn encode_bool(value: bool) -> Vec<u8> {
+    encode_u128(value.into())
+}
+
+/// A single value returned by a precompile, see [`encode_abi`].
+pub enum AbiValue {
+    /// A static value fitting one word: `uint`, `bool`, `bytes32`...
+    Word([u8; 32]),
+    /// A dynamic `bytes` or `string` value.
+    Bytes(Vec<u8>),
+    /// A dynamic array of static values, like `bytes32[]`.
+    Array(Vec<[u8; 32]>),
+}
+
+impl AbiValue {
+    pub fn uint(value: u128) -> Self {
+        Self::Word(u128_word(value))
+    }
+
+    pub fn bool(value: bool) -> Self {
+        Self::uint(value.into())
+    }
+
+    pub fn bytes32(value: impl AsRef<[u8; 32]>) -> Self {
+        Self::Word(*value.as_ref())
+    }
+}
+
+/// Encodes the values as an ABI tuple: static values are inlined in the
+/// head, dynamic ones are appended to the tail and referenced by offset.
+pub fn encode_abi(values: &[AbiValue]) -> Vec<u8> {
+    let head_len = values.len().saturating_mul(32);
+
+    let mut head = Vec::with_capacity(head_len);
+    let mut tail = Vec::new();
+
+    for value in values {
+        match value {
+            AbiValue::Word(word) => head.extend_from_slice(word),
+            AbiValue::Bytes(bytes) => {
+                let offset = head_len.saturating_add(tail.len());
+                head.extend(encode_u128(offset as u128));
+
+                let padding = bytes.len().next_multiple_of(32).saturating_sub(bytes.len());
+                tail.extend(encode_u128(bytes.len() as u128));
+                tail.extend_from_slice(bytes);
+                tail.extend(core::iter::repeat_n(0, padding));
+            }
+            AbiValue::Array(words) => {
+                let offset = head_len.saturating_add(tail.len());
+                head.extend(encode_u128(offset as u128));
+
+                tail.extend(encode_u128(words.len() as u128));
+                tail.extend(words.iter().flatten());
+            }
+        }
+    }
+
+    head.extend(tail);
+    head
+}
+
+/// Reverts with a Solidity `Error(string)` carrying the given message.
+pub fn revert(message: &str) -> PrecompileFailure {
+    let message = message.as_bytes();
+    let padding = message
+        .len()
+        .next_multiple_of(32)
+        .saturating_sub(message.len());
+
+    let output = get_method_id("Error(string)")
+        .into_iter()
+        .chain(encode_u128(32))
+        .chain(encode_u128(message.len() as u128))
+        .chain(message.iter().copied())
+        .chain(core::iter::repeat_n(0, padding))
+        .collect();
+
+    PrecompileFailure::Revert {
+        exit_status: ExitRevert::Reverted,
+        output,
+    }
+}
+
+/// Reverts if the call carries value, as it would be locked in the precompile.
+pub fn ensure_not_payable(handle: &impl PrecompileHandle) -> Result<(), PrecompileFailure> {
+    if handle.context().apparent_value.is_zero() {
+        Ok(())
+    } else {
+        Err(revert("function is not payable"))
+    }
+}
+
+/// Reverts in a static call, for methods changing state.
+pub fn ensure_not_static(handle: &impl PrecompileHandle) -> Result<(), PrecompileFailure> {
+    if handle.is_static() {
+        Err(revert("cannot modify state in a static call"))
+    } else {
+        Ok(())
+    }
+}
+
+/// The substrate account mapped to the caller of the precompile.
+pub fn caller_account_id(handle: &impl PrecompileHandle) -> AccountId32 {
+    address_to_account_id(handle.context().caller)
+}
+
+/// The substrate account mapped to an EVM address.
+pub fn address_to_account_id(address: H160) -> AccountId32 {
+    <Runtime as pallet_evm::Config>::AddressMapping::into_account_id(address)
+}
+
+/// Maximum number of agents visited when looking one up by name, as the
+/// registry is not indexed by name.
+pub const MAX_AGENTS_SCANNED: usize = 1024;
+
+/// Looks up the agent registered with the given name, charging one storage
+/// read for each visited agent. Reverts if the name is not found within the
+/// first [`MAX_AGENTS_SCANNED`] agents.
+pub fn find_agent_by_name(
+    handle: &mut impl PrecompileHandle,
+    name: &[u8],
+) -> Result<Option<(AccountId32, pallet_torus0::agent::Agent<Runtime>)>, PrecompileFailure> {
+    record_reads(handle, 1)?;
+
+    let mut agents = pallet_torus0::Agents::<Runtime>::iter();
+    for _ in 0..MAX_AGENTS_SCANNED {
+        let Some((key, agent)) = agents.next() else {
+            return Ok(None);
+        };
+
+        record_reads(handle, 1)?;
+        if *agent.name == name {
+            return Ok(Some((key, agent)));
+        }
+    }
+
+    match agents.next() {
+        Some(_) => Err(revert("too many agents to scan")),
+        None => Ok(None),
+    }
+}
+
+/// Charges the gas equivalent of `reads` storage reads.
+pub fn record_reads(
+    handle: &mut impl PrecompileHandle,
+    reads: u64,
+) -> Result<(), PrecompileFailure> {
+    let weight = <Runtime as frame_system::Config>::DbWeight::get().reads(reads);
+    handle.record_cost(<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight))?;
+    Ok(())
+}
+
+/// Charges the gas equivalent of `writes` storage writes.
+pub fn record_writes(
+    handle: &mut impl PrecompileHandle,
+    writes: u64,
+) -> Result<(), PrecompileFailure> {
+    let weight = <Runtime as frame_system::Config>::DbWeight::get().writes(writes);
+    handle.record_cost(<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight))?;
+    Ok(())
+}
+
+/// Dispatches the call signed by `origin`, charging its weight as gas.
+/// Dispatch errors revert with the error name.
+pub fn dispatch_as(
+    handle: &mut impl PrecompileHandle,
+    origin: AccountId32,
+    call: RuntimeCall,
+) -> Result<(), PrecompileFailure> {
+    let weight = call.get_dispatch_info().weight;
+    handle.record_cost(<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight))?;
+
+    call.dispatch(RawOrigin::Signed(origin).into())
+        .map_err(|err| {
+            let message: &'static str = err.error.into();
+            revert(message)
+        })?;
+
+    Ok(())
+}
+
+/// Runs `f` in its own storage layer, charging `weight` as gas beforehand.
+/// Used for pallet interfaces that return values, which dispatching calls
+/// would discard. Errors revert with their name.
+pub fn call_with_weight<R>(
+    handle: &mut impl PrecompileHandle,
+    weight: Weight,
+    f: impl FnOnce() -> Result<R, DispatchError>,
+) -> Result<R, PrecompileFailure> {
+    handle.record_cost(<Runtime as pallet_evm::Config>::GasWeightMapping::weight_to_gas(weight))?;
+
+    with_storage_layer(f).map_err(|err| {
+        let message: &'static str = err.into();
+        revert(message)
+    })
+}
+
+/// Emits an EVM log from the precompile, charging the standard log gas.
+pub fn emit_log(
+    handle: &mut impl PrecompileHandle,
+    topics: Vec<H256>,
+    data: Vec<u8>,
+) -> Result<(), PrecompileFailure> {
+    const LOG_GAS: u64 = 375;
+    const LOG_TOPIC_GAS: u64 = 375;
+    const LOG_DATA_GAS: u64 = 8;
+
+    let cost = LOG_GAS
+        .saturating_add(LOG_TOPIC_GAS.saturating_mul(topics.len() as u64))
+        .saturating_add(LOG_DATA_GAS.saturating_mul(data.len() as u64));
+    handle.record_cost(cost)?;
+
+    let address = handle.code_address();
+    handle.log(address, topics, data)?;
+    Ok(())
+}
+
+/// Topic of an event, the keccak hash of its signature.
+pub fn event_topic(signature: &str) -> H256 {
+    H256(keccak_256(signature.as_bytes()))
+}
@@ -0,0 +1,122 @@
+use alloc::vec::Vec;
+
+use pallet_evm::{
+    ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+use pallet_torus0::namespace::{self, NamespaceOwnership};
+use pallet_torus0_api::NamespacePath;
+
+use crate::{
+    Runtime,
+    precompiles::{
+        AbiValue, encode_abi, encode_bool, find_agent_by_name, get_method, get_method_id,
+        parse_account_id_arg, parse_bytes_arg, record_reads, revert,
+    },
+};
+
+pub const NAMESPACE_INDEX: u64 = 2051;
+
+/// Read-only access to torus0 namespaces. Paths are passed as strings in
+/// their dotted form, e.g. `agent.alice.memory`. See
+/// `solidity/namespace.sol`.
+pub struct NamespacePrecompile;
+
+impl NamespacePrecompile {
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        let method = get_method(&input)?;
+
+        let output = match method {
+            m if m == get_method_id("namespaceExists(bytes32,string)") => {
+                Self::namespace_exists(handle, &input)?
+            }
+            m if m == get_method_id("namespaceOwner(string)") => {
+                Self::namespace_owner(handle, &input)?
+            }
+            m if m == get_method_id("namespaceCreationCost(bytes32,string)") => {
+                Self::namespace_creation_cost(handle, &input)?
+            }
+            _ => return Err(revert("unknown selector")),
+        };
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+
+    fn namespace_exists(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let owner = parse_account_id_arg(input, 0)?;
+        let path = Self::parse_path_arg(input, 1)?;
+
+        record_reads(handle, 1)?;
+        let exists = pallet_torus0::Namespaces::<Runtime>::contains_key(
+            NamespaceOwnership::<Runtime>::Account(owner),
+            path,
+        );
+
+        Ok(encode_bool(exists))
+    }
+
+    /// Returns `(found, owner)`. Agent namespaces are owned by the agent
+    /// named in their second segment.
+    fn namespace_owner(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let path = Self::parse_path_arg(input, 0)?;
+
+        let not_found = encode_abi(&[AbiValue::bool(false), AbiValue::Word([0; 32])]);
+        let Some(agent_name) = path.agent_name() else {
+            return Ok(not_found);
+        };
+
+        let Some((owner, _)) = find_agent_by_name(handle, agent_name)? else {
+            return Ok(not_found);
+        };
+
+        record_reads(handle, 1)?;
+        let ownership = NamespaceOwnership::<Runtime>::Account(owner.clone());
+        if !pallet_torus0::Namespaces::<Runtime>::contains_key(ownership, &path) {
+            return Ok(not_found);
+        }
+
+        Ok(encode_abi(&[
+            AbiValue::bool(true),
+            AbiValue::bytes32(owner),
+        ]))
+    }
+
+    /// Returns `(fee, deposit)` the account would pay to create the path,
+    /// including its missing parents, under the current
+    /// `NamespacePricingConfig`.
+    fn namespace_creation_cost(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let account = parse_account_id_arg(input, 0)?;
+        let path = Self::parse_path_arg(input, 1)?;
+
+        // One read for each level of the path, the pricing config and the
+        // owner's namespace count.
+        record_reads(handle, u64::from(path.depth()).saturating_add(2))?;
+
+        let owner = NamespaceOwnership::Account(account);
+        let missing_paths = namespace::find_missing_paths::<Runtime>(&owner, &path);
+        let (fee, deposit) =
+            namespace::calculate_cost::<Runtime>(&owner, &missing_paths).map_err(|err| {
+                let message: &'static str = err.into();
+                revert(message)
+            })?;
+
+        Ok(encode_abi(&[AbiValue::uint(fee), AbiValue::uint(deposit)]))
+    }
+
+    fn parse_path_arg(input: &[u8], index: usize) -> Result<NamespacePath, PrecompileFailure> {
+        let path = parse_bytes_arg(input, index)?;
+        NamespacePath::new_agent(&path).map_err(revert)
+    }
+}
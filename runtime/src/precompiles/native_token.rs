@@ -0,0 +1,232 @@
+use alloc::vec::Vec;
+
+use pallet_evm::{
+    ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+use polkadot_sdk::{
+    pallet_balances,
+    sp_core::{H160, H256},
+    sp_runtime::traits::UniqueSaturatedInto,
+};
+
+use crate::{
+    Runtime, RuntimeCall,
+    precompiles::{
+        AbiValue, address_to_account_id, address_word, caller_account_id, dispatch_as, emit_log,
+        encode_abi, encode_bool, encode_u128, ensure_not_payable, ensure_not_static, event_topic,
+        get_arg, get_method, get_method_id, parse_account_id_arg, parse_address_arg,
+        parse_saturating_u128_arg, parse_u128_arg, record_reads, record_writes, revert,
+    },
+};
+
+pub const NATIVE_TOKEN_INDEX: u64 = 2048;
+
+/// Token metadata, matching the chain properties.
+const NAME: &[u8] = b"Torus";
+const SYMBOL: &[u8] = b"TORUS";
+const DECIMALS: u128 = 18;
+
+/// Amounts `spender` may transfer on behalf of `owner`, keyed by their
+/// mapped substrate accounts.
+type Allowances = pallet_torus0::NativeTokenAllowances<Runtime>;
+
+/// ERC-20-like interface to the native TORUS token. EVM addresses are
+/// resolved to their mapped substrate accounts. See
+/// `solidity/nativeToken.sol`.
+pub struct NativeTokenPrecompile;
+
+impl NativeTokenPrecompile {
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        let method = get_method(&input)?;
+
+        let output = match method {
+            m if m == get_method_id("name()") => encode_abi(&[AbiValue::Bytes(NAME.to_vec())]),
+            m if m == get_method_id("symbol()") => encode_abi(&[AbiValue::Bytes(SYMBOL.to_vec())]),
+            m if m == get_method_id("decimals()") => encode_u128(DECIMALS),
+            m if m == get_method_id("totalSupply()") => Self::total_supply(handle)?,
+            m if m == get_method_id("balanceOf(address)") => Self::balance_of(handle, &input)?,
+            m if m == get_method_id("allowance(address,address)") => {
+                Self::allowance(handle, &input)?
+            }
+            m if m == get_method_id("transfer(address,uint256)") => Self::transfer(handle, &input)?,
+            m if m == get_method_id("approve(address,uint256)") => Self::approve(handle, &input)?,
+            m if m == get_method_id("transferFrom(address,address,uint256)") => {
+                Self::transfer_from(handle, &input)?
+            }
+            m if m == get_method_id("transfer(bytes32)") => {
+                Self::transfer_to_account(handle, &input)?
+            }
+            _ => return Err(revert("unknown selector")),
+        };
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+
+    fn total_supply(handle: &mut impl PrecompileHandle) -> Result<Vec<u8>, PrecompileFailure> {
+        record_reads(handle, 1)?;
+        Ok(encode_u128(pallet_balances::TotalIssuance::<Runtime>::get()))
+    }
+
+    fn balance_of(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let owner = address_to_account_id(parse_address_arg(input, 0)?);
+
+        record_reads(handle, 1)?;
+        Ok(encode_u128(
+            pallet_balances::Pallet::<Runtime>::free_balance(owner),
+        ))
+    }
+
+    fn allowance(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let owner = address_to_account_id(parse_address_arg(input, 0)?);
+        let spender = address_to_account_id(parse_address_arg(input, 1)?);
+
+        record_reads(handle, 1)?;
+        let allowance = Allowances::get(owner, spender);
+
+        // Unlimited allowances are reported as the maximum uint256.
+        if allowance == u128::MAX {
+            return Ok(Vec::from([u8::MAX; 32]));
+        }
+
+        Ok(encode_u128(allowance))
+    }
+
+    fn transfer(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let from = handle.context().caller;
+        let to = parse_address_arg(input, 0)?;
+        let value = parse_u128_arg(input, 1)?;
+
+        Self::do_transfer(handle, from, to, value)?;
+
+        Ok(encode_bool(true))
+    }
+
+    /// Sets the allowance of the spender over the caller's tokens. Values
+    /// above `u128::MAX` are treated as unlimited.
+    fn approve(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let owner = handle.context().caller;
+        let spender = parse_address_arg(input, 0)?;
+        let value = parse_saturating_u128_arg(input, 1)?;
+
+        record_writes(handle, 1)?;
+        Allowances::insert(
+            address_to_account_id(owner),
+            address_to_account_id(spender),
+            value,
+        );
+
+        emit_log(
+            handle,
+            alloc::vec![
+                event_topic("Approval(address,address,uint256)"),
+                H256(address_word(owner)),
+                H256(address_word(spender)),
+            ],
+            get_arg(input, 1)?.to_vec(),
+        )?;
+
+        Ok(encode_bool(true))
+    }
+
+    /// Transfers on behalf of `from`, spending the caller's allowance.
+    fn transfer_from(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let spender = caller_account_id(handle);
+        let from = parse_address_arg(input, 0)?;
+        let to = parse_address_arg(input, 1)?;
+        let value = parse_u128_arg(input, 2)?;
+
+        let owner = address_to_account_id(from);
+
+        record_reads(handle, 1)?;
+        let allowance = Allowances::get(&owner, &spender);
+        let Some(remaining) = allowance.checked_sub(value) else {
+            return Err(revert("insufficient allowance"));
+        };
+
+        Self::do_transfer(handle, from, to, value)?;
+
+        if allowance != u128::MAX {
+            record_writes(handle, 1)?;
+            Allowances::insert(owner, spender, remaining);
+        }
+
+        Ok(encode_bool(true))
+    }
+
+    /// Transfers the value sent with the call to a substrate account.
+    /// Does nothing if no value was sent.
+    fn transfer_to_account(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+
+        let amount = handle.context().apparent_value;
+        if amount.is_zero() {
+            return Ok(Vec::new());
+        }
+
+        let destination = parse_account_id_arg(input, 0)?;
+
+        // The value was moved to the account mapped to the precompile.
+        let source = address_to_account_id(handle.code_address());
+        let call = RuntimeCall::Balances(pallet_balances::Call::<Runtime>::transfer_allow_death {
+            dest: destination.into(),
+            value: amount.unique_saturated_into(),
+        });
+        dispatch_as(handle, source, call)?;
+
+        Ok(Vec::new())
+    }
+
+    fn do_transfer(
+        handle: &mut impl PrecompileHandle,
+        from: H160,
+        to: H160,
+        value: u128,
+    ) -> Result<(), PrecompileFailure> {
+        let call = RuntimeCall::Balances(pallet_balances::Call::<Runtime>::transfer_allow_death {
+            dest: address_to_account_id(to).into(),
+            value,
+        });
+        dispatch_as(handle, address_to_account_id(from), call)?;
+
+        emit_log(
+            handle,
+            alloc::vec![
+                event_topic("Transfer(address,address,uint256)"),
+                H256(address_word(from)),
+                H256(address_word(to)),
+            ],
+            encode_u128(value),
+        )
+    }
+}
@@ -0,0 +1,466 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+
+use pallet_evm::{
+    ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+use pallet_permission0::{
+    PermissionContract, PermissionDuration, PermissionScope, RevocationTerms, StreamScope,
+    WeightInfo,
+};
+use pallet_permission0_api::{
+    DistributionControl as ApiDistributionControl, EnforcementAuthority as ApiEnforcementAuthority,
+    Permission0StreamApi, PermissionDuration as ApiPermissionDuration,
+    RevocationTerms as ApiRevocationTerms, StreamAllocation as ApiStreamAllocation,
+    generate_root_stream_id,
+};
+use polkadot_sdk::{
+    sp_core::H256,
+    sp_runtime::{AccountId32, Percent},
+};
+
+use crate::{
+    AccountId, Balance, BlockNumber, Permission0, Runtime, RuntimeCall,
+    precompiles::{
+        AbiValue, bytes_to_account_id, call_with_weight, caller_account_id, dispatch_as, emit_log,
+        encode_abi, encode_u128, ensure_not_payable, ensure_not_static, event_topic, get_arg,
+        get_method, get_method_id, parse_account_id_arg, parse_bool_arg, parse_u128_arg,
+        parse_words_arg, record_reads, revert, u128_word, word_to_u128,
+    },
+};
+
+pub const PERMISSION_INDEX: u64 = 2053;
+
+/// Scope kinds returned by `getPermission`.
+const SCOPE_STREAM: u128 = 0;
+const SCOPE_CURATOR: u128 = 1;
+const SCOPE_NAMESPACE: u128 = 2;
+const SCOPE_WALLET: u128 = 3;
+const SCOPE_ESCROW: u128 = 4;
+
+/// Revocation kinds accepted when delegating and returned by
+/// `getPermission`. Arbiter revocation can only be set up through the
+/// extrinsic.
+const REVOCATION_IRREVOCABLE: u128 = 0;
+const REVOCATION_BY_DELEGATOR: u128 = 1;
+const REVOCATION_AFTER: u128 = 2;
+const REVOCATION_BY_ARBITERS: u128 = 3;
+
+/// Exposes permission0 streams to EVM contracts, which act as the substrate
+/// account mapped to the caller, either as delegators or recipients. See
+/// `solidity/permission.sol`.
+pub struct PermissionPrecompile;
+
+impl PermissionPrecompile {
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        let method = get_method(&input)?;
+
+        let output = match method {
+            m if m
+                == get_method_id(
+                    "delegateFixedAmountStream(bytes32[],uint16[],uint256,uint8,uint256,uint256,uint8,uint256)",
+                ) =>
+            {
+                Self::delegate_fixed_amount_stream(handle, &input)?
+            }
+            m if m
+                == get_method_id(
+                    "delegatePercentageStream(bytes32[],uint16[],bytes32[],uint8[],uint8,uint256,uint256,uint8,uint256)",
+                ) =>
+            {
+                Self::delegate_percentage_stream(handle, &input)?
+            }
+            m if m == get_method_id("executePermission(bytes32)") => {
+                Self::execute_permission(handle, &input)?
+            }
+            m if m == get_method_id("togglePermissionAccumulation(bytes32,bool)") => {
+                Self::toggle_permission_accumulation(handle, &input)?
+            }
+            m if m == get_method_id("revokePermission(bytes32)") => {
+                Self::revoke_permission(handle, &input)?
+            }
+            m if m == get_method_id("getPermission(bytes32)") => {
+                Self::get_permission(handle, &input)?
+            }
+            m if m == get_method_id("getAccumulatedAmount(bytes32,bytes32,bytes32)") => {
+                Self::get_accumulated_amount(handle, &input)?
+            }
+            m if m == get_method_id("rootStreamId(bytes32)") => Self::root_stream_id(&input)?,
+            _ => return Err(revert("unknown selector")),
+        };
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+
+    fn delegate_fixed_amount_stream(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let recipients = Self::parse_recipients(input, 0, 1)?;
+        let amount = parse_u128_arg(input, 2)?;
+        let terms = StreamTerms::parse(input, 3)?;
+
+        Self::delegate_stream(
+            handle,
+            recipients,
+            ApiStreamAllocation::FixedAmount(amount),
+            terms,
+        )
+    }
+
+    fn delegate_percentage_stream(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let recipients = Self::parse_recipients(input, 0, 1)?;
+
+        let streams = parse_words_arg(input, 2)?;
+        let percentages = parse_words_arg(input, 3)?;
+        if streams.len() != percentages.len() {
+            return Err(revert("streams and percentages length mismatch"));
+        }
+
+        let mut allocation = BTreeMap::new();
+        for (stream, percentage) in streams.into_iter().zip(percentages.iter()) {
+            let percentage = word_to_u128(percentage)?;
+            let percentage = u8::try_from(percentage)
+                .ok()
+                .filter(|percentage| *percentage <= 100)
+                .ok_or_else(|| revert("invalid percentage"))?;
+            allocation.insert(H256(stream), Percent::from_percent(percentage));
+        }
+
+        let terms = StreamTerms::parse(input, 4)?;
+
+        Self::delegate_stream(
+            handle,
+            recipients,
+            ApiStreamAllocation::Streams(allocation),
+            terms,
+        )
+    }
+
+    /// Delegates the stream permission from the caller and returns its id.
+    fn delegate_stream(
+        handle: &mut impl PrecompileHandle,
+        recipients: Vec<(AccountId, u16)>,
+        allocation: ApiStreamAllocation<Balance>,
+        terms: StreamTerms,
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let delegator = caller_account_id(handle);
+        let weight =
+            <Runtime as pallet_permission0::Config>::WeightInfo::delegate_stream_permission();
+
+        let permission_id = call_with_weight(handle, weight, || {
+            <Permission0 as Permission0StreamApi<_, _, _, _, _>>::delegate_stream_permission(
+                delegator.clone(),
+                recipients,
+                allocation,
+                terms.distribution,
+                terms.duration,
+                terms.revocation,
+                ApiEnforcementAuthority::None,
+                None,
+                None,
+            )
+        })?;
+
+        emit_log(
+            handle,
+            alloc::vec![
+                event_topic("StreamPermissionDelegated(bytes32,bytes32)"),
+                permission_id,
+                H256(*AsRef::<[u8; 32]>::as_ref(&delegator)),
+            ],
+            Vec::new(),
+        )?;
+
+        Ok(permission_id.as_bytes().to_vec())
+    }
+
+    /// Triggers a manual distribution. Must be called by the delegator.
+    fn execute_permission(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let permission_id = Self::parse_permission_id(input, 0)?;
+        let caller = caller_account_id(handle);
+
+        let call =
+            RuntimeCall::Permission0(pallet_permission0::Call::<Runtime>::execute_permission {
+                permission_id,
+            });
+        dispatch_as(handle, caller.clone(), call)?;
+
+        emit_log(
+            handle,
+            alloc::vec![
+                event_topic("PermissionExecuted(bytes32,bytes32)"),
+                permission_id,
+                H256(*AsRef::<[u8; 32]>::as_ref(&caller)),
+            ],
+            Vec::new(),
+        )?;
+
+        Ok(Vec::new())
+    }
+
+    fn toggle_permission_accumulation(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let permission_id = Self::parse_permission_id(input, 0)?;
+        let accumulating = parse_bool_arg(input, 1)?;
+
+        let call = RuntimeCall::Permission0(
+            pallet_permission0::Call::<Runtime>::toggle_permission_accumulation {
+                permission_id,
+                accumulating,
+            },
+        );
+        dispatch_as(handle, caller_account_id(handle), call)?;
+
+        emit_log(
+            handle,
+            alloc::vec![
+                event_topic("PermissionAccumulationToggled(bytes32,bool)"),
+                permission_id,
+            ],
+            encode_abi(&[AbiValue::bool(accumulating)]),
+        )?;
+
+        Ok(Vec::new())
+    }
+
+    fn revoke_permission(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let permission_id = Self::parse_permission_id(input, 0)?;
+        let caller = caller_account_id(handle);
+
+        let call =
+            RuntimeCall::Permission0(pallet_permission0::Call::<Runtime>::revoke_permission {
+                permission_id,
+            });
+        dispatch_as(handle, caller.clone(), call)?;
+
+        emit_log(
+            handle,
+            alloc::vec![
+                event_topic("PermissionRevoked(bytes32,bytes32)"),
+                permission_id,
+                H256(*AsRef::<[u8; 32]>::as_ref(&caller)),
+            ],
+            Vec::new(),
+        )?;
+
+        Ok(Vec::new())
+    }
+
+    /// Returns the ABI encoding of the permission contract: `(exists,
+    /// delegator, scope, createdAt, untilBlock, revocation, revocationBlock,
+    /// executionCount, accumulating, recipients, weights)`. `untilBlock` is
+    /// zero for indefinite permissions. The last three values are only set
+    /// for stream permissions.
+    fn get_permission(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let permission_id = Self::parse_permission_id(input, 0)?;
+
+        record_reads(handle, 1)?;
+        let Some(contract) = pallet_permission0::Permissions::<Runtime>::get(permission_id) else {
+            return Ok(encode_abi(&[
+                AbiValue::bool(false),
+                AbiValue::Word([0; 32]),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::bool(false),
+                AbiValue::Array(Vec::new()),
+                AbiValue::Array(Vec::new()),
+            ]));
+        };
+
+        Ok(Self::encode_contract(&contract))
+    }
+
+    /// Amount accumulated by the permission from one of the delegator's
+    /// streams.
+    fn get_accumulated_amount(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let delegator = parse_account_id_arg(input, 0)?;
+        let stream = Self::parse_permission_id(input, 1)?;
+        let permission_id = Self::parse_permission_id(input, 2)?;
+
+        record_reads(handle, 1)?;
+        let amount = pallet_permission0::AccumulatedStreamAmounts::<Runtime>::get((
+            delegator,
+            stream,
+            permission_id,
+        ))
+        .unwrap_or_default();
+
+        Ok(encode_u128(amount))
+    }
+
+    /// The id of the agent's emission stream.
+    fn root_stream_id(input: &[u8]) -> Result<Vec<u8>, PrecompileFailure> {
+        let agent = parse_account_id_arg(input, 0)?;
+        Ok(generate_root_stream_id(&agent).as_bytes().to_vec())
+    }
+
+    fn encode_contract(contract: &PermissionContract<Runtime>) -> Vec<u8> {
+        let scope = match &contract.scope {
+            PermissionScope::Stream(_) => SCOPE_STREAM,
+            PermissionScope::Curator(_) => SCOPE_CURATOR,
+            PermissionScope::Namespace(_) => SCOPE_NAMESPACE,
+            PermissionScope::Wallet(_) => SCOPE_WALLET,
+            PermissionScope::Escrow(_) => SCOPE_ESCROW,
+        };
+
+        let until_block = match contract.duration {
+            PermissionDuration::UntilBlock(block) => block,
+            PermissionDuration::Indefinite => 0,
+        };
+
+        let (revocation, revocation_block) = match &contract.revocation {
+            RevocationTerms::Irrevocable => (REVOCATION_IRREVOCABLE, 0),
+            RevocationTerms::RevocableByDelegator => (REVOCATION_BY_DELEGATOR, 0),
+            RevocationTerms::RevocableAfter(block) => (REVOCATION_AFTER, *block),
+            RevocationTerms::RevocableByArbiters { .. } => (REVOCATION_BY_ARBITERS, 0),
+        };
+
+        let (accumulating, recipients, weights) = match &contract.scope {
+            PermissionScope::Stream(StreamScope {
+                accumulating,
+                recipients,
+                ..
+            }) => (
+                *accumulating,
+                recipients
+                    .keys()
+                    .map(|recipient| *AsRef::<[u8; 32]>::as_ref(recipient))
+                    .collect(),
+                recipients
+                    .values()
+                    .map(|weight| u128_word((*weight).into()))
+                    .collect(),
+            ),
+            _ => (false, Vec::new(), Vec::new()),
+        };
+
+        encode_abi(&[
+            AbiValue::bool(true),
+            AbiValue::bytes32(&contract.delegator),
+            AbiValue::uint(scope),
+            AbiValue::uint(contract.created_at.into()),
+            AbiValue::uint(until_block.into()),
+            AbiValue::uint(revocation),
+            AbiValue::uint(revocation_block.into()),
+            AbiValue::uint(contract.execution_count.into()),
+            AbiValue::bool(accumulating),
+            AbiValue::Array(recipients),
+            AbiValue::Array(weights),
+        ])
+    }
+
+    /// Parses the recipients and their weights from two parallel arrays.
+    fn parse_recipients(
+        input: &[u8],
+        recipients_index: usize,
+        weights_index: usize,
+    ) -> Result<Vec<(AccountId, u16)>, PrecompileFailure> {
+        let recipients = parse_words_arg(input, recipients_index)?;
+        let weights = parse_words_arg(input, weights_index)?;
+        if recipients.len() != weights.len() {
+            return Err(revert("recipients and weights length mismatch"));
+        }
+
+        recipients
+            .iter()
+            .zip(weights.iter())
+            .map(|(recipient, weight)| {
+                let recipient: AccountId32 = bytes_to_account_id(recipient)?;
+                let weight =
+                    u16::try_from(word_to_u128(weight)?).map_err(|_| revert("invalid weight"))?;
+                Ok((recipient, weight))
+            })
+            .collect()
+    }
+
+    fn parse_permission_id(input: &[u8], index: usize) -> Result<H256, PrecompileFailure> {
+        Ok(H256::from_slice(get_arg(input, index)?))
+    }
+}
+
+/// Distribution, duration and revocation arguments shared by the stream
+/// delegation methods, passed as `(uint8 distributionKind, uint256
+/// distributionValue, uint256 untilBlock, uint8 revocationKind, uint256
+/// revocationValue)`.
+struct StreamTerms {
+    distribution: ApiDistributionControl<Balance, BlockNumber>,
+    duration: ApiPermissionDuration<BlockNumber>,
+    revocation: ApiRevocationTerms<AccountId, BlockNumber>,
+}
+
+impl StreamTerms {
+    fn parse(input: &[u8], first_index: usize) -> Result<Self, PrecompileFailure> {
+        let arg = |offset: usize| parse_u128_arg(input, first_index.saturating_add(offset));
+        let block = |value: u128| {
+            BlockNumber::try_from(value).map_err(|_| revert("block number does not fit 64 bits"))
+        };
+
+        let distribution_value = arg(1)?;
+        let distribution = match arg(0)? {
+            0 => ApiDistributionControl::Manual,
+            1 => ApiDistributionControl::Automatic(distribution_value),
+            2 => ApiDistributionControl::AtBlock(block(distribution_value)?),
+            3 => ApiDistributionControl::Interval(block(distribution_value)?),
+            _ => return Err(revert("invalid distribution kind")),
+        };
+
+        let duration = match block(arg(2)?)? {
+            0 => ApiPermissionDuration::Indefinite,
+            until => ApiPermissionDuration::UntilBlock(until),
+        };
+
+        let revocation_value = arg(4)?;
+        let revocation = match arg(3)? {
+            REVOCATION_IRREVOCABLE => ApiRevocationTerms::Irrevocable,
+            REVOCATION_BY_DELEGATOR => ApiRevocationTerms::RevocableByDelegator,
+            REVOCATION_AFTER => ApiRevocationTerms::RevocableAfter(block(revocation_value)?),
+            _ => return Err(revert("invalid revocation kind")),
+        };
+
+        Ok(Self {
+            distribution,
+            duration,
+            revocation,
+        })
+    }
+}
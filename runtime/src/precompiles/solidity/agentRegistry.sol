@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT-0
+pragma solidity ^0.8.0;
+
+/// @dev The agent registry precompile address.
+address constant AGENT_REGISTRY_ADDRESS = 0x0000000000000000000000000000000000000802;
+
+/// @dev The agent registry precompile instance.
+IAgentRegistry constant AGENT_REGISTRY_CONTRACT = IAgentRegistry(AGENT_REGISTRY_ADDRESS);
+
+/// @title Torus agent registry interface.
+/// @notice Read-only view over registered agents. Keys are 32-byte
+/// substrate public keys.
+interface IAgentRegistry {
+    /// @notice Whether `key` is registered as an agent.
+    function isAgent(bytes32 key) external view returns (bool);
+
+    /// @notice The agent registered to `key`. `exists` is false and the
+    /// other values are empty if there is none.
+    function getAgent(bytes32 key)
+        external
+        view
+        returns (bool exists, string memory name, string memory url, string memory metadata, uint256 registrationBlock);
+
+    /// @notice The agent registered under `name`, same as `getAgent`.
+    function getAgentByName(string calldata name)
+        external
+        view
+        returns (bool exists, string memory agentName, string memory url, string memory metadata, uint256 registrationBlock);
+
+    /// @notice The key of the agent registered under `name`.
+    function findAgentByName(string calldata name) external view returns (bool found, bytes32 key);
+}
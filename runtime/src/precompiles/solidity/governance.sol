@@ -0,0 +1,33 @@
+// SPDX-License-Identifier: MIT-0
+pragma solidity ^0.8.0;
+
+/// @dev The governance precompile address.
+address constant GOVERNANCE_ADDRESS = 0x0000000000000000000000000000000000000804;
+
+/// @dev The governance precompile instance.
+IGovernance constant GOVERNANCE_CONTRACT = IGovernance(GOVERNANCE_ADDRESS);
+
+/// @title Torus governance interface.
+/// @notice Votes are cast as the substrate account mapped to the caller.
+interface IGovernance {
+    /// @notice The proposal `proposalId`. `status` is 0 when open, 1 when
+    /// accepted, 2 when refused and 3 when expired. Stakes are estimates
+    /// while the proposal is open and final once it is closed.
+    function getProposal(uint256 proposalId)
+        external
+        view
+        returns (
+            bool exists,
+            bytes32 proposer,
+            uint8 status,
+            uint256 expirationBlock,
+            uint256 stakeFor,
+            uint256 stakeAgainst
+        );
+
+    /// @notice Votes for or against the proposal `proposalId`.
+    function voteProposal(uint256 proposalId, bool agree) external;
+
+    /// @notice Removes the caller's vote on the proposal `proposalId`.
+    function removeVoteProposal(uint256 proposalId) external;
+}
@@ -0,0 +1,26 @@
+// SPDX-License-Identifier: MIT-0
+pragma solidity ^0.8.0;
+
+/// @dev The namespace precompile address.
+address constant NAMESPACE_ADDRESS = 0x0000000000000000000000000000000000000803;
+
+/// @dev The namespace precompile instance.
+INamespace constant NAMESPACE_CONTRACT = INamespace(NAMESPACE_ADDRESS);
+
+/// @title Torus namespace interface.
+/// @notice Read-only view over agent namespaces. Paths are given in their
+/// dotted form, e.g. `agent.alice.memory`. Invalid paths revert.
+interface INamespace {
+    /// @notice Whether `owner` has registered `path`.
+    function namespaceExists(bytes32 owner, string calldata path) external view returns (bool);
+
+    /// @notice The agent owning `path`, if it was registered.
+    function namespaceOwner(string calldata path) external view returns (bool found, bytes32 owner);
+
+    /// @notice Fee and deposit `account` would pay to register `path`,
+    /// including its missing parents.
+    function namespaceCreationCost(bytes32 account, string calldata path)
+        external
+        view
+        returns (uint256 fee, uint256 deposit);
+}
@@ -0,0 +1,39 @@
+// SPDX-License-Identifier: MIT-0
+pragma solidity ^0.8.0;
+
+/// @dev The native token precompile address.
+address constant NATIVE_TOKEN_ADDRESS = 0x0000000000000000000000000000000000000800;
+
+/// @dev The native token precompile instance.
+INativeToken constant NATIVE_TOKEN_CONTRACT = INativeToken(NATIVE_TOKEN_ADDRESS);
+
+/// @title Torus native token interface.
+/// @notice ERC-20 view of the TORUS balances. Addresses are resolved to
+/// their mapped substrate accounts. Amounts above 2^128 - 1 revert, except
+/// for `approve`, where they grant an unlimited allowance.
+interface INativeToken {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    event Approval(address indexed owner, address indexed spender, uint256 value);
+
+    function name() external view returns (string memory);
+
+    function symbol() external view returns (string memory);
+
+    function decimals() external view returns (uint8);
+
+    function totalSupply() external view returns (uint256);
+
+    function balanceOf(address owner) external view returns (uint256);
+
+    function allowance(address owner, address spender) external view returns (uint256);
+
+    function transfer(address to, uint256 value) external returns (bool);
+
+    function approve(address spender, uint256 value) external returns (bool);
+
+    function transferFrom(address from, address to, uint256 value) external returns (bool);
+
+    /// @notice Transfers `msg.value` to the substrate account `data`.
+    function transfer(bytes32 data) external payable;
+}
@@ -0,0 +1,103 @@
+// SPDX-License-Identifier: MIT-0
+pragma solidity ^0.8.0;
+
+/// @dev The permission precompile address.
+address constant PERMISSION_ADDRESS = 0x0000000000000000000000000000000000000805;
+
+/// @dev The permission precompile instance.
+IPermission constant PERMISSION_CONTRACT = IPermission(PERMISSION_ADDRESS);
+
+/// @title Torus permission interface.
+/// @notice Delegates and manages stream permissions as the substrate account
+/// mapped to the caller. Accounts, streams and permissions are identified by
+/// 32-byte values.
+///
+/// Stream delegations share the following terms:
+/// - `distributionKind`: 0 manual, 1 automatic once `distributionValue` is
+///   accumulated, 2 at block `distributionValue`, 3 every
+///   `distributionValue` blocks.
+/// - `untilBlock`: block the permission expires at, 0 for indefinite.
+/// - `revocationKind`: 0 irrevocable, 1 revocable by the delegator, 2
+///   revocable after block `revocationValue`. Arbiter revocation must be set
+///   up through the extrinsic and is reported as 3.
+interface IPermission {
+    /// @notice Emitted when the caller delegates a stream permission.
+    event StreamPermissionDelegated(bytes32 indexed permissionId, bytes32 indexed delegator);
+
+    /// @notice Emitted when a permission is executed through the precompile.
+    event PermissionExecuted(bytes32 indexed permissionId, bytes32 indexed caller);
+
+    /// @notice Emitted when accumulation is toggled through the precompile.
+    event PermissionAccumulationToggled(bytes32 indexed permissionId, bool accumulating);
+
+    /// @notice Emitted when a permission is revoked through the precompile.
+    event PermissionRevoked(bytes32 indexed permissionId, bytes32 indexed caller);
+
+    /// @notice Delegates a stream of `amount` to `recipients`, split by
+    /// `weights`.
+    function delegateFixedAmountStream(
+        bytes32[] calldata recipients,
+        uint16[] calldata weights,
+        uint256 amount,
+        uint8 distributionKind,
+        uint256 distributionValue,
+        uint256 untilBlock,
+        uint8 revocationKind,
+        uint256 revocationValue
+    ) external returns (bytes32 permissionId);
+
+    /// @notice Delegates `percentages` of the caller's `streams` to
+    /// `recipients`, split by `weights`.
+    function delegatePercentageStream(
+        bytes32[] calldata recipients,
+        uint16[] calldata weights,
+        bytes32[] calldata streams,
+        uint8[] calldata percentages,
+        uint8 distributionKind,
+        uint256 distributionValue,
+        uint256 untilBlock,
+        uint8 revocationKind,
+        uint256 revocationValue
+    ) external returns (bytes32 permissionId);
+
+    /// @notice Distributes the amounts accumulated by `permissionId`. Only
+    /// callable by its delegator.
+    function executePermission(bytes32 permissionId) external;
+
+    /// @notice Enables or disables accumulation for `permissionId`.
+    function togglePermissionAccumulation(bytes32 permissionId, bool accumulating) external;
+
+    /// @notice Revokes `permissionId`, subject to its revocation terms.
+    function revokePermission(bytes32 permissionId) external;
+
+    /// @notice The permission contract `permissionId`. `scope` is 0 for
+    /// streams, 1 for curators, 2 for namespaces, 3 for wallets and 4 for
+    /// escrows. The
+    /// accumulation state, recipients and weights are only set for streams.
+    function getPermission(bytes32 permissionId)
+        external
+        view
+        returns (
+            bool exists,
+            bytes32 delegator,
+            uint8 scope,
+            uint256 createdAt,
+            uint256 untilBlock,
+            uint8 revocationKind,
+            uint256 revocationBlock,
+            uint256 executionCount,
+            bool accumulating,
+            bytes32[] memory recipients,
+            uint16[] memory weights
+        );
+
+    /// @notice Amount accumulated by `permissionId` from `delegator`'s
+    /// `stream`.
+    function getAccumulatedAmount(bytes32 delegator, bytes32 stream, bytes32 permissionId)
+        external
+        view
+        returns (uint256);
+
+    /// @notice The id of `agent`'s emission stream.
+    function rootStreamId(bytes32 agent) external pure returns (bytes32);
+}
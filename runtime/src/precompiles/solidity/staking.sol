@@ -0,0 +1,32 @@
+// SPDX-License-Identifier: MIT-0
+pragma solidity ^0.8.0;
+
+/// @dev The staking precompile address.
+address constant STAKING_ADDRESS = 0x0000000000000000000000000000000000000801;
+
+/// @dev The staking precompile instance.
+IStaking constant STAKING_CONTRACT = IStaking(STAKING_ADDRESS);
+
+/// @title Torus staking interface.
+/// @notice Calls are dispatched as the substrate account mapped to the
+/// caller. Accounts are 32-byte substrate public keys and amounts are in
+/// the smallest unit of the native token.
+interface IStaking {
+    /// @notice Stakes `amount` from the caller on `agent`.
+    function addStake(bytes32 agent, uint256 amount) external;
+
+    /// @notice Removes `amount` of the caller's stake from `agent`.
+    function removeStake(bytes32 agent, uint256 amount) external;
+
+    /// @notice Moves `amount` of the caller's stake from `agent` to `newAgent`.
+    function transferStake(bytes32 agent, bytes32 newAgent, uint256 amount) external;
+
+    /// @notice Amount staked by `staker` on `agent`.
+    function getStake(bytes32 staker, bytes32 agent) external view returns (uint256);
+
+    /// @notice Total amount staked on `agent`.
+    function getTotalStake(bytes32 agent) external view returns (uint256);
+
+    /// @notice Total amount staked by `staker` across all agents.
+    function getTotalStaked(bytes32 staker) external view returns (uint256);
+}
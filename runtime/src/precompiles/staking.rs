@@ -0,0 +1,160 @@
+use alloc::vec::Vec;
+
+use pallet_evm::{
+    ExitSucceed, PrecompileFailure, PrecompileHandle, PrecompileOutput, PrecompileResult,
+};
+
+use crate::{
+    Runtime, RuntimeCall,
+    precompiles::{
+        caller_account_id, dispatch_as, encode_u128, ensure_not_payable, ensure_not_static,
+        get_method, get_method_id, parse_account_id_arg, parse_u128_arg, record_reads, revert,
+    },
+};
+
+pub const STAKING_INDEX: u64 = 2049;
+
+/// Exposes torus0 staking to EVM contracts. Calls are dispatched as the
+/// substrate account mapped to the caller. See `solidity/staking.sol`.
+pub struct StakingPrecompile;
+
+impl StakingPrecompile {
+    pub fn execute(handle: &mut impl PrecompileHandle) -> PrecompileResult {
+        let input = handle.input().to_vec();
+        let method = get_method(&input)?;
+
+        let output = match method {
+            m if m == get_method_id("addStake(bytes32,uint256)") => {
+                Self::add_stake(handle, &input)?
+            }
+            m if m == get_method_id("removeStake(bytes32,uint256)") => {
+                Self::remove_stake(handle, &input)?
+            }
+            m if m == get_method_id("transferStake(bytes32,bytes32,uint256)") => {
+                Self::transfer_stake(handle, &input)?
+            }
+            m if m == get_method_id("getStake(bytes32,bytes32)") => {
+                Self::get_stake(handle, &input)?
+            }
+            m if m == get_method_id("getTotalStake(bytes32)") => {
+                Self::get_total_stake(handle, &input)?
+            }
+            m if m == get_method_id("getTotalStaked(bytes32)") => {
+                Self::get_total_staked(handle, &input)?
+            }
+            _ => return Err(revert("unknown selector")),
+        };
+
+        Ok(PrecompileOutput {
+            exit_status: ExitSucceed::Returned,
+            output,
+        })
+    }
+
+    fn add_stake(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let agent_key = parse_account_id_arg(input, 0)?;
+        let amount = parse_u128_arg(input, 1)?;
+
+        let call =
+            RuntimeCall::Torus0(pallet_torus0::Call::<Runtime>::add_stake { agent_key, amount });
+        dispatch_as(handle, caller_account_id(handle), call)?;
+
+        Ok(Vec::new())
+    }
+
+    fn remove_stake(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let agent_key = parse_account_id_arg(input, 0)?;
+        let amount = parse_u128_arg(input, 1)?;
+
+        let call =
+            RuntimeCall::Torus0(pallet_torus0::Call::<Runtime>::remove_stake { agent_key, amount });
+        dispatch_as(handle, caller_account_id(handle), call)?;
+
+        Ok(Vec::new())
+    }
+
+    fn transfer_stake(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        ensure_not_static(handle)?;
+        ensure_not_payable(handle)?;
+
+        let agent_key = parse_account_id_arg(input, 0)?;
+        let new_agent_key = parse_account_id_arg(input, 1)?;
+        let amount = parse_u128_arg(input, 2)?;
+
+        let call = RuntimeCall::Torus0(pallet_torus0::Call::<Runtime>::transfer_stake {
+            agent_key,
+            new_agent_key,
+            amount,
+        });
+        dispatch_as(handle, caller_account_id(handle), call)?;
+
+        Ok(Vec::new())
+    }
+
+    /// Amount staked by the staker on the agent.
+    fn get_stake(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let staker = parse_account_id_arg(input, 0)?;
+        let agent = parse_account_id_arg(input, 1)?;
+
+        record_reads(handle, 1)?;
+        let stake = pallet_torus0::StakingTo::<Runtime>::get(staker, agent).unwrap_or_default();
+
+        Ok(encode_u128(stake))
+    }
+
+    /// Total amount staked on the agent.
+    fn get_total_stake(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let agent = parse_account_id_arg(input, 0)?;
+
+        let stakes = pallet_torus0::StakedBy::<Runtime>::iter_prefix_values(agent);
+        Ok(encode_u128(Self::sum_stakes(handle, stakes)?))
+    }
+
+    /// Total amount the staker has staked across all agents.
+    fn get_total_staked(
+        handle: &mut impl PrecompileHandle,
+        input: &[u8],
+    ) -> Result<Vec<u8>, PrecompileFailure> {
+        let staker = parse_account_id_arg(input, 0)?;
+
+        let stakes = pallet_torus0::StakingTo::<Runtime>::iter_prefix_values(staker);
+        Ok(encode_u128(Self::sum_stakes(handle, stakes)?))
+    }
+
+    /// Sums the stakes, charging one storage read for each of them.
+    fn sum_stakes(
+        handle: &mut impl PrecompileHandle,
+        stakes: impl Iterator<Item = u128>,
+    ) -> Result<u128, PrecompileFailure> {
+        record_reads(handle, 1)?;
+
+        let mut total: u128 = 0;
+        for stake in stakes {
+            record_reads(handle, 1)?;
+            total = total.saturating_add(stake);
+        }
+
+        Ok(total)
+    }
+}
@@ -0,0 +1,172 @@
+use alloc::vec::Vec;
+
+use pallet_evm::PrecompileFailure;
+use pallet_torus0::{Agents, agent::Agent};
+use polkadot_sdk::{
+    frame_support::BoundedVec,
+    sp_runtime::{AccountId32, Percent},
+};
+
+use super::{account, address, call, input, invalid_range, new_test_ext, register_agent};
+use crate::{
+    Runtime,
+    precompiles::{
+        AGENT_REGISTRY_INDEX, AbiValue, MAX_AGENTS_SCANNED, encode_abi, encode_bool, revert,
+        u128_word,
+    },
+};
+
+fn call_with_name(signature: &str, name: &[u8]) -> Result<Vec<u8>, PrecompileFailure> {
+    call(
+        AGENT_REGISTRY_INDEX,
+        address(1),
+        input(signature, &[AbiValue::Bytes(name.to_vec())]),
+    )
+    .map(|output| output.output)
+}
+
+/// Inserts an agent without going through registration, which is rate
+/// limited.
+fn insert_agent(id: u32) {
+    let key = AccountId32::new(u128_word(id.into()));
+    let name = alloc::format!("agent-{id}").into_bytes();
+
+    Agents::<Runtime>::insert(
+        key.clone(),
+        Agent {
+            key,
+            name: BoundedVec::truncate_from(name),
+            url: BoundedVec::truncate_from(b"agent://url".to_vec()),
+            metadata: BoundedVec::truncate_from(b"metadata".to_vec()),
+            weight_penalty_factor: Percent::zero(),
+            registration_block: 0,
+            fees: Default::default(),
+            last_update_block: 0,
+        },
+    );
+}
+
+#[test]
+fn unknown_selectors_revert() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(AGENT_REGISTRY_INDEX, address(1), input("agents()", &[])),
+            Err(revert("unknown selector"))
+        );
+        assert_eq!(
+            call(AGENT_REGISTRY_INDEX, address(1), Vec::new()),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn malformed_arguments_fail() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(
+                AGENT_REGISTRY_INDEX,
+                address(1),
+                input("isAgent(bytes32)", &[])
+            ),
+            Err(invalid_range())
+        );
+
+        // The offset of the string points past the end of the input.
+        assert_eq!(
+            call(
+                AGENT_REGISTRY_INDEX,
+                address(1),
+                input("getAgentByName(string)", &[AbiValue::uint(64)])
+            ),
+            Err(invalid_range())
+        );
+
+        // The length of the string is larger than the input.
+        let mut truncated = input(
+            "findAgentByName(string)",
+            &[AbiValue::Bytes(b"alice".to_vec())],
+        );
+        truncated.truncate(truncated.len().saturating_sub(32));
+        assert_eq!(
+            call(AGENT_REGISTRY_INDEX, address(1), truncated),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn agents_are_looked_up_by_key_and_name() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+        register_agent(&agent, "alice");
+
+        let is_agent = |key: &AccountId32| {
+            call(
+                AGENT_REGISTRY_INDEX,
+                address(1),
+                input("isAgent(bytes32)", &[AbiValue::bytes32(key)]),
+            )
+            .map(|output| output.output)
+        };
+        assert_eq!(is_agent(&agent), Ok(encode_bool(true)));
+        assert_eq!(is_agent(&account(2)), Ok(encode_bool(false)));
+
+        let expected = encode_abi(&[
+            AbiValue::bool(true),
+            AbiValue::Bytes(b"alice".to_vec()),
+            AbiValue::Bytes(b"agent://url".to_vec()),
+            AbiValue::Bytes(b"metadata".to_vec()),
+            AbiValue::uint(1),
+        ]);
+        assert_eq!(
+            call(
+                AGENT_REGISTRY_INDEX,
+                address(1),
+                input("getAgent(bytes32)", &[AbiValue::bytes32(&agent)])
+            )
+            .map(|output| output.output),
+            Ok(expected.clone())
+        );
+        assert_eq!(
+            call_with_name("getAgentByName(string)", b"alice"),
+            Ok(expected)
+        );
+
+        assert_eq!(
+            call_with_name("findAgentByName(string)", b"alice"),
+            Ok(encode_abi(&[
+                AbiValue::bool(true),
+                AbiValue::bytes32(&agent)
+            ]))
+        );
+        assert_eq!(
+            call_with_name("findAgentByName(string)", b"bob"),
+            Ok(encode_abi(&[
+                AbiValue::bool(false),
+                AbiValue::Word([0; 32])
+            ]))
+        );
+    });
+}
+
+#[test]
+fn name_lookups_are_bounded() {
+    new_test_ext().execute_with(|| {
+        let not_found = encode_abi(&[AbiValue::bool(false), AbiValue::Word([0; 32])]);
+
+        for id in 0..MAX_AGENTS_SCANNED as u32 {
+            insert_agent(id);
+        }
+        assert_eq!(
+            call_with_name("findAgentByName(string)", b"alice"),
+            Ok(not_found)
+        );
+
+        insert_agent(MAX_AGENTS_SCANNED as u32);
+        assert_eq!(
+            call_with_name("findAgentByName(string)", b"alice"),
+            Err(revert("too many agents to scan"))
+        );
+    });
+}
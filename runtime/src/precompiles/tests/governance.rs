@@ -0,0 +1,270 @@
+use alloc::vec::Vec;
+
+use pallet_evm::PrecompileFailure;
+use pallet_governance::{GlobalGovernanceConfig, Proposals, proposal::ProposalStatus};
+use polkadot_sdk::{frame_support::traits::Currency, sp_core::H160};
+
+use super::{
+    MockHandle, account, address, call, fund, input, invalid_range, new_test_ext, register_agent,
+};
+use crate::{
+    Balances, Runtime,
+    configs::as_tors,
+    precompiles::{AbiValue, GOVERNANCE_INDEX, address_to_account_id, encode_abi, revert},
+};
+
+fn vote(caller: H160, proposal_id: u128, agree: bool) -> Result<Vec<u8>, PrecompileFailure> {
+    call(
+        GOVERNANCE_INDEX,
+        caller,
+        input(
+            "voteProposal(uint256,bool)",
+            &[AbiValue::uint(proposal_id), AbiValue::bool(agree)],
+        ),
+    )
+    .map(|output| output.output)
+}
+
+fn remove_vote(caller: H160, proposal_id: u128) -> Result<Vec<u8>, PrecompileFailure> {
+    call(
+        GOVERNANCE_INDEX,
+        caller,
+        input(
+            "removeVoteProposal(uint256)",
+            &[AbiValue::uint(proposal_id)],
+        ),
+    )
+    .map(|output| output.output)
+}
+
+/// Opens a global custom proposal, returning its id.
+fn add_proposal() -> u64 {
+    let proposer = account(1);
+    let cost = GlobalGovernanceConfig::<Runtime>::get().proposal_cost;
+    let _ = Balances::make_free_balance_be(&proposer, cost.saturating_add(as_tors(10)));
+
+    pallet_governance::proposal::add_global_custom_proposal::<Runtime>(
+        proposer,
+        b"metadata".to_vec(),
+    )
+    .expect("failed to add proposal");
+
+    Proposals::<Runtime>::iter_keys()
+        .max()
+        .expect("proposal was added")
+}
+
+/// Gives the account mapped to `caller` stake to vote with, voting on its
+/// own behalf.
+fn add_voter(caller: H160) {
+    let agent = account(2);
+    if !pallet_torus0::Agents::<Runtime>::contains_key(&agent) {
+        register_agent(&agent, "alice");
+    }
+
+    let voter = fund(caller, as_tors(100));
+    pallet_torus0::stake::add_stake::<Runtime>(voter.clone(), agent, as_tors(10))
+        .expect("failed to add stake");
+    pallet_governance::voting::disable_delegation::<Runtime>(voter)
+        .expect("failed to disable delegation");
+}
+
+#[test]
+fn unknown_selectors_revert() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(GOVERNANCE_INDEX, address(1), input("proposals()", &[])),
+            Err(revert("unknown selector"))
+        );
+        assert_eq!(
+            call(GOVERNANCE_INDEX, address(1), alloc::vec![0x01]),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn malformed_arguments_fail() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(
+                GOVERNANCE_INDEX,
+                address(1),
+                input("getProposal(uint256)", &[AbiValue::uint(u128::MAX)])
+            ),
+            Err(revert("value does not fit 64 bits"))
+        );
+        assert_eq!(
+            call(
+                GOVERNANCE_INDEX,
+                address(1),
+                input(
+                    "voteProposal(uint256,bool)",
+                    &[AbiValue::uint(0), AbiValue::uint(2)]
+                )
+            ),
+            Err(revert("invalid bool"))
+        );
+        assert_eq!(
+            call(
+                GOVERNANCE_INDEX,
+                address(1),
+                input("voteProposal(uint256,bool)", &[AbiValue::uint(0)])
+            ),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn calls_revert_with_the_dispatch_error() {
+    new_test_ext().execute_with(|| {
+        let caller = address(10);
+        add_voter(caller);
+
+        assert_eq!(vote(caller, 0, true), Err(revert("ProposalNotFound")));
+
+        let proposal_id = add_proposal();
+
+        assert_eq!(
+            vote(address(11), proposal_id.into(), true),
+            Err(revert("InsufficientStake"))
+        );
+        assert_eq!(
+            remove_vote(caller, proposal_id.into()),
+            Err(revert("NotVoted"))
+        );
+        assert_eq!(
+            MockHandle::new(
+                GOVERNANCE_INDEX,
+                caller,
+                input(
+                    "voteProposal(uint256,bool)",
+                    &[AbiValue::uint(proposal_id.into()), AbiValue::bool(true)]
+                )
+            )
+            .with_value(1)
+            .execute(),
+            Err(revert("function is not payable"))
+        );
+    });
+}
+
+#[test]
+fn votes_are_cast_as_the_caller() {
+    new_test_ext().execute_with(|| {
+        let proposal_id = add_proposal();
+
+        let caller = address(10);
+        let other_caller = address(11);
+        add_voter(caller);
+        add_voter(other_caller);
+
+        let voter = address_to_account_id(caller);
+        let votes = || {
+            let Some(ProposalStatus::Open {
+                votes_for,
+                votes_against,
+                ..
+            }) = Proposals::<Runtime>::get(proposal_id).map(|proposal| proposal.status)
+            else {
+                panic!("proposal is not open");
+            };
+            (votes_for.into_inner(), votes_against.into_inner())
+        };
+
+        assert_eq!(vote(caller, proposal_id.into(), true), Ok(Vec::new()));
+        assert!(votes().0.contains(&voter));
+        assert_eq!(
+            vote(caller, proposal_id.into(), false),
+            Err(revert("AlreadyVoted"))
+        );
+
+        // Other callers cannot remove the caller's vote.
+        assert_eq!(
+            remove_vote(other_caller, proposal_id.into()),
+            Err(revert("NotVoted"))
+        );
+        assert_eq!(remove_vote(caller, proposal_id.into()), Ok(Vec::new()));
+        assert!(votes().0.is_empty());
+
+        assert_eq!(vote(caller, proposal_id.into(), false), Ok(Vec::new()));
+        assert!(votes().1.contains(&voter));
+    });
+}
+
+#[test]
+fn proposals_are_read_from_storage() {
+    new_test_ext().execute_with(|| {
+        let get_proposal = |proposal_id: u128| {
+            call(
+                GOVERNANCE_INDEX,
+                address(1),
+                input("getProposal(uint256)", &[AbiValue::uint(proposal_id)]),
+            )
+            .map(|output| output.output)
+        };
+
+        assert_eq!(
+            get_proposal(0),
+            Ok(encode_abi(&[
+                AbiValue::bool(false),
+                AbiValue::Word([0; 32]),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+            ]))
+        );
+
+        let proposal_id = add_proposal();
+        let proposal = Proposals::<Runtime>::get(proposal_id).expect("proposal exists");
+
+        assert_eq!(
+            get_proposal(proposal_id.into()),
+            Ok(encode_abi(&[
+                AbiValue::bool(true),
+                AbiValue::bytes32(account(1)),
+                AbiValue::uint(0),
+                AbiValue::uint(proposal.expiration_block.into()),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+            ]))
+        );
+    });
+}
+
+#[test]
+fn delegate_and_static_calls_cannot_vote() {
+    new_test_ext().execute_with(|| {
+        let proposal_id = add_proposal();
+        let caller = address(10);
+        add_voter(caller);
+
+        let vote = || {
+            MockHandle::new(
+                GOVERNANCE_INDEX,
+                caller,
+                input(
+                    "voteProposal(uint256,bool)",
+                    &[AbiValue::uint(proposal_id.into()), AbiValue::bool(true)],
+                ),
+            )
+        };
+        assert_eq!(
+            vote().delegated_by(address(20)).execute(),
+            Err(revert("cannot be called through delegatecall or callcode"))
+        );
+        assert_eq!(
+            vote().static_call().execute(),
+            Err(revert("cannot modify state in a static call"))
+        );
+
+        let Some(ProposalStatus::Open { votes_for, .. }) =
+            Proposals::<Runtime>::get(proposal_id).map(|proposal| proposal.status)
+        else {
+            panic!("proposal is not open");
+        };
+        assert!(votes_for.is_empty());
+    });
+}
@@ -0,0 +1,218 @@
+use alloc::vec::Vec;
+
+use fp_evm::{Context, ExitError, ExitReason, Transfer};
+use pallet_evm::{PrecompileFailure, PrecompileHandle, PrecompileResult, PrecompileSet};
+use polkadot_sdk::{
+    frame_support::traits::Currency,
+    frame_system,
+    sp_core::{H160, H256, U256},
+    sp_io,
+    sp_runtime::{AccountId32, BuildStorage},
+    sp_tracing,
+};
+
+use crate::{
+    Balances, Runtime, System,
+    configs::as_tors,
+    precompiles::{
+        AbiValue, FrontierPrecompiles, address_to_account_id, encode_abi, get_method_id,
+    },
+};
+
+mod agent_registry;
+mod governance;
+mod namespace;
+mod native_token;
+mod permission;
+mod staking;
+
+/// Gas available to each precompile call.
+const GAS_LIMIT: u64 = 1_000_000_000;
+
+/// Handle running a single precompile call, recording the gas it charges
+/// and the logs it emits.
+pub struct MockHandle {
+    input: Vec<u8>,
+    context: Context,
+    code_address: H160,
+    is_static: bool,
+    pub gas_used: u64,
+    pub logs: Vec<(H160, Vec<H256>, Vec<u8>)>,
+}
+
+impl MockHandle {
+    /// A call from `caller` to the precompile at `index`.
+    pub fn new(index: u64, caller: H160, input: Vec<u8>) -> Self {
+        let code_address = H160::from_low_u64_be(index);
+
+        Self {
+            input,
+            context: Context {
+                address: code_address,
+                caller,
+                apparent_value: U256::zero(),
+            },
+            code_address,
+            is_static: false,
+            gas_used: 0,
+            logs: Vec::new(),
+        }
+    }
+
+    /// Sends `value` along with the call.
+    pub fn with_value(mut self, value: u128) -> Self {
+        self.context.apparent_value = value.into();
+        self
+    }
+
+    /// Runs the call in the context of `contract`, as a delegate call from
+    /// it would.
+    pub fn delegated_by(mut self, contract: H160) -> Self {
+        self.context.address = contract;
+        self
+    }
+
+    /// Runs the call in a static context.
+    pub fn static_call(mut self) -> Self {
+        self.is_static = true;
+        self
+    }
+
+    /// Executes the call through the runtime precompile set.
+    pub fn execute(&mut self) -> PrecompileResult {
+        FrontierPrecompiles
+            .execute(self)
+            .expect("address is not a precompile")
+    }
+}
+
+impl PrecompileHandle for MockHandle {
+    fn call(
+        &mut self,
+        _to: H160,
+        _transfer: Option<Transfer>,
+        _input: Vec<u8>,
+        _gas_limit: Option<u64>,
+        _is_static: bool,
+        _context: &Context,
+    ) -> (ExitReason, Vec<u8>) {
+        unimplemented!("precompiles do not call into contracts")
+    }
+
+    fn record_cost(&mut self, cost: u64) -> Result<(), ExitError> {
+        self.gas_used = self
+            .gas_used
+            .checked_add(cost)
+            .filter(|used| *used <= GAS_LIMIT)
+            .ok_or(ExitError::OutOfGas)?;
+        Ok(())
+    }
+
+    fn record_external_cost(
+        &mut self,
+        _ref_time: Option<u64>,
+        _proof_size: Option<u64>,
+        _storage_growth: Option<u64>,
+    ) -> Result<(), ExitError> {
+        Ok(())
+    }
+
+    fn refund_external_cost(&mut self, _ref_time: Option<u64>, _proof_size: Option<u64>) {}
+
+    fn remaining_gas(&self) -> u64 {
+        GAS_LIMIT.saturating_sub(self.gas_used)
+    }
+
+    fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) -> Result<(), ExitError> {
+        self.logs.push((address, topics, data));
+        Ok(())
+    }
+
+    fn code_address(&self) -> H160 {
+        self.code_address
+    }
+
+    fn input(&self) -> &[u8] {
+        &self.input
+    }
+
+    fn context(&self) -> &Context {
+        &self.context
+    }
+
+    fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    fn gas_limit(&self) -> Option<u64> {
+        Some(GAS_LIMIT)
+    }
+}
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    sp_tracing::try_init_simple();
+    let t = frame_system::GenesisConfig::<Runtime>::default()
+        .build_storage()
+        .unwrap();
+    let mut ext = sp_io::TestExternalities::new(t);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
+
+/// Calls the precompile at `index` as `caller`.
+pub fn call(index: u64, caller: H160, input: Vec<u8>) -> PrecompileResult {
+    MockHandle::new(index, caller, input).execute()
+}
+
+/// Encodes a call to the method with the given signature.
+pub fn input(signature: &str, args: &[AbiValue]) -> Vec<u8> {
+    get_method_id(signature)
+        .into_iter()
+        .chain(encode_abi(args))
+        .collect()
+}
+
+/// Failure returned when the input is too short for the expected arguments.
+pub fn invalid_range() -> PrecompileFailure {
+    PrecompileFailure::Error {
+        exit_status: ExitError::InvalidRange,
+    }
+}
+
+/// An ABI word with every byte set, overflowing any argument narrower
+/// than 256 bits.
+pub fn max_word() -> AbiValue {
+    AbiValue::Word([u8::MAX; 32])
+}
+
+pub fn address(id: u64) -> H160 {
+    H160::from_low_u64_be(id)
+}
+
+pub fn account(id: u8) -> AccountId32 {
+    AccountId32::new([id; 32])
+}
+
+/// Gives the account mapped to `address` the given free balance.
+pub fn fund(address: H160, amount: u128) -> AccountId32 {
+    let account = address_to_account_id(address);
+    let _ = Balances::make_free_balance_be(&account, amount);
+    account
+}
+
+/// Registers `key` as an agent, funding its registration.
+pub fn register_agent(key: &AccountId32, name: &str) {
+    pallet_governance::whitelist::add_to_whitelist::<Runtime>(key.clone())
+        .expect("failed to whitelist agent");
+
+    let burn = pallet_torus0::Burn::<Runtime>::get();
+    let _ = Balances::make_free_balance_be(key, burn.saturating_add(as_tors(10)));
+
+    pallet_torus0::agent::register::<Runtime>(
+        key.clone(),
+        name.as_bytes().to_vec(),
+        b"agent://url".to_vec(),
+        b"metadata".to_vec(),
+    )
+    .expect("failed to register agent");
+}
@@ -0,0 +1,90 @@
+use alloc::vec::Vec;
+
+use pallet_evm::PrecompileFailure;
+
+use super::{account, address, call, input, invalid_range, new_test_ext, register_agent};
+use crate::precompiles::{AbiValue, NAMESPACE_INDEX, encode_abi, encode_bool, revert};
+
+fn namespace_owner(path: &[u8]) -> Result<Vec<u8>, PrecompileFailure> {
+    call(
+        NAMESPACE_INDEX,
+        address(1),
+        input("namespaceOwner(string)", &[AbiValue::Bytes(path.to_vec())]),
+    )
+    .map(|output| output.output)
+}
+
+#[test]
+fn unknown_selectors_revert() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(NAMESPACE_INDEX, address(1), input("namespaces()", &[])),
+            Err(revert("unknown selector"))
+        );
+        assert_eq!(
+            call(NAMESPACE_INDEX, address(1), alloc::vec![0; 3]),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn malformed_arguments_fail() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(
+                NAMESPACE_INDEX,
+                address(1),
+                input(
+                    "namespaceExists(bytes32,string)",
+                    &[AbiValue::bytes32(account(1))]
+                )
+            ),
+            Err(invalid_range())
+        );
+        assert_eq!(namespace_owner(b""), Err(revert("empty namespace path")));
+        assert_eq!(
+            namespace_owner(b"memory.alice"),
+            Err(revert("path must begin with agent prefix"))
+        );
+    });
+}
+
+#[test]
+fn namespaces_are_owned_by_their_agent() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+        register_agent(&agent, "alice");
+
+        let namespace_exists = |path: &[u8]| {
+            call(
+                NAMESPACE_INDEX,
+                address(1),
+                input(
+                    "namespaceExists(bytes32,string)",
+                    &[AbiValue::bytes32(&agent), AbiValue::Bytes(path.to_vec())],
+                ),
+            )
+            .map(|output| output.output)
+        };
+        assert_eq!(namespace_exists(b"agent.alice"), Ok(encode_bool(true)));
+        assert_eq!(
+            namespace_exists(b"agent.alice.memory"),
+            Ok(encode_bool(false))
+        );
+
+        let not_found = encode_abi(&[AbiValue::bool(false), AbiValue::Word([0; 32])]);
+        assert_eq!(
+            namespace_owner(b"agent.alice"),
+            Ok(encode_abi(&[
+                AbiValue::bool(true),
+                AbiValue::bytes32(&agent)
+            ]))
+        );
+        assert_eq!(
+            namespace_owner(b"agent.alice.memory"),
+            Ok(not_found.clone())
+        );
+        assert_eq!(namespace_owner(b"agent.bob"), Ok(not_found));
+    });
+}
@@ -0,0 +1,352 @@
+use alloc::vec::Vec;
+
+use pallet_evm::PrecompileFailure;
+use polkadot_sdk::sp_core::{H160, H256};
+
+use super::{
+    MockHandle, account, address, call, fund, input, invalid_range, max_word, new_test_ext,
+};
+use crate::{
+    Balances,
+    configs::as_tors,
+    precompiles::{
+        AbiValue, NATIVE_TOKEN_INDEX, address_to_account_id, address_word, encode_abi, encode_bool,
+        encode_u128, event_topic, revert,
+    },
+};
+
+fn call_token(
+    caller: H160,
+    signature: &str,
+    args: &[AbiValue],
+) -> Result<Vec<u8>, PrecompileFailure> {
+    call(NATIVE_TOKEN_INDEX, caller, input(signature, args)).map(|output| output.output)
+}
+
+fn balance_of(owner: H160) -> u128 {
+    Balances::free_balance(address_to_account_id(owner))
+}
+
+fn allowance(owner: H160, spender: H160) -> Result<Vec<u8>, PrecompileFailure> {
+    call_token(
+        address(1),
+        "allowance(address,address)",
+        &[
+            AbiValue::Word(address_word(owner)),
+            AbiValue::Word(address_word(spender)),
+        ],
+    )
+}
+
+fn transfer_from(
+    spender: H160,
+    from: H160,
+    to: H160,
+    value: u128,
+) -> Result<Vec<u8>, PrecompileFailure> {
+    call_token(
+        spender,
+        "transferFrom(address,address,uint256)",
+        &[
+            AbiValue::Word(address_word(from)),
+            AbiValue::Word(address_word(to)),
+            AbiValue::uint(value),
+        ],
+    )
+}
+
+#[test]
+fn unknown_selectors_revert() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call_token(address(1), "mint(address,uint256)", &[]),
+            Err(revert("unknown selector"))
+        );
+        assert_eq!(
+            call(NATIVE_TOKEN_INDEX, address(1), Vec::new()),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn malformed_arguments_fail() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call_token(address(1), "balanceOf(address)", &[max_word()]),
+            Err(revert("invalid address"))
+        );
+        assert_eq!(
+            call_token(
+                address(1),
+                "transfer(address,uint256)",
+                &[AbiValue::Word(address_word(address(2)))]
+            ),
+            Err(invalid_range())
+        );
+        assert_eq!(
+            call_token(
+                address(1),
+                "transfer(address,uint256)",
+                &[AbiValue::Word(address_word(address(2))), max_word()]
+            ),
+            Err(revert("value does not fit 128 bits"))
+        );
+    });
+}
+
+#[test]
+fn metadata_and_supply_are_read_from_the_chain() {
+    new_test_ext().execute_with(|| {
+        fund(address(1), as_tors(5));
+
+        assert_eq!(
+            call_token(address(1), "name()", &[]),
+            Ok(encode_abi(&[AbiValue::Bytes(b"Torus".to_vec())]))
+        );
+        assert_eq!(
+            call_token(address(1), "symbol()", &[]),
+            Ok(encode_abi(&[AbiValue::Bytes(b"TORUS".to_vec())]))
+        );
+        assert_eq!(
+            call_token(address(1), "decimals()", &[]),
+            Ok(encode_u128(18))
+        );
+        assert_eq!(
+            call_token(address(1), "totalSupply()", &[]),
+            Ok(encode_u128(Balances::total_issuance()))
+        );
+        assert_eq!(
+            call_token(
+                address(2),
+                "balanceOf(address)",
+                &[AbiValue::Word(address_word(address(1)))]
+            ),
+            Ok(encode_u128(as_tors(5)))
+        );
+    });
+}
+
+#[test]
+fn transfers_move_the_callers_tokens() {
+    new_test_ext().execute_with(|| {
+        let from = address(10);
+        let to = address(11);
+        fund(from, as_tors(100));
+
+        let mut handle = MockHandle::new(
+            NATIVE_TOKEN_INDEX,
+            from,
+            input(
+                "transfer(address,uint256)",
+                &[
+                    AbiValue::Word(address_word(to)),
+                    AbiValue::uint(as_tors(10)),
+                ],
+            ),
+        );
+        assert_eq!(
+            handle.execute().map(|output| output.output),
+            Ok(encode_bool(true))
+        );
+        assert_eq!(balance_of(from), as_tors(90));
+        assert_eq!(balance_of(to), as_tors(10));
+        assert_eq!(
+            handle.logs,
+            alloc::vec![(
+                address(NATIVE_TOKEN_INDEX),
+                alloc::vec![
+                    event_topic("Transfer(address,address,uint256)"),
+                    H256(address_word(from)),
+                    H256(address_word(to)),
+                ],
+                encode_u128(as_tors(10)),
+            )]
+        );
+
+        assert!(matches!(
+            call_token(
+                to,
+                "transfer(address,uint256)",
+                &[
+                    AbiValue::Word(address_word(from)),
+                    AbiValue::uint(as_tors(20))
+                ]
+            ),
+            Err(PrecompileFailure::Revert { .. })
+        ));
+        assert_eq!(
+            MockHandle::new(
+                NATIVE_TOKEN_INDEX,
+                from,
+                input(
+                    "transfer(address,uint256)",
+                    &[AbiValue::Word(address_word(to)), AbiValue::uint(1)]
+                ),
+            )
+            .with_value(1)
+            .execute(),
+            Err(revert("function is not payable"))
+        );
+    });
+}
+
+#[test]
+fn transfers_from_spend_the_callers_allowance() {
+    new_test_ext().execute_with(|| {
+        let owner = address(10);
+        let spender = address(11);
+        let recipient = address(12);
+        fund(owner, as_tors(100));
+
+        assert_eq!(
+            call_token(
+                owner,
+                "approve(address,uint256)",
+                &[
+                    AbiValue::Word(address_word(spender)),
+                    AbiValue::uint(as_tors(5))
+                ]
+            ),
+            Ok(encode_bool(true))
+        );
+        assert_eq!(allowance(owner, spender), Ok(encode_u128(as_tors(5))));
+        assert_eq!(allowance(spender, owner), Ok(encode_u128(0)));
+
+        // The allowance belongs to the spender, not to other callers.
+        assert_eq!(
+            transfer_from(recipient, owner, recipient, as_tors(1)),
+            Err(revert("insufficient allowance"))
+        );
+
+        assert_eq!(
+            transfer_from(spender, owner, recipient, as_tors(3)),
+            Ok(encode_bool(true))
+        );
+        assert_eq!(balance_of(owner), as_tors(97));
+        assert_eq!(balance_of(recipient), as_tors(3));
+        assert_eq!(allowance(owner, spender), Ok(encode_u128(as_tors(2))));
+
+        assert_eq!(
+            transfer_from(spender, owner, recipient, as_tors(3)),
+            Err(revert("insufficient allowance"))
+        );
+        assert_eq!(balance_of(recipient), as_tors(3));
+    });
+}
+
+#[test]
+fn unlimited_allowances_are_not_spent() {
+    new_test_ext().execute_with(|| {
+        let owner = address(10);
+        let spender = address(11);
+        fund(owner, as_tors(100));
+
+        assert_eq!(
+            call_token(
+                owner,
+                "approve(address,uint256)",
+                &[AbiValue::Word(address_word(spender)), max_word()]
+            ),
+            Ok(encode_bool(true))
+        );
+        assert_eq!(allowance(owner, spender), Ok(alloc::vec![u8::MAX; 32]));
+
+        assert_eq!(
+            transfer_from(spender, owner, spender, as_tors(3)),
+            Ok(encode_bool(true))
+        );
+        assert_eq!(allowance(owner, spender), Ok(alloc::vec![u8::MAX; 32]));
+    });
+}
+
+#[test]
+fn value_is_forwarded_to_substrate_accounts() {
+    new_test_ext().execute_with(|| {
+        let destination = account(1);
+        let transfer = || input("transfer(bytes32)", &[AbiValue::bytes32(&destination)]);
+
+        // Calls without value do nothing.
+        assert_eq!(
+            call(NATIVE_TOKEN_INDEX, address(10), transfer()).map(|output| output.output),
+            Ok(Vec::new())
+        );
+        assert_eq!(Balances::free_balance(&destination), 0);
+
+        // The EVM moves the value to the precompile before calling it.
+        fund(address(NATIVE_TOKEN_INDEX), as_tors(10));
+        assert_eq!(
+            MockHandle::new(NATIVE_TOKEN_INDEX, address(10), transfer())
+                .with_value(as_tors(5))
+                .execute()
+                .map(|output| output.output),
+            Ok(Vec::new())
+        );
+        assert_eq!(Balances::free_balance(&destination), as_tors(5));
+        assert_eq!(balance_of(address(NATIVE_TOKEN_INDEX)), as_tors(5));
+    });
+}
+
+#[test]
+fn delegate_and_static_calls_cannot_move_tokens() {
+    new_test_ext().execute_with(|| {
+        let owner = address(10);
+        let contract = address(20);
+        fund(owner, as_tors(100));
+
+        // A contract delegating to the precompile would otherwise spend the
+        // balance of whoever called it.
+        let transfer = || {
+            MockHandle::new(
+                NATIVE_TOKEN_INDEX,
+                owner,
+                input(
+                    "transfer(address,uint256)",
+                    &[
+                        AbiValue::Word(address_word(contract)),
+                        AbiValue::uint(as_tors(100)),
+                    ],
+                ),
+            )
+        };
+        assert_eq!(
+            transfer().delegated_by(contract).execute(),
+            Err(revert("cannot be called through delegatecall or callcode"))
+        );
+        assert_eq!(
+            transfer().static_call().execute(),
+            Err(revert("cannot modify state in a static call"))
+        );
+        assert_eq!(balance_of(owner), as_tors(100));
+        assert_eq!(balance_of(contract), 0);
+
+        let approve = MockHandle::new(
+            NATIVE_TOKEN_INDEX,
+            owner,
+            input(
+                "approve(address,uint256)",
+                &[AbiValue::Word(address_word(contract)), max_word()],
+            ),
+        )
+        .delegated_by(contract)
+        .execute();
+        assert_eq!(
+            approve,
+            Err(revert("cannot be called through delegatecall or callcode"))
+        );
+        assert_eq!(allowance(owner, contract), Ok(encode_u128(0)));
+
+        // Reads are allowed in static calls.
+        assert_eq!(
+            MockHandle::new(
+                NATIVE_TOKEN_INDEX,
+                contract,
+                input("balanceOf(address)", &[AbiValue::Word(address_word(owner))]),
+            )
+            .static_call()
+            .execute()
+            .map(|output| output.output),
+            Ok(encode_u128(as_tors(100)))
+        );
+    });
+}
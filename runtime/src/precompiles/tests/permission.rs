@@ -0,0 +1,368 @@
+use alloc::vec::Vec;
+
+use pallet_evm::PrecompileFailure;
+use pallet_permission0::Permissions;
+use pallet_permission0_api::generate_root_stream_id;
+use polkadot_sdk::{
+    sp_core::{H160, H256},
+    sp_runtime::AccountId32,
+};
+
+use super::{
+    MockHandle, account, address, call, input, invalid_range, new_test_ext, register_agent,
+};
+use crate::{
+    Balances, Runtime,
+    configs::as_tors,
+    precompiles::{
+        AbiValue, PERMISSION_INDEX, address_to_account_id, encode_u128, event_topic, revert,
+        u128_word,
+    },
+};
+
+const DELEGATE_FIXED_AMOUNT: &str =
+    "delegateFixedAmountStream(bytes32[],uint16[],uint256,uint8,uint256,uint256,uint8,uint256)";
+const DELEGATE_PERCENTAGE: &str = "delegatePercentageStream(bytes32[],uint16[],bytes32[],uint8[],uint8,uint256,uint256,uint8,uint256)";
+
+/// Delegates `amount` to the recipients, distributed manually, with the
+/// given revocation kind.
+fn delegate_fixed_amount(
+    recipients: &[(&AccountId32, u128)],
+    amount: u128,
+    revocation: u128,
+) -> Vec<u8> {
+    input(
+        DELEGATE_FIXED_AMOUNT,
+        &[
+            AbiValue::Array(
+                recipients
+                    .iter()
+                    .map(|(recipient, _)| *AsRef::<[u8; 32]>::as_ref(*recipient))
+                    .collect(),
+            ),
+            AbiValue::Array(
+                recipients
+                    .iter()
+                    .map(|(_, weight)| u128_word(*weight))
+                    .collect(),
+            ),
+            AbiValue::uint(amount),
+            AbiValue::uint(0),
+            AbiValue::uint(0),
+            AbiValue::uint(0),
+            AbiValue::uint(revocation),
+            AbiValue::uint(0),
+        ],
+    )
+}
+
+fn call_with_id(
+    caller: H160,
+    signature: &str,
+    permission_id: H256,
+) -> Result<Vec<u8>, PrecompileFailure> {
+    call(
+        PERMISSION_INDEX,
+        caller,
+        input(signature, &[AbiValue::Word(permission_id.0)]),
+    )
+    .map(|output| output.output)
+}
+
+/// Registers the delegator mapped to `caller` and a recipient agent,
+/// returning both.
+fn register_agents(caller: H160) -> (AccountId32, AccountId32) {
+    let delegator = address_to_account_id(caller);
+    register_agent(&delegator, "alice");
+
+    let recipient = account(2);
+    register_agent(&recipient, "bob");
+
+    (delegator, recipient)
+}
+
+#[test]
+fn unknown_selectors_revert() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(PERMISSION_INDEX, address(1), input("permissions()", &[])),
+            Err(revert("unknown selector"))
+        );
+        assert_eq!(
+            call(PERMISSION_INDEX, address(1), alloc::vec![0xff; 2]),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn malformed_arguments_fail() {
+    new_test_ext().execute_with(|| {
+        let recipient = account(2);
+        let delegate = |args: &[AbiValue]| {
+            call(
+                PERMISSION_INDEX,
+                address(1),
+                input(DELEGATE_FIXED_AMOUNT, args),
+            )
+        };
+        let terms = |distribution: u128, revocation: u128| {
+            [
+                AbiValue::uint(distribution),
+                AbiValue::uint(0),
+                AbiValue::uint(0),
+                AbiValue::uint(revocation),
+                AbiValue::uint(0),
+            ]
+        };
+        let recipients = || AbiValue::Array(alloc::vec![*AsRef::<[u8; 32]>::as_ref(&recipient)]);
+
+        let mut args = alloc::vec![recipients(), AbiValue::Array(Vec::new()), AbiValue::uint(1)];
+        args.extend(terms(0, 1));
+        assert_eq!(
+            delegate(&args),
+            Err(revert("recipients and weights length mismatch"))
+        );
+
+        let mut args = alloc::vec![
+            recipients(),
+            AbiValue::Array(alloc::vec![u128_word(
+                u128::from(u16::MAX).saturating_add(1)
+            )]),
+            AbiValue::uint(1)
+        ];
+        args.extend(terms(0, 1));
+        assert_eq!(delegate(&args), Err(revert("invalid weight")));
+
+        let weights = || AbiValue::Array(alloc::vec![u128_word(1)]);
+
+        let mut args = alloc::vec![recipients(), weights(), AbiValue::uint(1)];
+        args.extend(terms(4, 1));
+        assert_eq!(delegate(&args), Err(revert("invalid distribution kind")));
+
+        // Arbiter revocation is only available through the extrinsic.
+        let mut args = alloc::vec![recipients(), weights(), AbiValue::uint(1)];
+        args.extend(terms(0, 3));
+        assert_eq!(delegate(&args), Err(revert("invalid revocation kind")));
+
+        let stream = generate_root_stream_id(&account(1));
+        let delegate_percentage = |percentages: Vec<[u8; 32]>| {
+            let mut args = alloc::vec![
+                recipients(),
+                weights(),
+                AbiValue::Array(alloc::vec![stream.0]),
+                AbiValue::Array(percentages),
+            ];
+            args.extend(terms(0, 1));
+            call(
+                PERMISSION_INDEX,
+                address(1),
+                input(DELEGATE_PERCENTAGE, &args),
+            )
+        };
+        assert_eq!(
+            delegate_percentage(Vec::new()),
+            Err(revert("streams and percentages length mismatch"))
+        );
+        assert_eq!(
+            delegate_percentage(alloc::vec![u128_word(101)]),
+            Err(revert("invalid percentage"))
+        );
+
+        assert_eq!(
+            call(
+                PERMISSION_INDEX,
+                address(1),
+                input("executePermission(bytes32)", &[])
+            ),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn calls_revert_with_the_dispatch_error() {
+    new_test_ext().execute_with(|| {
+        let caller = address(10);
+        let recipient = account(2);
+        register_agent(&recipient, "bob");
+
+        // The caller is not a registered agent.
+        assert_eq!(
+            call(
+                PERMISSION_INDEX,
+                caller,
+                delegate_fixed_amount(&[(&recipient, 1)], as_tors(1), 1)
+            ),
+            Err(revert("NotRegisteredAgent"))
+        );
+        assert_eq!(
+            call_with_id(caller, "executePermission(bytes32)", H256::zero()),
+            Err(revert("PermissionNotFound"))
+        );
+        assert_eq!(
+            MockHandle::new(
+                PERMISSION_INDEX,
+                caller,
+                input("revokePermission(bytes32)", &[AbiValue::Word([0; 32])])
+            )
+            .with_value(1)
+            .execute(),
+            Err(revert("function is not payable"))
+        );
+    });
+}
+
+#[test]
+fn streams_are_delegated_and_executed_as_the_caller() {
+    new_test_ext().execute_with(|| {
+        let caller = address(10);
+        let (delegator, recipient) = register_agents(caller);
+        let amount = as_tors(1);
+
+        let mut handle = MockHandle::new(
+            PERMISSION_INDEX,
+            caller,
+            delegate_fixed_amount(&[(&recipient, 1)], amount, 1),
+        );
+        let output = handle.execute().expect("delegation failed").output;
+        let permission_id = H256::from_slice(&output);
+
+        let contract = Permissions::<Runtime>::get(permission_id).expect("permission exists");
+        assert_eq!(contract.delegator, delegator);
+        assert_eq!(Balances::reserved_balance(&delegator), amount);
+
+        let delegator_topic = H256(*AsRef::<[u8; 32]>::as_ref(&delegator));
+        assert_eq!(
+            handle.logs,
+            alloc::vec![(
+                address(PERMISSION_INDEX),
+                alloc::vec![
+                    event_topic("StreamPermissionDelegated(bytes32,bytes32)"),
+                    permission_id,
+                    delegator_topic,
+                ],
+                Vec::new(),
+            )]
+        );
+
+        let permission = call_with_id(caller, "getPermission(bytes32)", permission_id)
+            .expect("permission is readable");
+        assert_eq!(permission.get(..32), Some(encode_u128(1).as_slice()));
+        assert_eq!(
+            permission.get(32..64),
+            Some(AsRef::<[u8; 32]>::as_ref(&delegator).as_slice())
+        );
+
+        // Only the delegator can trigger the distribution.
+        assert_eq!(
+            call_with_id(address(11), "executePermission(bytes32)", permission_id),
+            Err(revert("NotPermissionDelegator"))
+        );
+
+        let recipient_balance = Balances::free_balance(&recipient);
+        assert_eq!(
+            call_with_id(caller, "executePermission(bytes32)", permission_id),
+            Ok(Vec::new())
+        );
+        assert_eq!(Balances::reserved_balance(&delegator), 0);
+        assert_eq!(
+            Balances::free_balance(&recipient),
+            recipient_balance.saturating_add(amount)
+        );
+    });
+}
+
+#[test]
+fn streams_are_revoked_as_the_caller() {
+    new_test_ext().execute_with(|| {
+        let caller = address(10);
+        let (delegator, recipient) = register_agents(caller);
+
+        let output = call(
+            PERMISSION_INDEX,
+            caller,
+            delegate_fixed_amount(&[(&recipient, 1)], as_tors(1), 1),
+        )
+        .expect("delegation failed")
+        .output;
+        let permission_id = H256::from_slice(&output);
+
+        assert_eq!(
+            call_with_id(address(11), "revokePermission(bytes32)", permission_id),
+            Err(revert("NotAuthorizedToRevoke"))
+        );
+        assert!(Permissions::<Runtime>::contains_key(permission_id));
+
+        assert_eq!(
+            call_with_id(caller, "revokePermission(bytes32)", permission_id),
+            Ok(Vec::new())
+        );
+        assert!(!Permissions::<Runtime>::contains_key(permission_id));
+        assert_eq!(Balances::reserved_balance(&delegator), 0);
+    });
+}
+
+#[test]
+fn stream_state_is_read_from_storage() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+
+        assert_eq!(
+            call(
+                PERMISSION_INDEX,
+                address(1),
+                input("rootStreamId(bytes32)", &[AbiValue::bytes32(&agent)])
+            )
+            .map(|output| output.output),
+            Ok(generate_root_stream_id(&agent).as_bytes().to_vec())
+        );
+
+        let permission = call_with_id(address(1), "getPermission(bytes32)", H256::zero())
+            .expect("permission is readable");
+        assert_eq!(permission.get(..32), Some(encode_u128(0).as_slice()));
+
+        assert_eq!(
+            call(
+                PERMISSION_INDEX,
+                address(1),
+                input(
+                    "getAccumulatedAmount(bytes32,bytes32,bytes32)",
+                    &[
+                        AbiValue::bytes32(&agent),
+                        AbiValue::Word(generate_root_stream_id(&agent).0),
+                        AbiValue::Word([0; 32]),
+                    ]
+                )
+            )
+            .map(|output| output.output),
+            Ok(encode_u128(0))
+        );
+    });
+}
+
+#[test]
+fn delegate_and_static_calls_cannot_delegate_streams() {
+    new_test_ext().execute_with(|| {
+        let caller = address(10);
+        let (delegator, recipient) = register_agents(caller);
+
+        let delegate = || {
+            MockHandle::new(
+                PERMISSION_INDEX,
+                caller,
+                delegate_fixed_amount(&[(&recipient, 1)], as_tors(1), 1),
+            )
+        };
+        assert_eq!(
+            delegate().delegated_by(address(20)).execute(),
+            Err(revert("cannot be called through delegatecall or callcode"))
+        );
+        assert_eq!(
+            delegate().static_call().execute(),
+            Err(revert("cannot modify state in a static call"))
+        );
+        assert_eq!(Permissions::<Runtime>::iter().count(), 0);
+        assert_eq!(Balances::reserved_balance(&delegator), 0);
+    });
+}
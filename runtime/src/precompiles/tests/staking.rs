@@ -0,0 +1,258 @@
+use alloc::vec::Vec;
+
+use pallet_torus0::{MinAllowedStake, StakingTo};
+use polkadot_sdk::sp_runtime::AccountId32;
+
+use super::{
+    MockHandle, account, address, call, fund, input, invalid_range, max_word, new_test_ext,
+    register_agent,
+};
+use crate::{
+    Runtime,
+    configs::as_tors,
+    precompiles::{AbiValue, STAKING_INDEX, encode_u128, revert},
+};
+
+fn stake_input(signature: &str, agent: &AccountId32, amount: u128) -> Vec<u8> {
+    input(
+        signature,
+        &[AbiValue::bytes32(agent), AbiValue::uint(amount)],
+    )
+}
+
+#[test]
+fn unknown_selectors_revert() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(
+            call(STAKING_INDEX, address(1), input("stake(bytes32)", &[])),
+            Err(revert("unknown selector"))
+        );
+        assert_eq!(
+            call(STAKING_INDEX, address(1), alloc::vec![0xde, 0xad]),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn malformed_arguments_fail() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                address(1),
+                input("addStake(bytes32,uint256)", &[AbiValue::bytes32(&agent)])
+            ),
+            Err(invalid_range())
+        );
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                address(1),
+                input(
+                    "addStake(bytes32,uint256)",
+                    &[AbiValue::bytes32(&agent), max_word()]
+                )
+            ),
+            Err(revert("value does not fit 128 bits"))
+        );
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                address(1),
+                input("getStake(bytes32,bytes32)", &[AbiValue::bytes32(&agent)])
+            ),
+            Err(invalid_range())
+        );
+    });
+}
+
+#[test]
+fn calls_revert_with_the_dispatch_error() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+        let caller = address(10);
+        fund(caller, as_tors(100));
+
+        let min_stake = MinAllowedStake::<Runtime>::get();
+        let add_stake = "addStake(bytes32,uint256)";
+
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                caller,
+                stake_input(add_stake, &agent, min_stake)
+            ),
+            Err(revert("AgentDoesNotExist"))
+        );
+
+        register_agent(&agent, "alice");
+
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                caller,
+                stake_input(add_stake, &agent, min_stake.saturating_sub(1))
+            ),
+            Err(revert("StakeTooSmall"))
+        );
+        assert_eq!(
+            MockHandle::new(
+                STAKING_INDEX,
+                caller,
+                stake_input(add_stake, &agent, min_stake)
+            )
+            .with_value(1)
+            .execute(),
+            Err(revert("function is not payable"))
+        );
+        assert_eq!(StakingTo::<Runtime>::iter().count(), 0);
+    });
+}
+
+#[test]
+fn stake_is_moved_as_the_caller() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+        let other_agent = account(2);
+        register_agent(&agent, "alice");
+        register_agent(&other_agent, "bob");
+
+        let caller = address(10);
+        let staker = fund(caller, as_tors(100));
+        let other_caller = address(11);
+        fund(other_caller, as_tors(100));
+
+        let amount = as_tors(10);
+        let stake = stake_input("addStake(bytes32,uint256)", &agent, amount);
+        assert!(call(STAKING_INDEX, caller, stake).is_ok());
+
+        assert_eq!(StakingTo::<Runtime>::get(&staker, &agent), Some(amount));
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                caller,
+                input(
+                    "getStake(bytes32,bytes32)",
+                    &[AbiValue::bytes32(&staker), AbiValue::bytes32(&agent)]
+                )
+            )
+            .map(|output| output.output),
+            Ok(encode_u128(amount))
+        );
+
+        // Other callers cannot move the caller's stake.
+        let remove = stake_input("removeStake(bytes32,uint256)", &agent, amount);
+        assert_eq!(
+            call(STAKING_INDEX, other_caller, remove),
+            Err(revert("NotEnoughStakeToWithdraw"))
+        );
+
+        let transfer = input(
+            "transferStake(bytes32,bytes32,uint256)",
+            &[
+                AbiValue::bytes32(&agent),
+                AbiValue::bytes32(&other_agent),
+                AbiValue::uint(amount),
+            ],
+        );
+        assert!(call(STAKING_INDEX, caller, transfer).is_ok());
+
+        assert_eq!(StakingTo::<Runtime>::get(&staker, &agent), None);
+        assert_eq!(
+            StakingTo::<Runtime>::get(&staker, &other_agent),
+            Some(amount)
+        );
+        assert_eq!(
+            call(
+                STAKING_INDEX,
+                caller,
+                input("getTotalStaked(bytes32)", &[AbiValue::bytes32(&staker)])
+            )
+            .map(|output| output.output),
+            Ok(encode_u128(amount))
+        );
+
+        let remove = stake_input("removeStake(bytes32,uint256)", &other_agent, amount);
+        assert!(call(STAKING_INDEX, caller, remove).is_ok());
+        assert_eq!(StakingTo::<Runtime>::get(&staker, &other_agent), None);
+    });
+}
+
+#[test]
+fn reading_stakes_charges_each_stake() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+        register_agent(&agent, "alice");
+
+        let total_stake = || {
+            let mut handle = MockHandle::new(
+                STAKING_INDEX,
+                address(1),
+                input("getTotalStake(bytes32)", &[AbiValue::bytes32(&agent)]),
+            );
+            let output = handle.execute().map(|output| output.output);
+            (output, handle.gas_used)
+        };
+
+        let (output, empty_gas) = total_stake();
+        assert_eq!(output, Ok(encode_u128(0)));
+
+        for id in 10..13 {
+            let caller = address(id);
+            fund(caller, as_tors(100));
+            let stake = stake_input("addStake(bytes32,uint256)", &agent, as_tors(1));
+            assert!(call(STAKING_INDEX, caller, stake).is_ok());
+        }
+
+        let (output, gas) = total_stake();
+        assert_eq!(output, Ok(encode_u128(as_tors(3))));
+        assert!(gas > empty_gas);
+    });
+}
+
+#[test]
+fn delegate_and_static_calls_cannot_move_stake() {
+    new_test_ext().execute_with(|| {
+        let agent = account(1);
+        register_agent(&agent, "alice");
+
+        let caller = address(10);
+        let staker = fund(caller, as_tors(100));
+        let stake = || stake_input("addStake(bytes32,uint256)", &agent, as_tors(10));
+
+        // A contract delegating to the precompile would stake its caller's
+        // balance on its own behalf.
+        assert_eq!(
+            MockHandle::new(STAKING_INDEX, caller, stake())
+                .delegated_by(address(20))
+                .execute(),
+            Err(revert("cannot be called through delegatecall or callcode"))
+        );
+        assert_eq!(
+            MockHandle::new(STAKING_INDEX, caller, stake())
+                .static_call()
+                .execute(),
+            Err(revert("cannot modify state in a static call"))
+        );
+        assert_eq!(StakingTo::<Runtime>::iter().count(), 0);
+
+        // Reads are allowed in static calls.
+        assert_eq!(
+            MockHandle::new(
+                STAKING_INDEX,
+                caller,
+                input(
+                    "getStake(bytes32,bytes32)",
+                    &[AbiValue::bytes32(&staker), AbiValue::bytes32(&agent)]
+                )
+            )
+            .static_call()
+            .execute()
+            .map(|output| output.output),
+            Ok(encode_u128(0))
+        );
+    });
+}
@@ -138,6 +138,9 @@ impl pallet_torus0::Config for Test {
 
     type DefaultAgentUpdateCooldown = ConstU64<32_400>;
 
+    type KeyRotationDelay = ConstU64<100>;
+    type MaxAgentProxies = ConstU32<8>;
+
     #[doc = " The storage MaxNameLength should be constrained to be no more than the value of this."]
     #[doc = " This is needed on agent::Agent to set the `name` field BoundedVec max length."]
     type MaxAgentNameLengthConstraint = ConstU32<256>;
@@ -169,6 +172,7 @@ parameter_types! {
     pub MaxSupply: NonZeroU128 = NonZeroU128::new(as_tors(144_000_000 * 4)).unwrap();
     pub const DefaultEmissionRecyclingPercentage: Percent = Percent::from_parts(70);
     pub const DefaultIncentivesRatio: Percent = Percent::from_parts(50);
+    pub const DefaultBondsMovingAverage: Percent = Percent::one();
 }
 
 impl pallet_emission0::Config for Test {
@@ -184,6 +188,15 @@ impl pallet_emission0::Config for Test {
 
     type DefaultIncentivesRatio = DefaultIncentivesRatio;
 
+    type DefaultBondsMovingAverage = DefaultBondsMovingAverage;
+
+    type MaxEpochReports = ConstU32<10>;
+    type MaxConsensusAgents = ConstU32<1_024>;
+
+    type MaxWeightDelegationDepth = ConstU32<3>;
+
+    type MaxWeightDelegatees = ConstU32<4>;
+
     type Currency = Balances;
 
     type Torus = Torus0;
@@ -229,6 +242,11 @@ impl pallet_governance::Config for Test {
 
     type DefaultProposalRewardInterval = ConstU64<75_600>;
 
+    type MaxCuratorActions = ConstU32<10>;
+
+    type MaxRebalancedDelegations = ConstU32<2>;
+    type MaxVoteDelegators = ConstU32<2>;
+
     type RuntimeEvent = RuntimeEvent;
 
     type Currency = Balances;
@@ -271,6 +289,10 @@ impl pallet_permission0::Config for Test {
     type MaxChildrenPerPermission = ConstU32<10>;
     type MaxCuratorSubpermissionsPerPermission = ConstU32<10>;
     type MaxBulkOperationsPerCall = ConstU32<20>;
+    type MaxStreamDelegationDepth = ConstU32<3>;
+    type MaxStreamGraphWalk = ConstU32<64>;
+    type MaxEscrowMilestones = ConstU32<4>;
+    type EnforcementVotingPeriod = ConstU64<100>;
 }
 
 impl pallet_balances::Config for Test {
@@ -475,6 +497,7 @@ pub fn delegate_curator_permission(
         pallet_permission0::PermissionDuration::Indefinite,
         pallet_permission0::RevocationTerms::Irrevocable,
         1,
+        Default::default(),
     )
     .expect("failed to register curator");
 }